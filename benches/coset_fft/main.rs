@@ -0,0 +1,34 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use sirius::{
+    ff::Field,
+    group::ff::WithSmallOrderMulGroup,
+    halo2curves::bn256::Fr,
+    polynomial::{lagrange, univariate::UnivariatePoly},
+};
+
+const LOG_DEGREE: u32 = 10;
+
+/// Values of polynomial on coset `zeta * {1, omega, omega^2, ...}`, like `G(X)` in `compute_K`
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut rnd = rand::thread_rng();
+    let poly = UnivariatePoly::from_iter((0..1 << LOG_DEGREE).map(|_| Fr::random(&mut rnd)));
+
+    let mut group = c.benchmark_group("coset_evaluation_1024");
+
+    group.bench_function("eval_in_each_point", |b| {
+        b.iter(|| {
+            lagrange::iter_cyclic_subgroup::<Fr>(LOG_DEGREE)
+                .map(|X| black_box(&poly).eval(Fr::ZETA * X))
+                .collect::<Box<[_]>>()
+        })
+    });
+
+    group.bench_function("coset_fft", |b| {
+        b.iter(|| black_box(&poly).coset_fft(LOG_DEGREE).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use sirius::{ff::Field, halo2curves::bn256::Fr, polynomial::univariate::UnivariatePoly};
+
+/// Lengths of operands around `KARATSUBA_THRESHOLD` (32) & `FFT_THRESHOLD` (256)
+const LENGTHS: [usize; 8] = [8, 16, 32, 64, 128, 256, 512, 1024];
+
+/// Product of two polynomials of the same length by each algorithm, to find where `Mul` should
+/// switch from one to another
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut rnd = rand::thread_rng();
+    let mut random_poly =
+        |len: usize| UnivariatePoly::from_iter((0..len).map(|_| Fr::random(&mut rnd)));
+
+    let mut group = c.benchmark_group("poly_mul");
+
+    for len in LENGTHS {
+        let (lhs, rhs) = (random_poly(len), random_poly(len));
+
+        group.bench_with_input(BenchmarkId::new("schoolbook", len), &len, |b, _| {
+            b.iter(|| black_box(&lhs).mul_schoolbook(black_box(&rhs)))
+        });
+        group.bench_with_input(BenchmarkId::new("karatsuba", len), &len, |b, _| {
+            b.iter(|| black_box(&lhs).mul_karatsuba(black_box(&rhs)))
+        });
+        group.bench_with_input(BenchmarkId::new("fft", len), &len, |b, _| {
+            b.iter(|| black_box(&lhs).mul_fft(black_box(&rhs)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -1,7 +1,7 @@
 use std::{
     fs::{self, File},
     io::{self, Read, Write},
-    iter, ops,
+    iter, mem, ops,
     ops::Not,
     path::Path,
     slice,
@@ -12,13 +12,16 @@ use halo2_proofs::{
     arithmetic::{best_multiexp, CurveAffine, CurveExt},
     plonk::ConstraintSystem,
 };
-use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use sha3::Shake256;
 use some_to_err::*;
 use tracing::*;
 
-use crate::{group::Curve, util::parallelize};
+use crate::{
+    group::Curve,
+    metrics,
+    util::{parallel::*, parallelize},
+};
 
 #[derive(Debug, PartialEq, Eq, thiserror::Error)]
 pub enum Error {
@@ -58,27 +61,70 @@ impl<C: CurveAffine> CommitmentKey<C> {
         assert!(k < 32);
         let n: usize = 1 << k;
 
+        CommitmentKey {
+            ck: Self::generate(label, 0, n),
+        }
+    }
+
+    /// Grows the key in place up to `2^required_k` generators
+    ///
+    /// Generators are taken sequentially from the XOF stream of `label`, so the key of smaller size
+    /// is a prefix of the key of bigger size with the same `label`. Only missing generators are
+    /// computed and existing ones are kept, so commitments of inputs that fit into the old key are
+    /// unchanged.
+    ///
+    /// `label` must be the same as the one used in [`CommitmentKey::setup`]. If the key already
+    /// has enough generators, nothing happens.
+    pub fn ensure_capacity(&mut self, required_k: u32, label: &'static [u8]) {
+        assert!(required_k < 32);
+        let n: usize = 1 << required_k;
+
+        if self.ck.len() >= n {
+            return;
+        }
+
+        debug!("grow commitment key from {} to {n}", self.ck.len());
+
+        let mut ck = mem::take(&mut self.ck).into_vec();
+        let missing = Self::generate(label, ck.len(), n - ck.len());
+        ck.extend_from_slice(&missing);
+
+        self.ck = ck.into_boxed_slice();
+    }
+
+    /// Generates `count` generators, skipping the first `skip` ones from the XOF stream of `label`
+    fn generate(label: &'static [u8], skip: usize, count: usize) -> Box<[C]> {
         let mut reader = Shake256::default().chain(label).finalize_xof();
 
+        io::copy(
+            &mut Read::by_ref(&mut reader).take((skip * 32) as u64),
+            &mut io::sink(),
+        )
+        .unwrap();
+
         let ck_proj: Box<[_]> = iter::repeat_with(|| {
             let mut buffer = [0u8; 32];
             reader.read_exact(&mut buffer).unwrap();
             buffer
         })
-        .take(n)
+        .take(count)
         .par_bridge()
         .map(|uniform_byte| (C::CurveExt::hash_to_curve("from_uniform_bytes"))(&uniform_byte))
         .collect();
 
-        let mut ck: Box<[C]> = iter::repeat(C::identity()).take(n).collect();
+        let mut ck: Box<[C]> = iter::repeat(C::identity()).take(count).collect();
         parallelize(&mut ck, |(ck, start)| {
             C::Curve::batch_normalize(&ck_proj[start..start + ck.len()], ck);
         });
 
-        CommitmentKey { ck }
+        ck
     }
 
+    #[instrument(skip_all, level = "debug", fields(len = v.len()))]
     pub fn commit(&self, v: &[C::Scalar]) -> Result<C, Error> {
+        metrics::increment_counter(metrics::COMMIT_TOTAL, 1);
+        metrics::record_histogram(metrics::MSM_SIZE, v.len() as f64);
+
         if self.ck.len() >= v.len() {
             Ok(best_multiexp(v, &self.ck[..v.len()]).to_affine())
         } else {
@@ -212,3 +258,35 @@ mod file_tests {
         assert_eq!(key, loaded);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tracing_test::traced_test;
+
+    use super::*;
+    use crate::{
+        ff::Field,
+        halo2curves::bn256::{Fr, G1Affine},
+    };
+
+    #[traced_test]
+    #[test]
+    fn ensure_capacity() {
+        let mut rnd = rand::thread_rng();
+        let input = iter::repeat_with(|| Fr::random(&mut rnd))
+            .take(1 << 4)
+            .collect::<Box<[_]>>();
+
+        let mut key = CommitmentKey::<G1Affine>::setup(4, b"grow");
+        let commitment = key.commit(&input).unwrap();
+
+        key.ensure_capacity(6, b"grow");
+
+        assert_eq!(key.len(), 1 << 6);
+        assert_eq!(key.commit(&input).unwrap(), commitment);
+        assert_eq!(key, CommitmentKey::setup(6, b"grow"));
+
+        key.ensure_capacity(5, b"grow");
+        assert_eq!(key.len(), 1 << 6);
+    }
+}
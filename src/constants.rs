@@ -10,3 +10,8 @@ pub(crate) const MAX_BITS: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(2
 pub(crate) const NUM_HASH_BITS: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(250) };
 // SAFETY: Safe because value non zero
 pub(crate) const NUM_CHALLENGE_BITS: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(128) };
+
+/// Version of the folding transcript: order & encoding of everything absorbed into random oracles
+///
+/// Must be bumped with any change of challenges for the same inputs
+pub const TRANSCRIPT_VERSION: u32 = 1;
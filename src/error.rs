@@ -2,6 +2,54 @@ use std::mem;
 
 use halo2_proofs::plonk::Error;
 
+use crate::{commitment, ivc, nifs, plonk, sps};
+
+/// Crate-wide error type
+///
+/// Aggregates errors of all modules, so applications embedding sirius can use `?` for any of
+/// them. Module errors stay public & can be precisely matched through the variants.
+#[derive(Debug, thiserror::Error)]
+pub enum SiriusError {
+    #[error(transparent)]
+    Halo2(#[from] halo2_proofs::plonk::Error),
+    #[error(transparent)]
+    Commitment(#[from] commitment::Error),
+    #[error(transparent)]
+    Eval(#[from] plonk::eval::Error),
+    #[error(transparent)]
+    Plonk(#[from] plonk::Error),
+    #[error(transparent)]
+    Sps(#[from] sps::Error),
+    #[error(transparent)]
+    StepSynthesis(#[from] ivc::step_circuit::SynthesisError),
+    #[error("Error while protogalaxy folding: {0}")]
+    ProtoGalaxy(#[from] nifs::protogalaxy::Error),
+    #[error("Error while sangria folding: {0}")]
+    Sangria(#[from] nifs::sangria::Error),
+    #[error("Error while IVC: {0}")]
+    IVC(#[from] ivc::Error),
+    #[error("Error while create public params of IVC: {0}")]
+    PublicParams(#[from] ivc::PublicParamsError),
+}
+
+impl From<nifs::protogalaxy::poly::Error> for SiriusError {
+    fn from(err: nifs::protogalaxy::poly::Error) -> Self {
+        Self::ProtoGalaxy(err.into())
+    }
+}
+
+impl From<nifs::protogalaxy::poly::PolyContextError> for SiriusError {
+    fn from(err: nifs::protogalaxy::poly::PolyContextError) -> Self {
+        Self::ProtoGalaxy(err.into())
+    }
+}
+
+impl From<ivc::protogalaxy::VerifyChipError> for SiriusError {
+    fn from(err: ivc::protogalaxy::VerifyChipError) -> Self {
+        Self::ProtoGalaxy(err.into())
+    }
+}
+
 /// Wrapper for [`halo2_proofs::plonk::Error`] to
 /// impl [`PartialEq`] & [`Eq`] and be able to use
 /// it in [`assert_eq`] and other comparisons
@@ -36,3 +84,40 @@ impl From<halo2_proofs::plonk::Error> for Halo2PlonkError {
         Self(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use super::SiriusError;
+    use crate::{
+        nifs::protogalaxy::{self, poly},
+        plonk::eval,
+    };
+
+    #[test]
+    fn nested_eval_error() {
+        let eval_err = eval::Error::RowIndexOutOfBoundary { row_index: 10 };
+        let err = SiriusError::from(poly::Error::Eval(eval_err.clone()));
+
+        assert!(matches!(
+            &err,
+            SiriusError::ProtoGalaxy(protogalaxy::Error::Poly(poly::Error::Eval(
+                eval::Error::RowIndexOutOfBoundary { row_index: 10 }
+            )))
+        ));
+
+        let source = err
+            .source()
+            .and_then(|source| source.downcast_ref::<protogalaxy::Error>());
+        assert!(matches!(
+            source,
+            Some(protogalaxy::Error::Poly(poly::Error::Eval(inner))) if inner.eq(&eval_err)
+        ));
+        assert_eq!(source.unwrap().to_string(), eval_err.to_string());
+        assert_eq!(
+            err.to_string(),
+            format!("Error while protogalaxy folding: {eval_err}")
+        );
+    }
+}
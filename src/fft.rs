@@ -3,7 +3,7 @@ use crate::{
     ff::{Field, PrimeField},
     group::{ff::WithSmallOrderMulGroup, GroupOpsOwned, ScalarMulOwned},
     polynomial::univariate::UnivariatePoly,
-    util,
+    util::{self, parallel},
 };
 
 /// Given FFT domain size k, return the omega in case of fft
@@ -59,7 +59,7 @@ fn bitreverse(input: usize, limit: usize) -> usize {
 ///
 /// This will use multithreading if beneficial.
 pub(crate) fn best_fft<Scalar: Field, G: FftGroup<Scalar>>(a: &mut [G], omega: Scalar, log_n: u32) {
-    let threads = rayon::current_num_threads();
+    let threads = parallel::current_num_threads();
     let log_threads = threads.ilog2();
     let n = a.len();
     assert_eq!(n, 1 << log_n);
@@ -128,7 +128,7 @@ pub(crate) fn recursive_butterfly_arithmetic<Scalar: Field, G: FftGroup<Scalar>>
         a[1] -= &t;
     } else {
         let (left, right) = a.split_at_mut(n / 2);
-        rayon::join(
+        parallel::join(
             || recursive_butterfly_arithmetic(left, n / 2, twiddle_chunk * 2, twiddles),
             || recursive_butterfly_arithmetic(right, n / 2, twiddle_chunk * 2, twiddles),
         );
@@ -197,6 +197,37 @@ pub fn coset_ifft<F: WithSmallOrderMulGroup<3>>(a: &mut [F]) -> UnivariatePoly<F
     UnivariatePoly(a.to_vec().into_boxed_slice())
 }
 
+/// FFT implementation used by polynomial computations of [`crate::nifs::protogalaxy`]
+///
+/// All methods take input of size `1 << log_n` and work in-place
+pub trait FftBackend<F: PrimeField>: Send + Sync {
+    /// Coefficients into values on the cyclic subgroup `{1, omega, omega^2, ...}`
+    fn fft(&self, a: &mut [F]);
+    /// Values on the cyclic subgroup into coefficients
+    fn ifft(&self, a: &mut [F]);
+    /// Values on the coset `zeta * {1, omega, omega^2, ...}` into coefficients
+    fn coset_ifft(&self, a: &mut [F]);
+}
+
+/// [`FftBackend`] of this module: [`fft`], [`ifft`] & [`coset_ifft`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultFftBackend;
+
+impl<F: WithSmallOrderMulGroup<3>> FftBackend<F> for DefaultFftBackend {
+    fn fft(&self, a: &mut [F]) {
+        fft(a)
+    }
+
+    fn ifft(&self, a: &mut [F]) {
+        ifft(a)
+    }
+
+    fn coset_ifft(&self, a: &mut [F]) {
+        ifft(a);
+        distribute_powers_zeta(a, F::ZETA, F::ZETA.square(), false);
+    }
+}
+
 /// Given a slice of group elements `[a_0, a_1, a_2, ...]`, this returns
 /// `[a_0, [zeta]a_1, [zeta^2]a_2, a_3, [zeta]a_4, [zeta^2]a_5, a_6, ...]`,
 /// where zeta is a cube root of unity in the multiplicative subgroup with
@@ -204,7 +235,7 @@ pub fn coset_ifft<F: WithSmallOrderMulGroup<3>>(a: &mut [F]) -> UnivariatePoly<F
 ///
 /// `into_coset` should be set to `true` when moving into the coset,
 /// and `false` when moving out. This toggles the choice of `zeta`.
-fn distribute_powers_zeta<F: PrimeField>(
+pub(crate) fn distribute_powers_zeta<F: PrimeField>(
     input: &mut [F],
     g_coset: F,
     g_coset_inv: F,
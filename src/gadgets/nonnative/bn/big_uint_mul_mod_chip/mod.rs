@@ -64,6 +64,10 @@ impl<F: PrimeField> BigUintMulModChip<F> {
         }
     }
 
+    pub fn limb_width(&self) -> NonZeroUsize {
+        self.limb_width
+    }
+
     pub fn to_bignat(&self, input: &BigUintRaw) -> Result<BigUint<F>, Error> {
         Ok(BigUint::<F>::from_biguint(
             input,
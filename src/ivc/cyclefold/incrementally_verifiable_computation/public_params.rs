@@ -163,6 +163,11 @@ where
                     &nifs::protogalaxy::ProverParam {
                         S: primary_cr.try_collect_plonk_structure().unwrap(),
                         pp_digest: CMain::identity(),
+                        fft: None,
+                        parallelism: None,
+                        #[cfg(feature = "memory-observer")]
+                        memory: None,
+                        progress: None,
                     },
                     &mut ro(),
                 )
@@ -15,4 +15,4 @@ mod public_params;
 
 pub use halo2_proofs::circuit::SimpleFloorPlanner;
 pub use incrementally_verifiable_computation::*;
-pub use public_params::{CircuitPublicParamsInput, PublicParams};
+pub use public_params::{CircuitPublicParamsInput, Error as PublicParamsError, PublicParams};
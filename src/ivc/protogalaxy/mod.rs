@@ -1,11 +1,22 @@
+#[cfg(feature = "shadow-verify")]
+pub use verify_chip::shadow;
+pub use verify_chip::Error as VerifyChipError;
+
 mod verify_chip {
-    use std::iter;
+    use std::{collections::BTreeMap, iter, num::NonZeroUsize};
 
     use itertools::Itertools;
     use tracing::*;
 
     use crate::{
-        gadgets::ecc::AssignedPoint,
+        constants::NUM_CHALLENGE_BITS,
+        gadgets::{
+            ecc::{AssignedPoint, EccChip},
+            nonnative::bn::{
+                big_uint::{self, BigUint},
+                big_uint_mul_mod_chip::{BigUintMulModChip, OverflowingBigUint},
+            },
+        },
         halo2_proofs::{
             arithmetic::Field,
             circuit::{AssignedCell, Chip, Value as Halo2Value},
@@ -16,16 +27,18 @@ mod verify_chip {
             plonk::Error as Halo2PlonkError,
         },
         main_gate::{
-            AdviceCyclicAssignor, AssignedValue, MainGate, MainGateConfig, RegionCtx, WrapValue,
+            AdviceCyclicAssignor, AdviceCyclicCopier, AssignedValue, MainGate, MainGateConfig,
+            RegionCtx, WrapValue,
         },
         nifs::protogalaxy::{
             self,
             poly::{PolyChallenges, PolyContext},
         },
-        plonk::PlonkInstance,
+        plonk::{PlonkInstance, PlonkTraceArgs},
         polynomial::{lagrange::iter_cyclic_subgroup, univariate::UnivariatePoly},
-        poseidon::ROCircuitTrait,
-        util::ScalarToBase,
+        poseidon::{ROCircuitTrait, ROPair},
+        sps::{sps_challenges, SpsAbsorber},
+        util,
     };
 
     #[derive(Debug, thiserror::Error)]
@@ -36,6 +49,15 @@ mod verify_chip {
             err: Halo2PlonkError,
         },
 
+        /// Scalar field value can't be represented in the base field of the circuit
+        ///
+        /// `index` is the position of the value among flattened values of `annotation`
+        #[error("Error while convert {annotation}[{index}] from scalar to base field")]
+        ConversionFailed {
+            annotation: &'static str,
+            index: usize,
+        },
+
         #[error("Error while squeeze challenges: {err:?}")]
         Squeeze { err: Halo2PlonkError },
 
@@ -48,12 +70,142 @@ mod verify_chip {
         #[error("Error while calculate new `e`: {err:?}")]
         WhileE { err: Halo2PlonkError },
 
+        #[error("Error while eval `F(alpha)` with {len} coefficients: {err:?}")]
+        EvalPolyF { len: usize, err: Halo2PlonkError },
+
+        #[error("Error while eval `K(gamma)` with {len} coefficients: {err:?}")]
+        EvalPolyK { len: usize, err: Halo2PlonkError },
+
+        #[error("Error while eval `L_{index}(gamma)`: {err:?}")]
+        EvalLagrange { index: usize, err: Halo2PlonkError },
+
+        #[error("Error while eval `Z(gamma) = gamma^{degree} - 1`: {err:?}")]
+        EvalVanishing { degree: usize, err: Halo2PlonkError },
+
         #[error("Error while fold instancess: {err:?}")]
         Fold { err: Halo2PlonkError },
 
         #[allow(clippy::upper_case_acronyms)]
         #[error("SPS Verify Error: {err:?}")]
         SPS { err: Halo2PlonkError },
+
+        /// Known value of the squeezed challenge differs from the assigned one
+        #[error(
+            "SPS mismatch of challenge {challenge_index} of incoming instance {instance_index}"
+        )]
+        SPSMismatch {
+            instance_index: usize,
+            challenge_index: usize,
+        },
+
+        /// Instance doesn't match the expected [`InstanceShape`]
+        ///
+        /// For `num_io` it's either the count of instance columns or the length of the first
+        /// mismatched column
+        #[error("Shape mismatch of {field}: expected {expected}, got {got}")]
+        ShapeMismatch {
+            field: &'static str,
+            expected: usize,
+            got: usize,
+        },
+    }
+
+    /// Expected shape of [`PlonkInstance`], derived from [`PlonkTraceArgs`]
+    ///
+    /// [`verify`] expects it from the accumulator & all incoming instances
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct InstanceShape {
+        pub num_io: Box<[usize]>,
+        pub num_challenges: usize,
+        pub num_witness_commitments: usize,
+    }
+
+    impl From<&PlonkTraceArgs> for InstanceShape {
+        fn from(args: &PlonkTraceArgs) -> Self {
+            Self {
+                num_io: args.num_io.clone(),
+                num_challenges: args.num_challenges,
+                num_witness_commitments: args.num_witness,
+            }
+        }
+    }
+
+    impl<C: CurveAffine> From<&AssignedPlonkInstance<C>> for InstanceShape {
+        fn from(instance: &AssignedPlonkInstance<C>) -> Self {
+            Self {
+                num_io: instance.instances.iter().map(Vec::len).collect(),
+                num_challenges: instance.challenges.len(),
+                num_witness_commitments: instance.W_commitments.len(),
+            }
+        }
+    }
+
+    fn check_shape(field: &'static str, expected: usize, got: usize) -> Result<(), Error> {
+        if expected == got {
+            Ok(())
+        } else {
+            Err(Error::ShapeMismatch {
+                field,
+                expected,
+                got,
+            })
+        }
+    }
+
+    fn check_num_io(expected: &[usize], got: &[usize]) -> Result<(), Error> {
+        check_shape("num_io", expected.len(), got.len())?;
+        expected
+            .iter()
+            .zip_eq(got)
+            .try_for_each(|(expected, got)| check_shape("num_io", *expected, *got))
+    }
+
+    impl InstanceShape {
+        fn check(
+            &self,
+            num_witness_commitments: usize,
+            num_io: &[usize],
+            num_challenges: usize,
+        ) -> Result<(), Error> {
+            check_shape(
+                "W_commitments",
+                self.num_witness_commitments,
+                num_witness_commitments,
+            )?;
+            check_num_io(&self.num_io, num_io)?;
+            check_shape("challenges", self.num_challenges, num_challenges)
+        }
+
+        /// Checks that `instance` is of this shape
+        pub fn check_assigned<C: CurveAffine>(
+            &self,
+            instance: &AssignedPlonkInstance<C>,
+        ) -> Result<(), Error> {
+            let InstanceShape {
+                num_io,
+                num_challenges,
+                num_witness_commitments,
+            } = InstanceShape::from(instance);
+
+            self.check(num_witness_commitments, &num_io, num_challenges)
+        }
+    }
+
+    /// Converts scalars into the base field without reduction
+    ///
+    /// A value out of the base field range would silently change after reduction, so it is
+    /// reported as [`Error::ConversionFailed`] instead
+    fn scalars_to_base<'l, C: CurveAffine>(
+        annotation: &'static str,
+        scalars: impl IntoIterator<Item = &'l C::ScalarExt>,
+    ) -> Result<Vec<C::Base>, Error> {
+        scalars
+            .into_iter()
+            .enumerate()
+            .map(|(index, scalar)| {
+                util::fe_to_fe_safe(scalar).ok_or(Error::ConversionFailed { annotation, index })
+            })
+            .collect()
     }
 
     /// Assigned version of [`crate::plonk::PlonkInstance`]
@@ -64,6 +216,24 @@ mod verify_chip {
     }
 
     impl<C: CurveAffine> AssignedPlonkInstance<C> {
+        /// [`Self::assign`] with the check of `pi` against `shape` before anything is assigned
+        ///
+        /// Mismatched instances would otherwise panic later in [`fold_instances`]
+        pub fn assign_checked<const T: usize>(
+            region: &mut RegionCtx<C::Base>,
+            main_gate_config: MainGateConfig<T>,
+            pi: PlonkInstance<C>,
+            shape: &InstanceShape,
+        ) -> Result<Self, Error> {
+            shape.check(
+                pi.W_commitments.len(),
+                &pi.instances.iter().map(Vec::len).collect::<Box<[_]>>(),
+                pi.challenges.len(),
+            )?;
+
+            Self::assign(region, main_gate_config, pi)
+        }
+
         pub fn assign<const T: usize>(
             region: &mut RegionCtx<C::Base>,
             main_gate_config: MainGateConfig<T>,
@@ -75,36 +245,29 @@ mod verify_chip {
                 challenges,
             } = pi;
 
+            let instances_len = instances.iter().map(Vec::len).collect::<Vec<_>>();
+            let instances_values =
+                scalars_to_base::<C>("PlonkInstance::instances", instances.iter().flatten())?;
+            let challenges = scalars_to_base::<C>("PlonkInstance::challenges", &challenges)?;
+
             let mut assigner = main_gate_config.advice_cycle_assigner();
 
-            let W_commitments = W_commitments
-                .iter()
-                .enumerate()
-                .map(|(i, W_commitment)| {
-                    assigner.assign_next_advice_point(
-                        region,
-                        || format!("W_commitments[{i}]"),
-                        W_commitment,
-                    )
-                })
-                .collect::<Result<Vec<_>, _>>();
+            let W_commitments =
+                assigner.assign_all_advice_points(region, || "W_commitments", W_commitments.iter());
 
-            let instances = instances
-                .iter()
-                .map(|instance| {
+            let mut instances_values = instances_values.into_iter();
+            let instances = instances_len
+                .into_iter()
+                .map(|len| {
                     assigner.assign_all_advice(
                         region,
                         || "instance",
-                        instance.iter().map(|i| C::scalar_to_base(i).unwrap()),
+                        instances_values.by_ref().take(len),
                     )
                 })
                 .collect::<Result<Vec<_>, _>>();
 
-            let challenges = assigner.assign_all_advice(
-                region,
-                || "challenges",
-                challenges.iter().map(|i| C::scalar_to_base(i).unwrap()),
-            );
+            let challenges = assigner.assign_all_advice(region, || "challenges", challenges);
 
             let map_err = |err| Error::Assign {
                 annotation: "PlonkInstance",
@@ -118,6 +281,103 @@ mod verify_chip {
             })
         }
 
+        /// Assigns the instance of the IVC base case: identity `W_commitments`, zero `instances`
+        /// & `challenges`, the same as [`PlonkInstance::new`] with the same arguments
+        ///
+        /// Identity is assigned as `(0, 0)`, so every cell is constrained to be zero
+        pub fn assign_zero<const T: usize>(
+            region: &mut RegionCtx<C::Base>,
+            main_gate_config: MainGateConfig<T>,
+            num_io: &[usize],
+            num_challenges: usize,
+            num_witness: usize,
+        ) -> Result<Self, Error> {
+            let zero = Self::assign(
+                region,
+                main_gate_config.clone(),
+                PlonkInstance::new(num_io, num_challenges, num_witness),
+            )?;
+            region.next();
+
+            let main_gate = MainGate::new(main_gate_config);
+            zero.W_commitments
+                .iter()
+                .flat_map(|W| [&W.x, &W.y])
+                .chain(zero.instances.iter().flatten())
+                .chain(zero.challenges.iter())
+                .try_for_each(|cell| {
+                    main_gate.assert_equal_const(region, cell.clone(), C::Base::ZERO)
+                })
+                .map_err(|err| Error::Assign {
+                    annotation: "PlonkInstance::zero",
+                    err,
+                })?;
+
+            Ok(zero)
+        }
+
+        /// Assigns a copy of every cell of `self` in `region`, constrained to the original one
+        ///
+        /// The cells of [`fold_instances`] belong to the folding region, with the copy the folded
+        /// instance can be used in another region, e.g. as the accumulator of the next `verify`
+        pub fn reassign_into<const T: usize>(
+            &self,
+            region: &mut RegionCtx<C::Base>,
+            main_gate_config: MainGateConfig<T>,
+        ) -> Result<Self, Error> {
+            // Same placement as the assigner of `Self::assign`
+            let mut assigner = main_gate_config.advice_cycle_assigner();
+            let mut copy = |region: &mut RegionCtx<C::Base>, cell: &AssignedValue<C::Base>| {
+                assigner.copy_next_advice(region, || "PlonkInstance copy", cell)
+            };
+
+            let map_err = |err| Error::Assign {
+                annotation: "PlonkInstance",
+                err,
+            };
+
+            let W_commitments = self
+                .W_commitments
+                .iter()
+                .map(|W_commitment| {
+                    Ok(AssignedPoint {
+                        x: copy(region, &W_commitment.x)?,
+                        y: copy(region, &W_commitment.y)?,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(map_err)?;
+
+            let instances = self
+                .instances
+                .iter()
+                .map(|instance| {
+                    instance
+                        .iter()
+                        .map(|value| copy(region, value))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(map_err)?;
+
+            let challenges = self
+                .challenges
+                .iter()
+                .map(|challenge| copy(region, challenge))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(map_err)?;
+
+            Ok(Self {
+                W_commitments,
+                instances,
+                challenges,
+            })
+        }
+
+        /// Order of absorption into the random oracle: `x` & `y` of each of `W_commitments`,
+        /// then `instances` column by column, then `challenges`
+        ///
+        /// The random oracle depends on this order, so it must not change
         pub fn iter_wrap_value(&self) -> impl '_ + Iterator<Item = WrapValue<C::Base>> {
             let Self {
                 W_commitments,
@@ -139,6 +399,49 @@ mod verify_chip {
                         .map(|challenge| WrapValue::Assigned(challenge.clone())),
                 )
         }
+
+        /// Mirror of [`Self::iter_wrap_value`]: the same elements in reverse order, starting with
+        /// the last challenge & ending with `y` then `x` of the first commitment
+        pub fn iter_wrap_value_rev(&self) -> impl '_ + Iterator<Item = WrapValue<C::Base>> {
+            let Self {
+                W_commitments,
+                instances,
+                challenges,
+            } = self;
+
+            challenges
+                .iter()
+                .rev()
+                .map(|challenge| WrapValue::Assigned(challenge.clone()))
+                .chain(instances.iter().rev().flat_map(|instance| {
+                    instance
+                        .iter()
+                        .rev()
+                        .map(|value| WrapValue::Assigned(value.clone()))
+                }))
+                .chain(W_commitments.iter().rev().flat_map(|W_commitment| {
+                    WrapValue::from_assigned_point(W_commitment)
+                        .into_iter()
+                        .rev()
+                }))
+        }
+
+        /// Returns the assigned instance & challenge cells, so the caller can constrain them
+        /// against cells of its own columns
+        ///
+        /// Order: all `instances` flattened column by column (in the order of
+        /// [`PlonkInstance::instances`]), followed by all `challenges`. `W_commitments` are not
+        /// included, since they are represented by points, not single cells.
+        ///
+        /// This matches the order of the non-point part of [`Self::iter_wrap_value`]
+        pub fn export_cells(&self) -> Vec<AssignedCell<C::Base, C::Base>> {
+            self.instances
+                .iter()
+                .flatten()
+                .chain(self.challenges.iter())
+                .cloned()
+                .collect()
+        }
     }
 
     /// Assigned version of [`crate::nifs::protogalaxy::accumulator::AccumulatorInstance`]
@@ -158,18 +461,14 @@ mod verify_chip {
 
             let ins = AssignedPlonkInstance::assign(region, main_gate_config.clone(), ins)?;
 
+            let betas = scalars_to_base::<C>("AccumulatorInstance::betas", betas.iter())?;
+            let e = scalars_to_base::<C>("AccumulatorInstance::e", [&e])?[0];
+
             let mut assigner = main_gate_config.advice_cycle_assigner();
 
-            // Convert and assign `betas`
             let betas = betas
-                .iter()
-                .map(|beta| {
-                    assigner.assign_next_advice(
-                        region,
-                        || "beta",
-                        C::scalar_to_base(beta).unwrap(), // assuming conversion to base field is needed
-                    )
-                })
+                .into_iter()
+                .map(|beta| assigner.assign_next_advice(region, || "beta", beta))
                 .collect::<Result<Vec<_>, _>>()
                 .map_err(|err| Error::Assign {
                     annotation: "AccumulatorInstance::betas",
@@ -177,9 +476,8 @@ mod verify_chip {
                 })?
                 .into_boxed_slice();
 
-            // Convert and assign `e`
             let e = assigner
-                .assign_next_advice(region, || "e", C::scalar_to_base(&e).unwrap())
+                .assign_next_advice(region, || "e", e)
                 .map_err(|err| Error::Assign {
                     annotation: "AccumulatorInstance::e",
                     err,
@@ -204,18 +502,91 @@ mod verify_chip {
     /// x^0, x^1, x^2, x^3, ... x^i, ...
     /// ```
     pub struct ValuePowers<F: PrimeField> {
+        /// `x^i` for each `i` in `0..powers.len()`
         powers: Vec<AssignedValue<F>>,
+        /// `x^i` for some `i > powers.len()`, evaluated by [`Self::get_or_eval`] without the
+        /// powers in between
+        sparse: BTreeMap<usize, AssignedValue<F>>,
     }
 
     impl<F: PrimeField> ValuePowers<F> {
-        pub fn new(one: AssignedValue<F>, value: AssignedValue<F>) -> Self {
-            if let Some(one) = one.value().unwrap() {
-                assert_eq!(one, &F::ONE);
-            }
+        /// `one` is constrained to be equal to [`PrimeField::ONE`]
+        pub fn new<const T: usize>(
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            one: AssignedValue<F>,
+            value: AssignedValue<F>,
+        ) -> Result<Self, Halo2PlonkError> {
+            // one * 1 - 1 = 0
+            main_gate.apply(
+                region,
+                (
+                    Some(vec![F::ONE]),
+                    None,
+                    Some(vec![WrapValue::Assigned(one.clone())]),
+                ),
+                Some(-F::ONE),
+                (F::ZERO, Halo2Value::known(F::ZERO).into()),
+            )?;
 
-            Self {
+            Ok(Self {
                 powers: vec![one, value],
+                sparse: BTreeMap::new(),
+            })
+        }
+
+        /// Same as [`Self::new`], but a known `one` not equal to [`PrimeField::ONE`] is reported
+        /// as [`Halo2PlonkError::Synthesis`] at once, instead of an unsatisfied constraint
+        pub fn try_new<const T: usize>(
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            one: AssignedValue<F>,
+            value: AssignedValue<F>,
+        ) -> Result<Self, Halo2PlonkError> {
+            // Unknown during keygen, then only the constraint is left
+            if one.value().unwrap().is_some_and(|one| *one != F::ONE) {
+                error!("`one` cell of `ValuePowers` is {:?}", one.value());
+                return Err(Halo2PlonkError::Synthesis);
             }
+
+            Self::new(region, main_gate, one, value)
+        }
+
+        /// Assign the `one` cell in place and constrain it to be equal to [`PrimeField::ONE`]
+        pub fn with_assigned_one<const T: usize>(
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            value: AssignedValue<F>,
+        ) -> Result<Self, Halo2PlonkError> {
+            Self::with_one_value(region, main_gate, Halo2Value::known(F::ONE), value)
+        }
+
+        fn with_one_value<const T: usize>(
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            one: Halo2Value<F>,
+            value: AssignedValue<F>,
+        ) -> Result<Self, Halo2PlonkError> {
+            // 1 - one = 0
+            let one = main_gate.apply(
+                region,
+                (None, None, None),
+                Some(F::ONE),
+                (-F::ONE, one.into()),
+            )?;
+
+            Ok(Self {
+                powers: vec![one, value],
+                sparse: BTreeMap::new(),
+            })
+        }
+
+        /// Constrained `one` cell, can be shared with other [`ValuePowers`]
+        pub fn one(&self) -> AssignedValue<F> {
+            self.powers
+                .first()
+                .expect("Cannot be created without at least one element inside")
+                .clone()
         }
 
         pub fn iter(&self) -> impl Iterator<Item = &AssignedValue<F>> {
@@ -229,34 +600,170 @@ mod verify_chip {
                 .clone()
         }
 
+        fn get(&self, exp: usize) -> Option<AssignedValue<F>> {
+            self.powers
+                .get(exp)
+                .or_else(|| self.sparse.get(&exp))
+                .cloned()
+        }
+
+        fn insert(&mut self, exp: usize, value: AssignedValue<F>) {
+            if exp != self.powers.len() {
+                self.sparse.insert(exp, value);
+                return;
+            }
+
+            self.powers.push(value);
+            while let Some(value) = self.sparse.remove(&self.powers.len()) {
+                self.powers.push(value);
+            }
+        }
+
         /// Get from cache or calculate the `exp` degree of original value
         ///
         /// `self.value^exp`
         ///
-        /// TODO: Can be improved by using two mult in main_gate
+        /// Starts from the highest cached power `x^h` below `exp` and evaluates `x^{exp - h}` by
+        /// square-and-multiply, so it takes `O(log(exp - h))` multiplications. The powers in
+        /// between aren't evaluated, use [`Self::get_or_eval_all`] if they are needed
         pub fn get_or_eval<const T: usize>(
             &mut self,
             region: &mut RegionCtx<F>,
             main_gate: &MainGate<F, T>,
             exp: usize,
         ) -> Result<AssignedValue<F>, Halo2PlonkError> {
-            if let Some(value) = self.powers.get(exp) {
-                return Ok(value.clone());
+            if let Some(value) = self.get(exp) {
+                return Ok(value);
+            }
+
+            let (highest, highest_value) = self
+                .sparse
+                .range(..exp)
+                .next_back()
+                .map(|(highest, value)| (*highest, value.clone()))
+                .unwrap_or_else(|| {
+                    let highest = self.powers.len() - 1;
+                    (highest, self.powers[highest].clone())
+                });
+
+            let rest = self.eval_by_squaring(region, main_gate, exp - highest)?;
+            let value = main_gate.mul(region, &highest_value, &rest)?;
+            self.insert(exp, value.clone());
+
+            Ok(value)
+        }
+
+        /// `x^exp` as a product of `x^{2^i}` for each bit `i` of `exp`, all intermediate powers
+        /// are cached
+        fn eval_by_squaring<const T: usize>(
+            &mut self,
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            exp: usize,
+        ) -> Result<AssignedValue<F>, Halo2PlonkError> {
+            let mut result = Option::<(usize, AssignedValue<F>)>::None;
+
+            for bit in (0..usize::BITS).filter(|bit| exp >> bit & 1 == 1) {
+                let square = self.get_or_eval_square(region, main_gate, bit)?;
+
+                result = Some(match result {
+                    None => (1 << bit, square),
+                    Some((acc_exp, acc)) => {
+                        let acc_exp = acc_exp + (1 << bit);
+                        let acc = match self.get(acc_exp) {
+                            Some(cached) => cached,
+                            None => {
+                                let acc = main_gate.mul(region, &acc, &square)?;
+                                self.insert(acc_exp, acc.clone());
+                                acc
+                            }
+                        };
+                        (acc_exp, acc)
+                    }
+                });
+            }
+
+            Ok(result.expect("`exp` must be greater than zero").1)
+        }
+
+        /// `x^{2^i}`
+        fn get_or_eval_square<const T: usize>(
+            &mut self,
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            i: u32,
+        ) -> Result<AssignedValue<F>, Halo2PlonkError> {
+            if let Some(value) = self.get(1 << i) {
+                return Ok(value);
             }
 
+            // `x^1` is always cached, so `i > 0` here
+            let half = self.get_or_eval_square(region, main_gate, i - 1)?;
+            let value = main_gate.mul(region, &half, &half)?;
+            self.insert(1 << i, value.clone());
+
+            Ok(value)
+        }
+
+        /// Fill the cache with all powers up to `exp` inclusive, one multiplication per missing
+        /// power, and return them
+        pub fn get_or_eval_all<const T: usize>(
+            &mut self,
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            exp: usize,
+        ) -> Result<&[AssignedValue<F>], Halo2PlonkError> {
             while self.powers.len() <= exp {
                 let value = self.value();
                 let last = self.powers.last().unwrap();
                 let new = main_gate.mul(region, &value, last)?;
-                self.powers.push(new);
+                self.insert(self.powers.len(), new);
             }
 
-            Ok(self.powers.get(exp).cloned().unwrap())
+            Ok(&self.powers[..=exp])
         }
+
+        /// [`Self::get_or_eval`] for each of `exps`
+        ///
+        /// `exps` are evaluated in ascending order, so the smaller ones are reused by the bigger
+        /// ones & the following [`Self::get_or_eval`] calls for them don't assign anything
+        pub fn get_or_eval_many<const T: usize>(
+            &mut self,
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            exps: impl IntoIterator<Item = usize>,
+        ) -> Result<Vec<AssignedValue<F>>, Halo2PlonkError> {
+            let exps = exps.into_iter().collect::<Vec<_>>();
+
+            for exp in exps.iter().copied().sorted() {
+                self.get_or_eval(region, main_gate, exp)?;
+            }
+
+            Ok(exps
+                .into_iter()
+                .map(|exp| self.get(exp).expect("evaluated above"))
+                .collect())
+        }
+    }
+
+    /// How [`AssignedUnivariatePoly`] is evaluated
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PolyEvalMode {
+        /// Sum of `coeff * x^i` over [`ValuePowers`], two coefficients per row
+        ///
+        /// Cheaper when the powers are already evaluated & shared with other polynomials
+        Powers,
+        /// Horner's scheme, one coefficient per row & without the powers
+        Horner,
     }
 
     /// Assigned version of [`crate::polynomial::univariate::UnivariatePoly`]
-    pub struct AssignedUnivariatePoly<F: PrimeField>(UnivariatePoly<AssignedValue<F>>);
+    ///
+    /// Annotation of the assignment is kept to be attached to the spans of evaluations
+    pub struct AssignedUnivariatePoly<F: PrimeField>(
+        UnivariatePoly<AssignedValue<F>>,
+        &'static str,
+    );
 
     impl<F: PrimeField> AssignedUnivariatePoly<F> {
         pub fn assign<const T: usize>(
@@ -265,44 +772,211 @@ mod verify_chip {
             annotation: &'static str,
             poly: &UnivariatePoly<F>,
         ) -> Result<Self, Error> {
-            let up = AssignedUnivariatePoly(UnivariatePoly(
-                main_gate_config
-                    .advice_cycle_assigner()
-                    .assign_all_advice(region, || annotation, poly.iter().copied())
-                    .map_err(|err| Error::Assign { annotation, err })?
-                    .into_boxed_slice(),
-            ));
+            let up = AssignedUnivariatePoly(
+                UnivariatePoly(
+                    main_gate_config
+                        .advice_cycle_assigner()
+                        .assign_all_advice(region, || annotation, poly.coeffs().iter().copied())
+                        .map_err(|err| Error::Assign { annotation, err })?
+                        .into_boxed_slice(),
+                ),
+                annotation,
+            );
 
             region.next();
 
             Ok(up)
         }
 
+        /// Same as [`Self::assign`], but the coefficients are padded with zeros to `target_len`
+        /// cells, so the assigned length doesn't depend on trailing zeros of `poly`
+        ///
+        /// Trailing zeros beyond `target_len` are dropped, only the degree of `poly` must fit
+        pub fn assign_padded<const T: usize>(
+            region: &mut RegionCtx<F>,
+            main_gate_config: MainGateConfig<T>,
+            annotation: &'static str,
+            poly: &UnivariatePoly<F>,
+            target_len: usize,
+        ) -> Result<Self, Error> {
+            if poly.degree() >= target_len {
+                return Err(Error::ShapeMismatch {
+                    field: annotation,
+                    expected: target_len,
+                    got: poly.degree() + 1,
+                });
+            }
+
+            let padded = poly
+                .iter()
+                .copied()
+                .chain(iter::repeat(F::ZERO))
+                .take(target_len)
+                .collect::<UnivariatePoly<F>>();
+
+            Self::assign(region, main_gate_config, annotation, &padded)
+        }
+
         pub fn iter_wrap_value(&self) -> impl '_ + Iterator<Item = WrapValue<F>> {
             debug!("iter wrap value len: {:?}", self.0.len());
 
             self.0
                 .iter()
-                .inspect(|coeff| debug!("coeff {coeff:?}"))
+                .inspect(|coeff| trace!("coeff {coeff:?}"))
                 .map(|coeff| WrapValue::Assigned(coeff.clone()))
         }
 
-        fn degree(&self) -> usize {
-            self.0.len()
+        /// Annotation passed at assignment, e.g. `poly_F`
+        pub fn annotation(&self) -> &'static str {
+            self.1
         }
 
         fn len(&self) -> usize {
             self.0.len()
         }
 
-        pub fn eval<const T: usize>(
+        /// Evaluate by Horner's scheme: `((c_n * x + c_{n-1}) * x + ...) * x + c_0`
+        ///
+        /// Only `x` & the running accumulator are used, nothing is cached in [`ValuePowers`].
+        ///
+        /// For a polynomial of degree `n` it takes `n` rows, while [`Self::eval`] takes
+        /// `ceil((n + 1) / 2)` rows plus up to `n - 1` rows to fill [`ValuePowers`] with `x^n`.
+        /// So this is cheaper when the powers of `x` aren't shared with other polynomials, and
+        /// [`Self::eval`] is cheaper when they are already cached
+        #[instrument(skip_all, fields(annotation = self.1, len = self.len()))]
+        pub fn eval_horner<const T: usize>(
+            &self,
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            x: &AssignedValue<F>,
+        ) -> Result<AssignedValue<F>, Halo2PlonkError> {
+            let (leading, rest) = self.0.split_last().ok_or(Halo2PlonkError::Synthesis)?;
+
+            rest.iter().rev().try_fold(leading.clone(), |acc, coeff| {
+                let output = acc.value().copied() * x.value() + coeff.value();
+
+                main_gate.apply(
+                    region,
+                    (
+                        Some(vec![F::ZERO, F::ZERO, F::ONE]),
+                        Some(vec![F::ONE]),
+                        Some(vec![
+                            WrapValue::Assigned(acc),
+                            WrapValue::Assigned(x.clone()),
+                            WrapValue::Assigned(coeff.clone()),
+                        ]),
+                    ),
+                    None,
+                    (-F::ONE, output.into()),
+                )
+            })
+        }
+
+        /// Evaluate with the chosen [`PolyEvalMode`]
+        pub fn eval_with<const T: usize>(
             &self,
             region: &mut RegionCtx<F>,
             main_gate: &MainGate<F, T>,
             challenge_powers: &mut ValuePowers<F>,
+            mode: PolyEvalMode,
         ) -> Result<AssignedValue<F>, Halo2PlonkError> {
-            let main_gate_config = main_gate.config();
+            match mode {
+                PolyEvalMode::Powers => self.eval(region, main_gate, challenge_powers),
+                PolyEvalMode::Horner => {
+                    let x = challenge_powers.get_or_eval(region, main_gate, 1)?;
+                    self.eval_horner(region, main_gate, &x)
+                }
+            }
+        }
 
+        #[instrument(skip_all, fields(annotation = self.1, len = self.len()))]
+        pub fn eval<const T: usize>(
+            &self,
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            challenge_powers: &mut ValuePowers<F>,
+        ) -> Result<AssignedValue<F>, Halo2PlonkError> {
+            let main_gate_config = main_gate.config();
+
+            challenge_powers.get_or_eval_all(region, main_gate, self.len().saturating_sub(1))?;
+
+            // `challenge_powers` can contain more powers than needed
+            self.0
+                .iter()
+                .zip(challenge_powers.iter())
+                .chunks(2)
+                .into_iter()
+                .try_fold(Option::<AssignedValue<F>>::None, |prev, chunk| {
+                    Self::assign_eval_row(region, main_gate_config, prev, chunk).map(Some)
+                })?
+                .ok_or(Halo2PlonkError::Synthesis)
+        }
+
+        /// [`Self::eval`] in each of `challenge_powers_array`, results are in the same order
+        ///
+        /// Evaluations are done in one pass: for each pair of coefficients the row of every
+        /// challenge is assigned, so the layout of each row is the same as in [`Self::eval`]. The
+        /// main gate has two multiplications per row, so it takes as many rows as `N` separate
+        /// [`Self::eval`] calls
+        #[instrument(skip_all, fields(annotation = self.1, len = self.len(), points = N))]
+        pub fn eval_batch<const T: usize, const N: usize>(
+            &self,
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            mut challenge_powers_array: [&mut ValuePowers<F>; N],
+        ) -> Result<[AssignedValue<F>; N], Halo2PlonkError> {
+            if self.0.is_empty() {
+                return Err(Halo2PlonkError::Synthesis);
+            }
+
+            let main_gate_config = main_gate.config();
+
+            for challenge_powers in challenge_powers_array.iter_mut() {
+                challenge_powers.get_or_eval_all(
+                    region,
+                    main_gate,
+                    self.len().saturating_sub(1),
+                )?;
+            }
+
+            // `challenge_powers` can contain more powers than needed
+            let powers = challenge_powers_array.each_ref().map(|challenge_powers| {
+                challenge_powers
+                    .iter()
+                    .take(self.len())
+                    .collect::<Box<[_]>>()
+            });
+
+            let mut results = [(); N].map(|_| Option::<AssignedValue<F>>::None);
+
+            for (chunk_index, coeffs) in self.0.chunks(2).enumerate() {
+                for (prev, powers) in results.iter_mut().zip(powers.iter()) {
+                    let chunk = coeffs
+                        .iter()
+                        .zip_eq(powers[chunk_index * 2..].iter().take(coeffs.len()).copied());
+
+                    *prev = Some(Self::assign_eval_row(
+                        region,
+                        main_gate_config,
+                        prev.take(),
+                        chunk,
+                    )?);
+                }
+            }
+
+            Ok(results.map(|result| result.expect("poly is not empty")))
+        }
+
+        /// One row of [`Self::eval`]: `prev + sum(coeff * cha_in_power)` over up to two pairs
+        fn assign_eval_row<'l, const T: usize>(
+            region: &mut RegionCtx<F>,
+            main_gate_config: &MainGateConfig<T>,
+            prev: Option<AssignedValue<F>>,
+            chunk: impl Iterator<Item = (&'l AssignedValue<F>, &'l AssignedValue<F>)>,
+        ) -> Result<AssignedValue<F>, Halo2PlonkError>
+        where
+            F: 'l,
+        {
             let enable_selectors = |region: &mut RegionCtx<F>| {
                 [
                     main_gate_config.q_m[0],
@@ -318,70 +992,70 @@ mod verify_chip {
             let prev_col = &main_gate_config.input;
             let result_col = &main_gate_config.out;
 
-            challenge_powers.get_or_eval(region, main_gate, self.len().saturating_sub(1))?;
-
-            self.0
-                .iter()
-                .zip_eq(challenge_powers.iter())
-                .chunks(2)
-                .into_iter()
-                .try_fold(Option::<AssignedValue<F>>::None, |prev, chunks| {
-                    let (coeffs, cha_in_power): (Vec<_>, Vec<_>) = chunks.unzip();
-                    enable_selectors(region)?;
-
-                    let assigned_prev = match prev {
-                        None => {
-                            region.assign_advice(|| "zero", *prev_col, Halo2Value::known(F::ZERO))
-                        }
-                        Some(prev_cell) => region.assign_advice_from(
-                            || "previous chunk values",
-                            *prev_col,
-                            prev_cell,
-                        ),
-                    }?;
+            let (coeffs, cha_in_power): (Vec<_>, Vec<_>) = chunk.unzip();
+            enable_selectors(region)?;
 
-                    let assigned_coeffs = coeffs
-                        .iter()
-                        .zip_eq(coeffs_col)
-                        .map(|(coeff, col)| region.assign_advice_from(|| "coeff", col, *coeff))
-                        .collect::<Result<Box<[_]>, _>>()?;
+            let assigned_prev = match prev {
+                None => region.assign_advice(|| "zero", *prev_col, Halo2Value::known(F::ZERO)),
+                Some(prev_cell) => {
+                    region.assign_advice_from(|| "previous chunk values", *prev_col, prev_cell)
+                }
+            }?;
 
-                    let assigned_cha = cha_in_power
-                        .iter()
-                        .zip_eq(cha_col)
-                        .map(|(cha_in_power, col)| {
-                            region.assign_advice_from(|| "cha", col, *cha_in_power)
-                        })
-                        .collect::<Result<Box<[_]>, _>>()?;
+            let assigned_coeffs = coeffs
+                .iter()
+                .zip_eq(coeffs_col)
+                .map(|(coeff, col)| region.assign_advice_from(|| "coeff", col, *coeff))
+                .collect::<Result<Box<[_]>, _>>()?;
 
-                    let output = assigned_coeffs
-                        .iter()
-                        .zip_eq(assigned_cha.iter())
-                        .fold(assigned_prev.value().copied(), |res, (coeff, cha)| {
-                            res + (coeff.value().copied() * cha.value())
-                        });
+            let assigned_cha = cha_in_power
+                .iter()
+                .zip_eq(cha_col)
+                .map(|(cha_in_power, col)| region.assign_advice_from(|| "cha", col, *cha_in_power))
+                .collect::<Result<Box<[_]>, _>>()?;
 
-                    let assigned_output = region.assign_advice(|| "result", *result_col, output);
+            let output = assigned_coeffs
+                .iter()
+                .zip_eq(assigned_cha.iter())
+                .fold(assigned_prev.value().copied(), |res, (coeff, cha)| {
+                    res + (coeff.value().copied() * cha.value())
+                });
+
+            let assigned_output = region.assign_advice(|| "result", *result_col, output);
+
+            trace!(
+                "coeffs: {:?}; cha_in_power: {:?}, prev: {:?}, output: {:?}",
+                coeffs.iter().map(|cell| cell.value()).collect::<Box<[_]>>(),
+                cha_in_power
+                    .iter()
+                    .map(|cell| cell.value())
+                    .collect::<Box<[_]>>(),
+                assigned_prev.value(),
+                assigned_output
+                    .as_ref()
+                    .ok()
+                    .and_then(|cell| cell.value().unwrap()),
+            );
 
-                    debug!(
-                        "coeffs: {:?}; cha_in_power: {:?}, prev: {:?}, output: {:?}",
-                        coeffs.iter().map(|cell| cell.value()).collect::<Box<[_]>>(),
-                        cha_in_power
-                            .iter()
-                            .map(|cell| cell.value())
-                            .collect::<Box<[_]>>(),
-                        assigned_prev.value(),
-                        assigned_output
-                            .as_ref()
-                            .ok()
-                            .and_then(|cell| cell.value().unwrap()),
-                    );
+            region.next();
 
-                    region.next();
+            assigned_output
+        }
 
-                    assigned_output.map(Some)
-                })?
-                .ok_or(Halo2PlonkError::Synthesis)
+        /// [`Self::eval`] for each of `challenges_powers`, results are in the same order
+        ///
+        /// The coefficients are assigned once by [`Self::assign`], every evaluation only copies
+        /// them, so evaluating at many points costs no more than separate [`Self::eval`] calls
+        pub fn eval_multi<const T: usize>(
+            &self,
+            region: &mut RegionCtx<F>,
+            main_gate: &MainGate<F, T>,
+            challenges_powers: &mut [ValuePowers<F>],
+        ) -> Result<Vec<AssignedValue<F>>, Halo2PlonkError> {
+            challenges_powers
+                .iter_mut()
+                .map(|challenge_powers| self.eval(region, main_gate, challenge_powers))
+                .collect()
         }
     }
 
@@ -448,6 +1122,7 @@ mod verify_chip {
     }
 
     /// Assigned version of [`crate::nifs::protogalaxy::Challenges`]
+    #[derive(Clone)]
     struct AssignedChallanges<F: PrimeField> {
         delta: AssignedValue<F>,
         alpha: AssignedValue<F>,
@@ -455,7 +1130,7 @@ mod verify_chip {
     }
 
     impl<F: PrimeField> AssignedChallanges<F> {
-        #[instrument(skip_all, name = "on_circuit_generate")]
+        #[instrument(skip_all, name = "on_circuit_generate", fields(rows_used = field::Empty))]
         fn generate<C: CurveAffine<Base = F>>(
             region: &mut RegionCtx<C::Base>,
             mut ro_circuit: impl ROCircuitTrait<C::Base>,
@@ -468,6 +1143,8 @@ mod verify_chip {
             C::Base: FromUniformBytes<64> + PrimeFieldBits,
             C::ScalarExt: FromUniformBytes<64> + PrimeFieldBits,
         {
+            let start = region.offset();
+
             let delta = ro_circuit
                 .absorb_point(WrapValue::from_assigned_point(&vp.pp_digest))
                 .absorb_iter(accumulator.iter_wrap_value())
@@ -482,6 +1159,8 @@ mod verify_chip {
                 .absorb_iter(proof.poly_K.iter_wrap_value())
                 .squeeze(region)?;
 
+            record_rows_used(region, start);
+
             Ok(AssignedChallanges {
                 delta,
                 alpha,
@@ -490,6 +1169,12 @@ mod verify_chip {
         }
     }
 
+    /// Records rows assigned since `start` into the `rows_used` field of the current span, for a
+    /// per-phase row breakdown of [`verify`]
+    fn record_rows_used<F: PrimeField>(region: &RegionCtx<F>, start: usize) {
+        Span::current().record("rows_used", region.rows_used(start));
+    }
+
     /// Calculate v, v^2, v^4, v^8 ...
     fn calculate_exponentiation_sequence<F: PrimeField, const T: usize>(
         region: &mut RegionCtx<F>,
@@ -514,16 +1199,20 @@ mod verify_chip {
         .collect::<Result<Box<[_]>, Halo2PlonkError>>()
     }
 
+    #[instrument(skip_all, fields(rows_used = field::Empty))]
     fn calculate_betas_stroke<C: CurveAffine, const T: usize>(
         region: &mut RegionCtx<C::Base>,
         main_gate: &MainGate<C::Base, T>,
         cha: PolyChallenges<AssignedCell<C::Base, C::Base>>,
     ) -> Result<Box<[AssignedCell<C::Base, C::Base>]>, Error> {
+        let start = region.offset();
+
         let deltas =
             calculate_exponentiation_sequence(region, main_gate, cha.delta, cha.betas.len())
                 .map_err(|err| Error::Deltas { err })?;
 
-        cha.betas
+        let betas_stroke = cha
+            .betas
             .iter()
             .zip_eq(deltas)
             .map(|(beta, delta_power)| {
@@ -531,7 +1220,11 @@ mod verify_chip {
                 main_gate.add(region, beta, &alpha_mul_delta)
             })
             .collect::<Result<Box<[_]>, Halo2PlonkError>>()
-            .map_err(|err| Error::BetasStroke { err })
+            .map_err(|err| Error::BetasStroke { err })?;
+
+        record_rows_used(region, start);
+
+        Ok(betas_stroke)
     }
 
     /// Evaluate the values of the Lagrange polynomial for a cyclic subgroup of length `n` (`2.pow(log_n)`) at
@@ -550,7 +1243,8 @@ mod verify_chip {
     /// # Generics
     /// `T` is setup for main gate
     /// - `L`: 'Length' - constant representing the number of instances to
-    ///                   fold in a single `prove`. `L-1` be power of two
+    ///                   fold in a single `prove`. If `L + 1` is not a power of two,
+    ///                   the lagrange domain is padded with zero traces
     fn eval_lagrange_poly<F: PrimeField, const T: usize, const L: usize>(
         region: &mut RegionCtx<F>,
         main_gate: &MainGate<F, T>,
@@ -609,40 +1303,213 @@ mod verify_chip {
     }
 
     // F(alpha) * L(gamma) + Z(gamma) * K(gamma)
+    //
+    // Failure of each evaluation is reported by its own variant of [`Error`], failure of the
+    // final combination - by [`Error::WhileE`]
+    #[instrument(skip_all, fields(rows_used = field::Empty))]
     fn calculate_e<F: PrimeField, const T: usize, const L: usize>(
         region: &mut RegionCtx<F>,
         main_gate: &MainGate<F, T>,
         proof: &AssignedProof<F>,
         gamma_cha: &mut ValuePowers<F>,
         alpha_cha: &mut ValuePowers<F>,
-    ) -> Result<AssignedValue<F>, Halo2PlonkError> {
+    ) -> Result<AssignedValue<F>, Error> {
+        let start = region.offset();
         let lagrange_domain = PolyContext::<F>::get_lagrange_domain::<L>();
+        let vanishing_degree = 1 << lagrange_domain;
+
+        let poly_L0_in_gamma = eval_lagrange_poly::<F, T, L>(region, main_gate, 0, gamma_cha)
+            .map_err(|err| Error::EvalLagrange { index: 0, err })?;
+
+        let poly_F_alpha = proof
+            .poly_F
+            .eval(region, main_gate, alpha_cha)
+            .map_err(|err| Error::EvalPolyF {
+                len: proof.poly_F.len(),
+                err,
+            })?;
+        let poly_Z_gamma = eval_vanish_polynomial(region, main_gate, vanishing_degree, gamma_cha)
+            .map_err(|err| Error::EvalVanishing {
+            degree: vanishing_degree,
+            err,
+        })?;
+        let poly_K_gamma = proof
+            .poly_K
+            .eval(region, main_gate, gamma_cha)
+            .map_err(|err| Error::EvalPolyK {
+                len: proof.poly_K.len(),
+                err,
+            })?;
+
+        let lhs = main_gate
+            .mul(region, &poly_F_alpha, &poly_L0_in_gamma)
+            .map_err(|err| Error::WhileE { err })?;
+        let rhs = main_gate
+            .mul(region, &poly_Z_gamma, &poly_K_gamma)
+            .map_err(|err| Error::WhileE { err })?;
+
+        let e = main_gate
+            .add(region, &lhs, &rhs)
+            .map_err(|err| Error::WhileE { err })?;
+
+        record_rows_used(region, start);
+
+        Ok(e)
+    }
+
+    /// How `W_commitments` are handled while folding instances
+    ///
+    /// Depends on the IVC topology: with a cycle of curves commitments are folded by the secondary
+    /// circuit, with a single curve they can be folded right here
+    pub enum CommitmentFoldMode<'e, C: CurveAffine, const T: usize> {
+        /// Carry commitments of the accumulator forward unfolded, delegate folding to the secondary
+        /// circuit
+        Delegate,
+        /// Fold commitments on-circuit with the ecc gadget
+        ///
+        /// Lagrange polynomials are evaluated over `C::Scalar` with the non-native `bn_chip`, so the
+        /// result is the same as the off-circuit fold
+        OnCircuit {
+            ecc_chip: &'e EccChip<C, MainGate<C::Base, T>>,
+            bn_chip: &'e BigUintMulModChip<C::Base>,
+        },
+    }
+
+    /// Cell constrained to be equal to `value`
+    fn assign_const<F: PrimeField, const T: usize>(
+        region: &mut RegionCtx<F>,
+        main_gate: &MainGate<F, T>,
+        value: F,
+    ) -> Result<AssignedValue<F>, Halo2PlonkError> {
+        main_gate.apply(
+            region,
+            (None, None, None),
+            Some(value),
+            (-F::ONE, WrapValue::Unassigned(Halo2Value::known(value))),
+        )
+    }
+
+    /// Lagrange polynomials `L_0(gamma), ..., L_L(gamma)` over `C::Scalar` as little-endian bits
+    ///
+    /// With `n` points in the lagrange domain `L_i(X) = 1/n * sum_k (omega^{-i} * X)^k`, so only
+    /// powers of `gamma` & products with constants are computed with `bn_chip`, no inversions
+    fn eval_lagrange_bits_in_scalar<C: CurveAffine, const T: usize, const L: usize>(
+        region: &mut RegionCtx<C::Base>,
+        main_gate: &MainGate<C::Base, T>,
+        bn_chip: &BigUintMulModChip<C::Base>,
+        gamma: &AssignedValue<C::Base>,
+    ) -> Result<Vec<Vec<AssignedValue<C::Base>>>, Halo2PlonkError>
+    where
+        C::Base: PrimeFieldBits,
+    {
+        let to_halo2 = |err| {
+            error!("while eval lagrange polys over scalar field: {err:?}");
+            Halo2PlonkError::Synthesis
+        };
+        let assign_const_limbs = |region: &mut RegionCtx<C::Base>, value: C::ScalarExt| {
+            bn_chip
+                .to_bignat(&big_uint::f_to_nat(&value))
+                .map_err(to_halo2)?
+                .limbs()
+                .iter()
+                .map(|limb| assign_const(region, main_gate, *limb))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let modulus: BigUint<C::Base> = bn_chip
+            .to_bignat(&(big_uint::f_to_nat(&-C::ScalarExt::ONE) + 1u32))
+            .map_err(to_halo2)?;
+
+        let lagrange_domain = PolyContext::<C::ScalarExt>::get_lagrange_domain::<L>();
+        let points_count = 1 << lagrange_domain;
+        let points = iter_cyclic_subgroup::<C::ScalarExt>(lagrange_domain).collect::<Box<[_]>>();
+        let inverted_n = C::ScalarExt::from(points_count as u64)
+            .invert()
+            .expect("safe because it's `2^log_n`");
+
+        // gamma^1, ..., gamma^{n-1}
+        let gamma = bn_chip
+            .from_assigned_cell_to_limbs(region, gamma)
+            .map_err(to_halo2)?;
+        let mut powers = vec![gamma.clone()];
+        while powers.len() < points_count - 1 {
+            let next = bn_chip
+                .mult_mod(region, powers.last().unwrap(), &gamma, &modulus)
+                .map_err(to_halo2)?
+                .remainder;
+            powers.push(next);
+        }
 
-        let poly_L0_in_gamma = eval_lagrange_poly::<F, T, L>(region, main_gate, 0, gamma_cha)?;
+        (0..=L)
+            .map(|i| {
+                let mut sum = OverflowingBigUint::new(
+                    assign_const_limbs(region, inverted_n)?,
+                    bn_chip.limb_width(),
+                );
 
-        let poly_F_alpha = proof.poly_F.eval(region, main_gate, alpha_cha)?;
-        let poly_Z_gamma =
-            eval_vanish_polynomial(region, main_gate, 1 << lagrange_domain, gamma_cha)?;
-        let poly_K_gamma = proof.poly_K.eval(region, main_gate, gamma_cha)?;
+                for (k, power) in powers.iter().enumerate().map(|(k, p)| (k + 1, p)) {
+                    // omega^{-i * k} / n
+                    let coeff = points[(points_count - i) * k % points_count] * inverted_n;
+                    let coeff = assign_const_limbs(region, coeff)?;
+
+                    let term = bn_chip
+                        .mult_mod(region, &coeff, power, &modulus)
+                        .map_err(to_halo2)?
+                        .remainder;
+                    sum = bn_chip
+                        .assign_sum(region, &sum, &term)
+                        .map_err(to_halo2)?
+                        .res;
+                }
 
-        let lhs = main_gate.mul(region, &poly_F_alpha, &poly_L0_in_gamma)?;
-        let rhs = main_gate.mul(region, &poly_Z_gamma, &poly_K_gamma)?;
+                let l_i = bn_chip
+                    .red_mod(region, sum, &modulus)
+                    .map_err(to_halo2)?
+                    .remainder;
 
-        main_gate.add(region, &lhs, &rhs)
+                bn_chip.to_le_bits(region, &l_i).map_err(to_halo2)
+            })
+            .collect()
     }
 
-    /// Fold instances, but without on-circuit ecc operations
+    /// Fold instances, `W_commitments` are handled according to `commitment_fold_mode`
+    #[instrument(skip_all, fields(rows_used = field::Empty))]
     fn fold_instances<C: CurveAffine, const T: usize, const L: usize>(
         region: &mut RegionCtx<C::Base>,
         main_gate: &MainGate<C::Base, T>,
         acc: &AssignedPlonkInstance<C>,
         incoming: &[AssignedPlonkInstance<C>; L],
         gamma_cha: &mut ValuePowers<C::Base>,
-    ) -> Result<AssignedPlonkInstance<C>, Halo2PlonkError> {
+        commitment_fold_mode: &CommitmentFoldMode<C, T>,
+    ) -> Result<AssignedPlonkInstance<C>, Halo2PlonkError>
+    where
+        C::Base: PrimeFieldBits,
+    {
+        let start = region.offset();
         let l_0 = eval_lagrange_poly::<C::Base, T, L>(region, main_gate, 0, gamma_cha)?;
 
+        let lagrange_bits = match commitment_fold_mode {
+            CommitmentFoldMode::Delegate => None,
+            CommitmentFoldMode::OnCircuit { ecc_chip, bn_chip } => Some((
+                ecc_chip,
+                eval_lagrange_bits_in_scalar::<C, T, L>(
+                    region,
+                    main_gate,
+                    bn_chip,
+                    &gamma_cha.value(),
+                )?,
+            )),
+        };
+
         let new_acc = AssignedPlonkInstance {
-            W_commitments: acc.W_commitments.clone(), // Don't fold here, delegate it to secondary circuit
+            W_commitments: match &lagrange_bits {
+                None => acc.W_commitments.clone(),
+                Some((ecc_chip, lagrange_bits)) => acc
+                    .W_commitments
+                    .iter()
+                    .map(|W| ecc_chip.scalar_mul(region, W, &lagrange_bits[0]))
+                    .collect::<Result<Vec<_>, _>>()?,
+            },
             instances: acc
                 .instances
                 .iter()
@@ -660,13 +1527,28 @@ mod verify_chip {
                 .collect::<Result<Vec<_>, _>>()?,
         };
 
-        incoming
+        let folded = incoming
             .iter()
             .enumerate()
             .try_fold(new_acc, |mut acc, (index, tr)| {
                 let l_n =
                     eval_lagrange_poly::<C::Base, T, L>(region, main_gate, index + 1, gamma_cha)?;
 
+                if let Some((ecc_chip, lagrange_bits)) = &lagrange_bits {
+                    acc.W_commitments
+                        .iter_mut()
+                        .zip_eq(tr.W_commitments.iter())
+                        .try_for_each(|(acc_W, W)| {
+                            let rhs = ecc_chip.scalar_mul(region, W, &lagrange_bits[index + 1])?;
+
+                            let new = ecc_chip.add(region, acc_W, &rhs)?;
+
+                            *acc_W = new;
+
+                            Result::<_, Halo2PlonkError>::Ok(())
+                        })?;
+                }
+
                 acc.instances
                     .iter_mut()
                     .zip_eq(tr.instances.iter())
@@ -698,31 +1580,120 @@ mod verify_chip {
                     })?;
 
                 Result::<_, Halo2PlonkError>::Ok(acc)
-            })
+            })?;
+
+        record_rows_used(region, start);
+
+        Ok(folded)
+    }
+
+    /// [`SpsAbsorber`] over on-circuit [`ROCircuitTrait`]
+    ///
+    /// Challenges are squeezed as [`NUM_CHALLENGE_BITS`] bits, the same as off-circuit
+    struct OnCircuitSps<'r, 'a, C: CurveAffine, RO, const T: usize> {
+        region: &'r mut RegionCtx<'a, C::Base>,
+        main_gate: &'r MainGate<C::Base, T>,
+        ro_circuit: &'r mut RO,
+    }
+
+    impl<C: CurveAffine, RO, const T: usize> SpsAbsorber for OnCircuitSps<'_, '_, C, RO, T>
+    where
+        C::Base: FromUniformBytes<64> + PrimeFieldBits,
+        RO: ROCircuitTrait<C::Base>,
+    {
+        type Instance = AssignedValue<C::Base>;
+        type Commitment = AssignedPoint<C>;
+        type Challenge = AssignedValue<C::Base>;
+        type Error = Halo2PlonkError;
+
+        fn absorb_instance(&mut self, instance: &AssignedValue<C::Base>) {
+            self.ro_circuit.absorb_base(WrapValue::from(instance));
+        }
+
+        fn absorb_commitment(&mut self, commitment: &AssignedPoint<C>) {
+            self.ro_circuit
+                .absorb_point(WrapValue::from_assigned_point(commitment));
+        }
+
+        fn squeeze_challenge(&mut self) -> Result<AssignedValue<C::Base>, Halo2PlonkError> {
+            let bits = self
+                .ro_circuit
+                .squeeze_n_bits(self.region, NUM_CHALLENGE_BITS)?;
+
+            self.main_gate.le_bits_to_num(self.region, &bits)
+        }
     }
 
-    pub fn verify_sps<C: CurveAffine, const L: usize>(
+    /// Assigned version of [`crate::sps::SpecialSoundnessVerifier::sps_verify`]
+    ///
+    /// Challenges are derived with the same [`sps_challenges`] schedule as off-circuit
+    ///
+    /// In addition to the transcript, the shape of each instance is checked: instance with
+    /// challenges must have exactly one commitment per challenge, as off-circuit. This is known at
+    /// layout time, so mismatch is reported as [`Error::ShapeMismatch`]. Identity commitments are
+    /// allowed, as off-circuit, e.g. of an all-zero round
+    ///
+    /// If values are known, squeezed challenges are compared with the assigned ones before the
+    /// constraint, so a malformed instance is reported as [`Error::SPSMismatch`] instead of a
+    /// failed constraint
+    #[instrument(skip_all)]
+    pub fn verify_sps<C: CurveAffine, const L: usize, const T: usize>(
         region: &mut RegionCtx<C::Base>,
+        main_gate: &MainGate<C::Base, T>,
         ro_circuit: &mut impl ROCircuitTrait<C::Base>,
         incoming: &[AssignedPlonkInstance<C>; L],
-    ) -> Result<(), Halo2PlonkError>
+    ) -> Result<(), Error>
     where
         C::Base: FromUniformBytes<64> + PrimeFieldBits,
         C::ScalarExt: FromUniformBytes<64> + PrimeFieldBits,
     {
-        for pi in incoming {
-            if pi.challenges.is_empty() {
-                continue;
+        for (instance_index, pi) in incoming.iter().enumerate() {
+            // Commitments after the last challenge are not absorbed, so they would be unbound
+            if !pi.challenges.is_empty() && pi.W_commitments.len() != pi.challenges.len() {
+                error!(
+                    "instance has {} challenges, but {} commitments",
+                    pi.challenges.len(),
+                    pi.W_commitments.len()
+                );
+                return Err(Error::ShapeMismatch {
+                    field: "W_commitments",
+                    expected: pi.challenges.len(),
+                    got: pi.W_commitments.len(),
+                });
             }
 
-            ro_circuit.absorb_iter(pi.instances.iter().flat_map(|inst| inst.iter()));
-
-            for (W_commitment, challenge) in pi.W_commitments.iter().zip_eq(pi.challenges.iter()) {
-                let expected = ro_circuit
-                    .absorb_point(WrapValue::from_assigned_point(W_commitment))
-                    .squeeze(region)?;
+            let expected = sps_challenges(
+                &mut OnCircuitSps {
+                    region,
+                    main_gate,
+                    ro_circuit,
+                },
+                &pi.instances,
+                &pi.W_commitments,
+                pi.challenges.len(),
+            )
+            .map_err(|err| Error::SPS { err })?;
+
+            for (challenge_index, (expected, challenge)) in
+                expected.iter().zip_eq(pi.challenges.iter()).enumerate()
+            {
+                let is_mismatch = expected
+                    .value()
+                    .zip(challenge.value())
+                    .map(|(expected, challenge)| expected != challenge)
+                    .unwrap();
+
+                if is_mismatch == Some(true) {
+                    error!("challenge {challenge_index} of instance {instance_index} mismatch");
+                    return Err(Error::SPSMismatch {
+                        instance_index,
+                        challenge_index,
+                    });
+                }
 
-                region.constrain_equal(expected.cell(), challenge.cell())?;
+                region
+                    .constrain_equal(expected.cell(), challenge.cell())
+                    .map_err(|err| Error::SPS { err })?;
             }
         }
 
@@ -731,6 +1702,9 @@ mod verify_chip {
 
     /// Assigned version of `fn verify` logic from [`crate::nifs::protogalaxy::ProtoGalaxy`].
     ///
+    /// Before anything is assigned, the accumulator & `incoming` are checked against `shape`, and
+    /// `incoming` against the accumulator, mismatch is reported as [`Error::ShapeMismatch`]
+    ///
     /// # Algorithm
     ///
     /// The logic of the proof generation follows several key steps:
@@ -752,25 +1726,100 @@ mod verify_chip {
     ///
     /// 5. **Fold the Instance:**
     ///     - [`ProtoGalaxy::fold_instance`]
-    pub fn verify<C: CurveAffine, const L: usize, const T: usize>(
+    ///     - `W_commitments` are folded according to [`CommitmentFoldMode`]
+    ///
+    /// The on-circuit oracle is built from `ro_args`; with `shadow-verify` feature the off-circuit
+    /// one is built too & verification goes through [`shadow::verify`]
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, name = "on_circuit_verify")]
+    pub fn verify<C: CurveAffine, RO, const L: usize, const T: usize>(
         region: &mut RegionCtx<C::Base>,
         main_gate_config: MainGateConfig<T>,
-        ro_circuit: impl ROCircuitTrait<C::Base>,
+        ro_args: RO::Args,
         vp: AssignedVerifierParam<C>,
         accumulator: AssignedAccumulatorInstance<C>,
         incoming: &[AssignedPlonkInstance<C>; L],
+        shape: &InstanceShape,
         proof: AssignedProof<C::Base>,
+        commitment_fold_mode: CommitmentFoldMode<C, T>,
     ) -> Result<AssignedAccumulatorInstance<C>, Error>
+    where
+        C::Base: FromUniformBytes<64> + PrimeFieldBits,
+        C::ScalarExt: FromUniformBytes<64> + PrimeFieldBits,
+        RO: ROPair<C::Base, Config = MainGateConfig<T>>,
+    {
+        let ro_circuit = RO::OnCircuit::new(main_gate_config.clone(), ro_args.clone());
+
+        #[cfg(feature = "shadow-verify")]
+        {
+            use crate::poseidon::ROTrait;
+
+            shadow::verify(
+                region,
+                main_gate_config,
+                ro_circuit,
+                &mut RO::OffCircuit::new(ro_args),
+                vp,
+                accumulator,
+                incoming,
+                shape,
+                proof,
+                commitment_fold_mode,
+            )
+            .map(|(acc, _report)| acc)
+        }
+
+        #[cfg(not(feature = "shadow-verify"))]
+        {
+            verify_inner(
+                region,
+                main_gate_config,
+                ro_circuit,
+                vp,
+                accumulator,
+                incoming,
+                shape,
+                proof,
+                commitment_fold_mode,
+            )
+            .map(|verified| verified.acc)
+        }
+    }
+
+    /// Result of [`verify`] with the challenges used, kept for [`shadow`] checks
+    struct Verified<C: CurveAffine> {
+        challenges: AssignedChallanges<C::Base>,
+        acc: AssignedAccumulatorInstance<C>,
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn verify_inner<C: CurveAffine, const L: usize, const T: usize>(
+        region: &mut RegionCtx<C::Base>,
+        main_gate_config: MainGateConfig<T>,
+        ro_circuit: impl ROCircuitTrait<C::Base>,
+        vp: AssignedVerifierParam<C>,
+        accumulator: AssignedAccumulatorInstance<C>,
+        incoming: &[AssignedPlonkInstance<C>; L],
+        shape: &InstanceShape,
+        proof: AssignedProof<C::Base>,
+        commitment_fold_mode: CommitmentFoldMode<C, T>,
+    ) -> Result<Verified<C>, Error>
     where
         C::Base: FromUniformBytes<64> + PrimeFieldBits,
         C::ScalarExt: FromUniformBytes<64> + PrimeFieldBits,
     {
+        iter::once(&accumulator.ins)
+            .chain(incoming.iter())
+            .try_for_each(|instance| shape.check_assigned(instance))?;
+
+        let challenges =
+            AssignedChallanges::generate(region, ro_circuit, vp, &accumulator, incoming, &proof)
+                .map_err(|err| Error::Squeeze { err })?;
         let AssignedChallanges {
             delta,
             alpha,
             gamma,
-        } = AssignedChallanges::generate(region, ro_circuit, vp, &accumulator, incoming, &proof)
-            .map_err(|err| Error::Squeeze { err })?;
+        } = challenges.clone();
 
         let main_gate = MainGate::new(main_gate_config);
 
@@ -784,20 +1833,31 @@ mod verify_chip {
             },
         )?;
 
-        let one = region
-            .assign_advice(
-                || "one",
-                main_gate.config().state[0],
-                Halo2Value::known(C::Base::ONE),
-            )
+        let mut gamma_powers =
+            ValuePowers::with_assigned_one(region, &main_gate, gamma).map_err(|err| {
+                Error::Assign {
+                    annotation: "one",
+                    err,
+                }
+            })?;
+        let mut alpha_powers = ValuePowers::new(region, &main_gate, gamma_powers.one(), alpha)
             .map_err(|err| Error::Assign {
                 annotation: "one",
                 err,
             })?;
-        region.next();
 
-        let mut gamma_powers = ValuePowers::new(one.clone(), gamma);
-        let mut alpha_powers = ValuePowers::new(one, alpha);
+        // `K(gamma)` needs all powers up to its degree, `L_i(gamma)` & `Z(gamma)` -
+        // `gamma^{2^log_n}`, all of them are taken from this cache
+        gamma_powers
+            .get_or_eval_all(region, &main_gate, proof.poly_K.len().saturating_sub(1))
+            .map_err(|err| Error::WhileE { err })?;
+        gamma_powers
+            .get_or_eval(
+                region,
+                &main_gate,
+                1 << PolyContext::<C::Base>::get_lagrange_domain::<L>(),
+            )
+            .map_err(|err| Error::WhileE { err })?;
 
         let e = calculate_e::<C::Base, T, L>(
             region,
@@ -805,8 +1865,7 @@ mod verify_chip {
             &proof,
             &mut gamma_powers,
             &mut alpha_powers,
-        )
-        .map_err(|err| Error::WhileE { err })?;
+        )?;
 
         let ins = fold_instances(
             region,
@@ -814,80 +1873,338 @@ mod verify_chip {
             &accumulator.ins,
             incoming,
             &mut gamma_powers,
+            &commitment_fold_mode,
         )
         .map_err(|err| Error::Fold { err })?;
 
-        Ok(AssignedAccumulatorInstance { ins, betas, e })
+        Ok(Verified {
+            challenges,
+            acc: AssignedAccumulatorInstance { ins, betas, e },
+        })
     }
 
-    #[cfg(test)]
-    mod tests {
-        use tracing_test::traced_test;
-
+    /// Shadow verification: off-circuit recomputation of [`verify`] stages
+    ///
+    /// With known witness values, each intermediate value of [`verify`] is recomputed off-circuit
+    /// from the assigned inputs & compared with the assigned one. The first mismatch is logged
+    /// with `tracing::error!`, all of them are collected in [`ShadowReport`].
+    ///
+    /// `W_commitments` are not checked: in [`CommitmentFoldMode::Delegate`] they are carried
+    /// forward & in [`CommitmentFoldMode::OnCircuit`] they are folded over `C::Scalar`, so there is
+    /// no base field counterpart.
+    #[cfg(feature = "shadow-verify")]
+    pub mod shadow {
         use super::*;
         use crate::{
-            halo2_proofs::{
-                arithmetic::Field,
-                circuit::{
-                    floor_planner::single_pass::SingleChipLayouter, Chip, Layouter,
-                    SimpleFloorPlanner,
-                },
-                dev::MockProver,
-                plonk::{Circuit, ConstraintSystem},
-            },
-            halo2curves::{bn256::G1Affine as Affine, group::prime::PrimeCurveAffine},
-            main_gate::MainGate,
-            nifs::{
-                self,
-                protogalaxy::{AccumulatorArgs, VerifierParam},
-            },
-            polynomial,
-            poseidon::{poseidon_circuit::PoseidonChip, PoseidonHash, ROTrait, Spec},
-            table::WitnessCollector,
+            constants::MAX_BITS, nifs::protogalaxy::calculate_e, polynomial::lagrange,
+            poseidon::ROTrait,
         };
 
-        const T: usize = 5;
-        const RATE: usize = T - 1;
-        const K: usize = 14;
+        /// Stage of [`verify`], in the order of computation
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Stage {
+            Delta,
+            Alpha,
+            BetasStroke,
+            Gamma,
+            E,
+            /// Instances & challenges of the folded instance, in [`AssignedPlonkInstance::export_cells`]
+            /// order
+            FoldedInstance,
+        }
 
-        type Base = <Affine as CurveAffine>::Base;
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Mismatch<F> {
+            pub stage: Stage,
+            /// Index of the value inside of the stage
+            pub index: usize,
+            pub on_circuit: F,
+            pub off_circuit: F,
+        }
 
-        fn get_witness_collector() -> (WitnessCollector<Base>, MainGateConfig<T>) {
-            let mut cs = ConstraintSystem::default();
-            let config = MainGate::<Base, T>::configure(&mut cs);
-            let witness = WitnessCollector {
-                instances: vec![vec![]],
-                advice: vec![vec![Base::ZERO.into(); 1 << K]; cs.num_advice_columns()],
-            };
+        /// Mismatches between on-circuit & off-circuit values in the order of [`Stage`]
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        pub struct ShadowReport<F> {
+            pub mismatches: Vec<Mismatch<F>>,
+        }
 
-            (witness, config)
+        impl<F: PrimeField> ShadowReport<F> {
+            /// The first diverging value, all of the next ones usually depend on it
+            pub fn first(&self) -> Option<&Mismatch<F>> {
+                self.mismatches.first()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.mismatches.is_empty()
+            }
+
+            fn compare<'l>(
+                &mut self,
+                stage: Stage,
+                on_circuit: impl IntoIterator<Item = &'l AssignedValue<F>>,
+                off_circuit: impl IntoIterator<Item = F>,
+            ) {
+                on_circuit
+                    .into_iter()
+                    .zip_eq(off_circuit)
+                    .enumerate()
+                    .filter_map(|(index, (on_circuit, off_circuit))| {
+                        let on_circuit = on_circuit.value().unwrap().copied()?;
+                        (on_circuit != off_circuit).then_some(Mismatch {
+                            stage,
+                            index,
+                            on_circuit,
+                            off_circuit,
+                        })
+                    })
+                    .for_each(|mismatch| self.mismatches.push(mismatch));
+            }
         }
 
-        struct Mock {
-            params: VerifierParam<Affine>,
-            spec: Spec<<Affine as CurveAffine>::Base, T, RATE>,
-            acc: nifs::protogalaxy::Accumulator<Affine>,
-            proof: nifs::protogalaxy::Proof<<Affine as CurveAffine>::ScalarExt>,
+        /// Values of [`verify`] inputs
+        struct Inputs<F> {
+            /// Absorbed before `delta`
+            transcript: Vec<F>,
+            poly_F: Vec<F>,
+            poly_K: Vec<F>,
+            betas: Vec<F>,
+            /// [`AssignedPlonkInstance::export_cells`] of the accumulator & incoming instances
+            instances: Vec<Vec<F>>,
         }
 
-        impl Mock {
-            fn new() -> Self {
-                let params = VerifierParam::<Affine> {
-                    pp_digest: Affine::identity(),
-                };
+        impl<F: PrimeField> Inputs<F> {
+            /// `None` if any of values is unknown, e.g. during keygen
+            fn new<C: CurveAffine<Base = F>>(
+                vp: &AssignedVerifierParam<C>,
+                accumulator: &AssignedAccumulatorInstance<C>,
+                incoming: &[AssignedPlonkInstance<C>],
+                proof: &AssignedProof<F>,
+            ) -> Option<Self> {
+                fn values<F: PrimeField>(
+                    iter: impl Iterator<Item = WrapValue<F>>,
+                ) -> Option<Vec<F>> {
+                    iter.map(|value| value.value().unwrap()).collect()
+                }
 
-                let spec = Spec::<<Affine as CurveAffine>::Base, 5, 4>::new(10, 10);
+                Some(Self {
+                    transcript: values(
+                        WrapValue::from_assigned_point(&vp.pp_digest)
+                            .into_iter()
+                            .chain(accumulator.iter_wrap_value())
+                            .chain(incoming.iter().flat_map(|tr| tr.iter_wrap_value())),
+                    )?,
+                    poly_F: values(proof.poly_F.iter_wrap_value())?,
+                    poly_K: values(proof.poly_K.iter_wrap_value())?,
+                    betas: values(accumulator.betas.iter().map(WrapValue::from))?,
+                    instances: iter::once(&accumulator.ins)
+                        .chain(incoming.iter())
+                        .map(|ins| values(ins.export_cells().iter().map(WrapValue::from)))
+                        .collect::<Option<_>>()?,
+                })
+            }
 
-                let acc = nifs::protogalaxy::Accumulator::<Affine>::new(
-                    AccumulatorArgs {
-                        num_io: Box::new([]),
-                        num_challenges: 0,
-                        num_witness: 0,
-                        k_table_size: K,
-                        round_sizes: Box::new([]),
-                    },
-                    10,
+            fn check<C: CurveAffine<Base = F>, const L: usize>(
+                self,
+                ro: &mut impl ROTrait<F>,
+                verified: &Verified<C>,
+            ) -> ShadowReport<F> {
+                let Self {
+                    transcript,
+                    poly_F,
+                    poly_K,
+                    betas,
+                    instances,
+                } = self;
+                let Verified {
+                    challenges: on_circuit,
+                    acc,
+                } = verified;
+
+                let mut squeeze = |values: &[F]| -> F {
+                    let challenge = ro
+                        .absorb_field_iter(values.iter().copied())
+                        .squeeze::<C>(MAX_BITS);
+                    util::fe_to_fe(&challenge).unwrap()
+                };
+
+                let delta = squeeze(&transcript);
+                let alpha = squeeze(&poly_F);
+                let gamma = squeeze(&poly_K);
+
+                let lagrange_domain = PolyContext::<F>::get_lagrange_domain::<L>();
+
+                let betas_stroke = PolyChallenges {
+                    betas: betas.into_boxed_slice(),
+                    alpha,
+                    delta,
+                }
+                .iter_beta_stroke();
+
+                let e = calculate_e(
+                    &UnivariatePoly(poly_F.into_boxed_slice()),
+                    &UnivariatePoly(poly_K.into_boxed_slice()),
+                    gamma,
+                    alpha,
+                    lagrange_domain,
+                );
+
+                let folded =
+                    lagrange::iter_eval_lagrange_poly_for_cyclic_group(gamma, lagrange_domain)
+                        .zip(instances.iter())
+                        .fold(None, |folded: Option<Vec<F>>, (l, values)| {
+                            let values = values.iter().map(|value| *value * l);
+                            Some(match folded {
+                                None => values.collect(),
+                                Some(folded) => {
+                                    folded.iter().zip_eq(values).map(|(a, b)| *a + b).collect()
+                                }
+                            })
+                        })
+                        .unwrap_or_default();
+
+                let mut report = ShadowReport::default();
+                report.compare(Stage::Delta, [&on_circuit.delta], [delta]);
+                report.compare(Stage::Alpha, [&on_circuit.alpha], [alpha]);
+                report.compare(Stage::BetasStroke, acc.betas.iter(), betas_stroke);
+                report.compare(Stage::Gamma, [&on_circuit.gamma], [gamma]);
+                report.compare(Stage::E, [&acc.e], [e]);
+                report.compare(Stage::FoldedInstance, &acc.ins.export_cells(), folded);
+
+                report
+            }
+        }
+
+        /// [`verify`] with shadow verification by `ro_off_circuit`
+        ///
+        /// `ro_off_circuit` must be the off-circuit counterpart of `ro_circuit` in the same state.
+        /// Report is `None` if witness values are unknown, e.g. during keygen
+        #[allow(clippy::too_many_arguments)]
+        #[instrument(skip_all, name = "on_circuit_shadow_verify")]
+        pub fn verify<C: CurveAffine, const L: usize, const T: usize>(
+            region: &mut RegionCtx<C::Base>,
+            main_gate_config: MainGateConfig<T>,
+            ro_circuit: impl ROCircuitTrait<C::Base>,
+            ro_off_circuit: &mut impl ROTrait<C::Base>,
+            vp: AssignedVerifierParam<C>,
+            accumulator: AssignedAccumulatorInstance<C>,
+            incoming: &[AssignedPlonkInstance<C>; L],
+            shape: &InstanceShape,
+            proof: AssignedProof<C::Base>,
+            commitment_fold_mode: CommitmentFoldMode<C, T>,
+        ) -> Result<
+            (
+                AssignedAccumulatorInstance<C>,
+                Option<ShadowReport<C::Base>>,
+            ),
+            Error,
+        >
+        where
+            C::Base: FromUniformBytes<64> + PrimeFieldBits,
+            C::ScalarExt: FromUniformBytes<64> + PrimeFieldBits,
+        {
+            let inputs = Inputs::new(&vp, &accumulator, incoming, &proof);
+
+            let verified = verify_inner(
+                region,
+                main_gate_config,
+                ro_circuit,
+                vp,
+                accumulator,
+                incoming,
+                shape,
+                proof,
+                commitment_fold_mode,
+            )?;
+
+            let report = inputs.map(|inputs| inputs.check::<C, L>(ro_off_circuit, &verified));
+
+            if let Some(Mismatch {
+                stage,
+                index,
+                on_circuit,
+                off_circuit,
+            }) = report.as_ref().and_then(ShadowReport::first)
+            {
+                error!(
+                    "shadow verify diverged at {stage:?}[{index}]: on-circuit {on_circuit:?}, off-circuit {off_circuit:?}"
                 );
+            }
+
+            Ok((verified.acc, report))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::array;
+
+        use tracing_test::traced_test;
+
+        use super::*;
+        use crate::{
+            halo2_proofs::{
+                arithmetic::Field,
+                circuit::{
+                    floor_planner::single_pass::SingleChipLayouter, Chip, Layouter,
+                    SimpleFloorPlanner,
+                },
+                dev::MockProver,
+                plonk::{Circuit, ConstraintSystem},
+            },
+            halo2curves::{bn256::G1Affine as Affine, group::prime::PrimeCurveAffine},
+            main_gate::MainGate,
+            nifs::{
+                self,
+                protogalaxy::{AccumulatorArgs, VerifierParam},
+            },
+            polynomial,
+            poseidon::{poseidon_circuit::PoseidonChip, PoseidonHash, PoseidonRO, ROTrait, Spec},
+            table::WitnessCollector,
+            util::BaseToScalar,
+        };
+
+        const T: usize = 5;
+        const RATE: usize = T - 1;
+        const K: usize = 14;
+
+        type Base = <Affine as CurveAffine>::Base;
+
+        fn get_witness_collector() -> (WitnessCollector<Base>, MainGateConfig<T>) {
+            let mut cs = ConstraintSystem::default();
+            let config = MainGate::<Base, T>::configure(&mut cs);
+            let witness = WitnessCollector {
+                instances: vec![vec![]],
+                advice: vec![vec![Base::ZERO.into(); 1 << K]; cs.num_advice_columns()],
+            };
+
+            (witness, config)
+        }
+
+        struct Mock {
+            params: VerifierParam<Affine>,
+            spec: Spec<<Affine as CurveAffine>::Base, T, RATE>,
+            shape: InstanceShape,
+            acc: nifs::protogalaxy::Accumulator<Affine>,
+            proof: nifs::protogalaxy::Proof<<Affine as CurveAffine>::ScalarExt>,
+        }
+
+        impl Mock {
+            fn new() -> Self {
+                let params = VerifierParam::<Affine> {
+                    pp_digest: Affine::identity(),
+                };
+
+                let spec = Spec::<<Affine as CurveAffine>::Base, 5, 4>::new(10, 10);
+
+                let args = AccumulatorArgs {
+                    num_io: Box::new([]),
+                    num_challenges: 0,
+                    num_witness: 0,
+                    k_table_size: K,
+                    round_sizes: Box::new([]),
+                };
+                let shape = InstanceShape::from(&args);
+                let acc = nifs::protogalaxy::Accumulator::<Affine>::new(args, 10);
 
                 let mut values = (0..).map(Into::into);
                 let proof = nifs::protogalaxy::Proof {
@@ -898,45 +2215,294 @@ mod verify_chip {
                 Self {
                     params,
                     spec,
+                    shape,
                     acc,
                     proof,
                 }
             }
         }
 
+        #[test]
+        fn conversion_failed() {
+            use crate::halo2curves::pasta::pallas;
+
+            type Base = <pallas::Affine as CurveAffine>::Base;
+            type Scalar = <pallas::Affine as CurveAffine>::ScalarExt;
+
+            let mut cs = ConstraintSystem::default();
+            let config = MainGate::<Base, T>::configure(&mut cs);
+            let mut wc = WitnessCollector {
+                instances: vec![vec![]],
+                advice: vec![vec![Base::ZERO.into(); 1 << K]; cs.num_advice_columns()],
+            };
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            // The scalar field of pallas is bigger than the base one, so `-1` is out of range
+            let out_of_range = -Scalar::ONE;
+
+            let (instance_err, acc_err) = layouter
+                .assign_region(
+                    || "conversion_failed",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0);
+
+                        let instance_err = AssignedPlonkInstance::assign(
+                            &mut region,
+                            config.clone(),
+                            PlonkInstance::<pallas::Affine> {
+                                W_commitments: vec![],
+                                instances: vec![vec![Scalar::ONE], vec![Scalar::ONE, out_of_range]],
+                                challenges: vec![Scalar::ONE],
+                            },
+                        )
+                        .err();
+
+                        let acc_err = AssignedAccumulatorInstance::assign(
+                            &mut region,
+                            config.clone(),
+                            protogalaxy::AccumulatorInstance::<pallas::Affine> {
+                                ins: PlonkInstance {
+                                    W_commitments: vec![],
+                                    instances: vec![],
+                                    challenges: vec![],
+                                },
+                                betas: Box::new([Scalar::ONE]),
+                                e: out_of_range,
+                            },
+                        )
+                        .err();
+
+                        Ok((instance_err, acc_err))
+                    },
+                )
+                .unwrap();
+
+            assert!(matches!(
+                instance_err,
+                Some(Error::ConversionFailed {
+                    annotation: "PlonkInstance::instances",
+                    index: 2,
+                })
+            ));
+            assert!(matches!(
+                acc_err,
+                Some(Error::ConversionFailed {
+                    annotation: "AccumulatorInstance::e",
+                    index: 0,
+                })
+            ));
+        }
+
+        #[cfg(feature = "shadow-verify")]
         #[traced_test]
         #[test]
-        fn challanges() {
-            let m = Mock::new();
+        fn shadow_verify() {
+            use crate::main_gate::AssignedBit;
+
+            /// Transcript with off-by-one in the second challenge (`alpha`)
+            struct OffByOne {
+                inner: PoseidonChip<Base, T, RATE>,
+                column: halo2_proofs::plonk::Column<halo2_proofs::plonk::Advice>,
+                squeezed: usize,
+            }
 
-            let off_circuit_challenges = nifs::protogalaxy::Challenges::generate(
-                &m.params,
-                &mut PoseidonHash::new(m.spec.clone()),
-                &m.acc,
-                iter::empty::<&PlonkInstance<Affine>>(),
-                &m.proof,
+            impl ROCircuitTrait<Base> for OffByOne {
+                type Args = Spec<Base, T, RATE>;
+                type Config = MainGateConfig<T>;
+
+                fn new(config: Self::Config, args: Self::Args) -> Self {
+                    Self {
+                        column: config.out,
+                        inner: PoseidonChip::new(config, args),
+                        squeezed: 0,
+                    }
+                }
+
+                fn absorb_base(&mut self, base: WrapValue<Base>) -> &mut Self {
+                    ROCircuitTrait::absorb_base(&mut self.inner, base);
+                    self
+                }
+
+                fn absorb_point(&mut self, point: [WrapValue<Base>; 2]) -> &mut Self {
+                    ROCircuitTrait::absorb_point(&mut self.inner, point);
+                    self
+                }
+
+                fn inspect(&mut self, scan: impl FnOnce(&[Base])) -> &mut Self {
+                    ROCircuitTrait::inspect(&mut self.inner, scan);
+                    self
+                }
+
+                fn squeeze_n_bits(
+                    &mut self,
+                    ctx: &mut RegionCtx<'_, Base>,
+                    num_bits: NonZeroUsize,
+                ) -> Result<Vec<AssignedBit<Base>>, Halo2PlonkError> {
+                    ROCircuitTrait::squeeze_n_bits(&mut self.inner, ctx, num_bits)
+                }
+
+                fn squeeze(
+                    &mut self,
+                    ctx: &mut RegionCtx<'_, Base>,
+                ) -> Result<AssignedValue<Base>, Halo2PlonkError> {
+                    let challenge = ROCircuitTrait::squeeze(&mut self.inner, ctx)?;
+                    self.squeezed += 1;
+
+                    if self.squeezed != 2 {
+                        return Ok(challenge);
+                    }
+
+                    let wrong = ctx.assign_advice(
+                        || "off by one",
+                        self.column,
+                        challenge.value().map(|value| *value + Base::ONE),
+                    )?;
+                    ctx.next();
+                    Ok(wrong)
+                }
+            }
+
+            let run = |off_by_one: bool| {
+                let m = Mock::new();
+                let (mut wc, config) = get_witness_collector();
+                let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+                layouter
+                    .assign_region(
+                        || "shadow_verify",
+                        |region| {
+                            let mut region = RegionCtx::new(region, 0);
+
+                            let params = AssignedVerifierParam::assign::<T>(
+                                &mut region,
+                                config.clone(),
+                                &m.params,
+                            )
+                            .unwrap();
+                            let acc = AssignedAccumulatorInstance::assign(
+                                &mut region,
+                                config.clone(),
+                                m.acc.clone().into(),
+                            )
+                            .unwrap();
+                            let incoming = [AssignedPlonkInstance::assign(
+                                &mut region,
+                                config.clone(),
+                                m.acc.trace.u.clone(),
+                            )
+                            .unwrap()];
+                            let proof = AssignedProof::assign(
+                                &mut region,
+                                config.clone(),
+                                protogalaxy::Proof {
+                                    poly_F: m.proof.poly_F.fe_to_fe().unwrap(),
+                                    poly_K: m.proof.poly_K.fe_to_fe().unwrap(),
+                                },
+                            )
+                            .unwrap();
+
+                            let mut ro_off_circuit = PoseidonHash::new(m.spec.clone());
+                            let (_, report) = if off_by_one {
+                                shadow::verify::<Affine, 1, T>(
+                                    &mut region,
+                                    config.clone(),
+                                    OffByOne::new(config.clone(), m.spec.clone()),
+                                    &mut ro_off_circuit,
+                                    params,
+                                    acc,
+                                    &incoming,
+                                    &m.shape,
+                                    proof,
+                                    CommitmentFoldMode::Delegate,
+                                )
+                            } else {
+                                shadow::verify::<Affine, 1, T>(
+                                    &mut region,
+                                    config.clone(),
+                                    PoseidonChip::new(config.clone(), m.spec.clone()),
+                                    &mut ro_off_circuit,
+                                    params,
+                                    acc,
+                                    &incoming,
+                                    &m.shape,
+                                    proof,
+                                    CommitmentFoldMode::Delegate,
+                                )
+                            }
+                            .unwrap();
+
+                            Ok(report.unwrap())
+                        },
+                    )
+                    .unwrap()
+            };
+
+            assert!(run(false).is_empty());
+
+            let report = run(true);
+            assert_eq!(report.first().map(|m| m.stage), Some(shadow::Stage::Alpha));
+            assert!(!report
+                .mismatches
+                .iter()
+                .any(|m| m.stage == shadow::Stage::Delta));
+            assert!(logs_contain("shadow verify diverged at Alpha[0]"));
+        }
+
+        /// Challenges squeezed on-circuit are the same as off-circuit ones
+        fn challanges<C>()
+        where
+            C: CurveAffine,
+            C::Base: PrimeFieldBits + FromUniformBytes<64>,
+            C::ScalarExt: PrimeFieldBits + FromUniformBytes<64>,
+        {
+            let params = VerifierParam::<C> {
+                pp_digest: C::identity(),
+            };
+            let spec = Spec::<C::Base, T, RATE>::new(10, 10);
+            let acc = nifs::protogalaxy::Accumulator::<C>::new(
+                AccumulatorArgs {
+                    num_io: Box::new([]),
+                    num_challenges: 0,
+                    num_witness: 0,
+                    k_table_size: K,
+                    round_sizes: Box::new([]),
+                },
+                10,
             );
+            let mut values = (0..).map(C::ScalarExt::from);
+            let proof = nifs::protogalaxy::Proof {
+                poly_F: UnivariatePoly::from_iter(values.by_ref().take(10)),
+                poly_K: UnivariatePoly::from_iter(values.take(10)),
+            };
 
-            let (mut wc, config) = get_witness_collector();
+            let off_circuit_challenges = nifs::protogalaxy::Challenges::generate(
+                &params,
+                &mut PoseidonHash::new(spec.clone()),
+                &acc,
+                iter::empty::<&PlonkInstance<C>>(),
+                &proof,
+            );
 
+            let mut cs = ConstraintSystem::default();
+            let config = MainGate::<C::Base, T>::configure(&mut cs);
+            let mut wc = WitnessCollector {
+                instances: vec![vec![]],
+                advice: vec![vec![C::Base::ZERO.into(); 1 << K]; cs.num_advice_columns()],
+            };
             let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
 
             let on_circuit_challanges = layouter
                 .assign_region(
                     || "challenges_test",
-                    move |region| {
+                    |region| {
                         let mut region = RegionCtx::new(region, 0);
 
-                        let Mock {
-                            params,
-                            spec,
-                            acc,
-                            proof,
-                        } = &m;
-
-                        let params =
-                            AssignedVerifierParam::assign::<T>(&mut region, config.clone(), params)
-                                .unwrap();
+                        let params = AssignedVerifierParam::assign::<T>(
+                            &mut region,
+                            config.clone(),
+                            &params,
+                        )
+                        .unwrap();
                         let acc = AssignedAccumulatorInstance::assign(
                             &mut region,
                             config.clone(),
@@ -985,73 +2551,1003 @@ mod verify_chip {
             );
         }
 
+        crate::curve_tests!(
+            [challanges];
+            bn256 => crate::halo2curves::bn256::G1Affine,
+            pallas => crate::halo2curves::pasta::EpAffine,
+        );
+
+        /// On-circuit [`verify`] folds to the same instance as off-circuit
+        /// [`nifs::protogalaxy::ProtoGalaxy::verify`]
+        ///
+        /// The arithmetic of them is over different fields, so the values are compared where the
+        /// fold doesn't depend on the field: `F(X)` & `K(X)` are zero, there are no betas & the
+        /// folded instances are equal, since the lagrange polynomials sum up to one
         #[traced_test]
         #[test]
-        fn betas_stroke() {
-            let mut rnd = rand::thread_rng();
-            let mut rnd = iter::repeat_with(|| Base::random(&mut rnd));
-
-            let cha = PolyChallenges {
-                alpha: rnd.next().unwrap(),
-                delta: rnd.next().unwrap(),
-                betas: rnd.take(10).collect(),
-            };
+        fn off_circuit_verify() {
+            use crate::{group::Curve, sps::SpecialSoundnessVerifier, util::fe_to_fe};
 
-            fn assign_poly_challenges<F: PrimeField, const T: usize>(
-                region: &mut RegionCtx<F>,
-                main_gate_config: MainGateConfig<T>,
-                cha: &PolyChallenges<F>,
-            ) -> Result<PolyChallenges<AssignedCell<F, F>>, Halo2PlonkError> {
-                let mut assigner = main_gate_config.advice_cycle_assigner();
+            type Scalar = <Affine as CurveAffine>::ScalarExt;
 
-                let PolyChallenges {
-                    betas,
-                    alpha,
-                    delta,
-                } = cha;
+            let spec = Spec::<Base, T, RATE>::new(10, 10);
 
-                Ok(PolyChallenges {
-                    betas: assigner
-                        .assign_all_advice(region, || "betas", betas.iter().copied())?
-                        .into_boxed_slice(),
-                    alpha: assigner.assign_next_advice(region, || "alpha", *alpha)?,
-                    delta: assigner.assign_next_advice(region, || "delta", *delta)?,
-                })
-            }
+            let mut instance = PlonkInstance::<Affine> {
+                W_commitments: vec![(Affine::generator() * Scalar::from(5)).to_affine()],
+                instances: vec![vec![Scalar::from(1), Scalar::from(2)]],
+                challenges: vec![Scalar::ZERO],
+            };
+            instance.challenges =
+                instance.sps_derive_challenges(&mut PoseidonHash::new(spec.clone()));
 
-            let off_circuit_beta_strokes = cha.clone().iter_beta_stroke().collect::<Box<[_]>>();
+            let params = VerifierParam::<Affine> {
+                pp_digest: Affine::identity(),
+            };
+            let acc = protogalaxy::AccumulatorInstance {
+                ins: instance.clone(),
+                betas: Box::new([]),
+                e: Scalar::ZERO,
+            };
+            let proof = protogalaxy::Proof {
+                poly_F: UnivariatePoly::from_iter([Scalar::ZERO]),
+                poly_K: UnivariatePoly::from_iter([Scalar::ZERO]),
+            };
 
-            let (mut wc, main_gate_config) = get_witness_collector();
+            let off_circuit = nifs::protogalaxy::ProtoGalaxy::<Affine, 1>::verify(
+                &params,
+                &mut PoseidonHash::new(spec.clone()),
+                &mut PoseidonHash::new(spec.clone()),
+                &acc,
+                &[instance.clone()],
+                &proof,
+            )
+            .unwrap();
 
+            let (mut wc, config) = get_witness_collector();
             let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
 
-            let on_circuit_beta_strokes = layouter
+            let on_circuit = layouter
                 .assign_region(
-                    || "betas_stroke",
-                    move |region| {
+                    || "off_circuit_verify",
+                    |region| {
                         let mut region = RegionCtx::new(region, 0);
-                        let cha =
-                            assign_poly_challenges(&mut region, main_gate_config.clone(), &cha)
-                                .unwrap();
-                        let main_gate = MainGate::<Base, T>::new(main_gate_config.clone());
 
-                        Ok(
-                            calculate_betas_stroke::<Affine, T>(&mut region, &main_gate, cha)
-                                .unwrap(),
+                        let params = AssignedVerifierParam::assign::<T>(
+                            &mut region,
+                            config.clone(),
+                            &params,
                         )
-                    },
-                )
-                .unwrap()
+                        .unwrap();
+                        let acc = AssignedAccumulatorInstance::assign(
+                            &mut region,
+                            config.clone(),
+                            acc.clone(),
+                        )
+                        .unwrap();
+                        let incoming = [AssignedPlonkInstance::assign(
+                            &mut region,
+                            config.clone(),
+                            instance.clone(),
+                        )
+                        .unwrap()];
+                        let proof = AssignedProof::assign(
+                            &mut region,
+                            config.clone(),
+                            protogalaxy::Proof {
+                                poly_F: proof.poly_F.fe_to_fe().unwrap(),
+                                poly_K: proof.poly_K.fe_to_fe().unwrap(),
+                            },
+                        )
+                        .unwrap();
+
+                        Ok(verify::<Affine, PoseidonRO<T, RATE>, 1, T>(
+                            &mut region,
+                            config.clone(),
+                            spec.clone(),
+                            params,
+                            acc,
+                            &incoming,
+                            &InstanceShape {
+                                num_io: Box::new([2]),
+                                num_challenges: 1,
+                                num_witness_commitments: 1,
+                            },
+                            proof,
+                            CommitmentFoldMode::Delegate,
+                        )
+                        .unwrap())
+                    },
+                )
+                .unwrap();
+
+            let value = |cell: &AssignedValue<Base>| cell.value().unwrap().copied().unwrap();
+
+            assert_eq!(
+                on_circuit
+                    .ins
+                    .export_cells()
+                    .iter()
+                    .map(value)
+                    .collect::<Vec<_>>(),
+                off_circuit
+                    .ins
+                    .instances
+                    .iter()
+                    .flatten()
+                    .chain(off_circuit.ins.challenges.iter())
+                    .map(|value| fe_to_fe(value).unwrap())
+                    .collect::<Vec<_>>()
+            );
+            assert!(on_circuit.betas.is_empty() && off_circuit.betas.is_empty());
+            assert_eq!(value(&on_circuit.e), fe_to_fe(&off_circuit.e).unwrap());
+        }
+
+        #[traced_test]
+        #[test]
+        fn shape_mismatch() {
+            use crate::group::Curve;
+
+            type Scalar = <Affine as CurveAffine>::ScalarExt;
+
+            let commitment = (Affine::generator() * Scalar::from(5)).to_affine();
+            let instance = |W_commitments: usize, num_io: &[usize]| PlonkInstance::<Affine> {
+                W_commitments: vec![commitment; W_commitments],
+                instances: num_io.iter().map(|len| vec![Scalar::ONE; *len]).collect(),
+                challenges: vec![],
+            };
+            let args = AccumulatorArgs {
+                num_io: Box::new([2]),
+                num_challenges: 0,
+                num_witness: 1,
+                k_table_size: K,
+                round_sizes: Box::new([1 << K]),
+            };
+            let shape = InstanceShape::from(&args);
+
+            let spec = Spec::<Base, T, RATE>::new(10, 10);
+            let (mut wc, config) = get_witness_collector();
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let (conforming, wrong_commitments, wrong_columns) = layouter
+                .assign_region(
+                    || "shape_mismatch",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0);
+                        let mut assign_checked = |W_commitments: usize, num_io: &[usize]| {
+                            AssignedPlonkInstance::assign_checked(
+                                &mut region,
+                                config.clone(),
+                                instance(W_commitments, num_io),
+                                &shape,
+                            )
+                            .err()
+                        };
+
+                        let conforming = assign_checked(1, &[2]);
+                        let wrong_commitments = assign_checked(2, &[2]);
+
+                        let assigned = AssignedPlonkInstance::assign(
+                            &mut region,
+                            config.clone(),
+                            instance(1, &[2]),
+                        )
+                        .unwrap();
+                        assert!(shape.check_assigned(&assigned).is_ok());
+                        assert!(matches!(
+                            InstanceShape {
+                                num_io: Box::new([2]),
+                                num_challenges: 1,
+                                num_witness_commitments: 1,
+                            }
+                            .check_assigned(&assigned),
+                            Err(Error::ShapeMismatch {
+                                field: "challenges",
+                                expected: 1,
+                                got: 0,
+                            })
+                        ));
+
+                        let params = AssignedVerifierParam::assign::<T>(
+                            &mut region,
+                            config.clone(),
+                            &VerifierParam {
+                                pp_digest: Affine::identity(),
+                            },
+                        )
+                        .unwrap();
+                        let acc = AssignedAccumulatorInstance::assign(
+                            &mut region,
+                            config.clone(),
+                            protogalaxy::AccumulatorInstance {
+                                ins: instance(1, &[2]),
+                                betas: Box::new([]),
+                                e: Scalar::ZERO,
+                            },
+                        )
+                        .unwrap();
+                        // Columns of differing lengths
+                        let incoming = [AssignedPlonkInstance::assign(
+                            &mut region,
+                            config.clone(),
+                            instance(1, &[1]),
+                        )
+                        .unwrap()];
+                        let proof = AssignedProof::assign(
+                            &mut region,
+                            config.clone(),
+                            protogalaxy::Proof {
+                                poly_F: UnivariatePoly::from_iter([Base::ZERO]),
+                                poly_K: UnivariatePoly::from_iter([Base::ZERO]),
+                            },
+                        )
+                        .unwrap();
+
+                        let wrong_columns = verify::<Affine, PoseidonRO<T, RATE>, 1, T>(
+                            &mut region,
+                            config.clone(),
+                            spec.clone(),
+                            params,
+                            acc,
+                            &incoming,
+                            &shape,
+                            proof,
+                            CommitmentFoldMode::Delegate,
+                        )
+                        .err();
+
+                        Ok((conforming, wrong_commitments, wrong_columns))
+                    },
+                )
+                .unwrap();
+
+            assert!(conforming.is_none());
+            assert!(matches!(
+                wrong_commitments,
+                Some(Error::ShapeMismatch {
+                    field: "W_commitments",
+                    expected: 1,
+                    got: 2,
+                })
+            ));
+            assert!(matches!(
+                wrong_columns,
+                Some(Error::ShapeMismatch {
+                    field: "num_io",
+                    expected: 2,
+                    got: 1,
+                })
+            ));
+        }
+
+        #[traced_test]
+        #[test]
+        fn rows_used_spans() {
+            use std::sync::{Arc, Mutex};
+
+            use tracing::{
+                field::{Field as TracingField, Visit},
+                span::{Id, Record},
+                Subscriber,
+            };
+            use tracing_subscriber::{
+                layer::{Context, Layer, SubscriberExt},
+                registry::LookupSpan,
+            };
+
+            /// Collects `(span name, rows_used)` of every recorded `rows_used` field
+            #[derive(Clone, Default)]
+            struct RowsUsedCollector(Arc<Mutex<Vec<(&'static str, u64)>>>);
+
+            struct RowsUsed(Option<u64>);
+
+            impl Visit for RowsUsed {
+                fn record_u64(&mut self, field: &TracingField, value: u64) {
+                    if field.name() == "rows_used" {
+                        self.0 = Some(value);
+                    }
+                }
+
+                fn record_debug(&mut self, _field: &TracingField, _value: &dyn std::fmt::Debug) {}
+            }
+
+            impl<S: Subscriber + for<'l> LookupSpan<'l>> Layer<S> for RowsUsedCollector {
+                fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+                    let mut rows_used = RowsUsed(None);
+                    values.record(&mut rows_used);
+
+                    if let Some(rows_used) = rows_used.0 {
+                        let name = ctx.span(id).expect("span is recorded").name();
+                        self.0.lock().unwrap().push((name, rows_used));
+                    }
+                }
+            }
+
+            let collector = RowsUsedCollector::default();
+            tracing::subscriber::with_default(
+                tracing_subscriber::registry().with(collector.clone()),
+                || {
+                    let m = Mock::new();
+                    let (mut wc, config) = get_witness_collector();
+                    let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+                    layouter
+                        .assign_region(
+                            || "rows_used_spans",
+                            |region| {
+                                let mut region = RegionCtx::new(region, 0);
+
+                                let params = AssignedVerifierParam::assign::<T>(
+                                    &mut region,
+                                    config.clone(),
+                                    &m.params,
+                                )
+                                .unwrap();
+                                let acc = AssignedAccumulatorInstance::assign(
+                                    &mut region,
+                                    config.clone(),
+                                    m.acc.clone().into(),
+                                )
+                                .unwrap();
+                                let incoming = [AssignedPlonkInstance::assign(
+                                    &mut region,
+                                    config.clone(),
+                                    m.acc.trace.u.clone(),
+                                )
+                                .unwrap()];
+                                let proof = AssignedProof::assign(
+                                    &mut region,
+                                    config.clone(),
+                                    protogalaxy::Proof {
+                                        poly_F: m.proof.poly_F.fe_to_fe().unwrap(),
+                                        poly_K: m.proof.poly_K.fe_to_fe().unwrap(),
+                                    },
+                                )
+                                .unwrap();
+
+                                verify::<Affine, PoseidonRO<T, RATE>, 1, T>(
+                                    &mut region,
+                                    config.clone(),
+                                    m.spec.clone(),
+                                    params,
+                                    acc,
+                                    &incoming,
+                                    &m.shape,
+                                    proof,
+                                    CommitmentFoldMode::Delegate,
+                                )
+                                .unwrap();
+
+                                Ok(())
+                            },
+                        )
+                        .unwrap();
+                },
+            );
+
+            let recorded = collector.0.lock().unwrap();
+            for phase in [
+                "on_circuit_generate",
+                "calculate_betas_stroke",
+                "calculate_e",
+                "fold_instances",
+            ] {
+                assert!(
+                    recorded
+                        .iter()
+                        .any(|(name, rows_used)| *name == phase && *rows_used > 0),
+                    "no rows_used of {phase} in {recorded:?}"
+                );
+            }
+        }
+
+        #[traced_test]
+        #[test]
+        fn assign_zero() {
+            use crate::constants::MAX_BITS;
+
+            const NUM_IO: [usize; 2] = [2, 1];
+            const NUM_CHALLENGES: usize = 2;
+            const NUM_WITNESS: usize = 3;
+
+            let spec = Spec::<Base, T, RATE>::new(10, 10);
+
+            let off_circuit = PoseidonHash::new(spec.clone())
+                .absorb(&PlonkInstance::<Affine>::new(
+                    &NUM_IO,
+                    NUM_CHALLENGES,
+                    NUM_WITNESS,
+                ))
+                .squeeze::<Affine>(MAX_BITS);
+
+            let (mut wc, config) = get_witness_collector();
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let on_circuit = layouter
+                .assign_region(
+                    || "assign_zero",
+                    move |region| {
+                        let mut region = RegionCtx::new(region, 0);
+
+                        let zero = AssignedPlonkInstance::<Affine>::assign_zero(
+                            &mut region,
+                            config.clone(),
+                            &NUM_IO,
+                            NUM_CHALLENGES,
+                            NUM_WITNESS,
+                        )
+                        .unwrap();
+
+                        PoseidonChip::new(config.clone(), spec.clone())
+                            .absorb_iter(zero.iter_wrap_value())
+                            .squeeze(&mut region)
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                on_circuit.value().unwrap(),
+                Some(&crate::util::fe_to_fe(&off_circuit).unwrap())
+            );
+        }
+
+        #[cfg(feature = "proptest")]
+        mod sps_schedule {
+            use proptest::{collection::vec, prelude::*};
+
+            use super::*;
+            use crate::{sps::SpecialSoundnessVerifier, testing::strategies};
+
+            /// Instance with 0..=3 challenges & 1..=3 instance columns, challenges are derived
+            /// off-circuit
+            fn plonk_instance() -> impl Strategy<Value = PlonkInstance<Affine>> {
+                (0..=3usize, vec(vec(strategies::field(), 0..4), 1..=3)).prop_flat_map(
+                    |(num_challenges, instances)| {
+                        vec(strategies::point::<Affine>(), num_challenges.max(1)).prop_map(
+                            move |W_commitments| {
+                                let mut pi = PlonkInstance {
+                                    W_commitments,
+                                    instances: instances.clone(),
+                                    challenges: vec![Default::default(); num_challenges],
+                                };
+                                pi.challenges = pi.sps_derive_challenges(&mut ro());
+                                pi
+                            },
+                        )
+                    },
+                )
+            }
+
+            fn ro() -> PoseidonHash<Base, T, RATE> {
+                PoseidonHash::new(Spec::new(10, 10))
+            }
+
+            proptest! {
+                #![proptest_config(ProptestConfig::with_cases(16))]
+
+                /// On-circuit schedule squeezes the same challenges as off-circuit `sps_verify`
+                #[test]
+                fn same_challenges(pi in plonk_instance()) {
+                    prop_assert!(pi.sps_verify(&mut ro()).is_ok());
+
+                    let spec = Spec::<Base, T, RATE>::new(10, 10);
+                    let (mut wc, config) = get_witness_collector();
+                    let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+                    let on_circuit = layouter
+                        .assign_region(
+                            || "sps_schedule",
+                            |region| {
+                                let mut region = RegionCtx::new(region, 0);
+                                let main_gate = MainGate::<Base, T>::new(config.clone());
+
+                                let assigned =
+                                    AssignedPlonkInstance::assign(&mut region, config.clone(), pi.clone())
+                                        .unwrap();
+                                region.next();
+
+                                let challenges = sps_challenges(
+                                    &mut OnCircuitSps {
+                                        region: &mut region,
+                                        main_gate: &main_gate,
+                                        ro_circuit: &mut PoseidonChip::new(config.clone(), spec.clone()),
+                                    },
+                                    &assigned.instances,
+                                    &assigned.W_commitments,
+                                    assigned.challenges.len(),
+                                )?;
+
+                                verify_sps(
+                                    &mut region,
+                                    &main_gate,
+                                    &mut PoseidonChip::new(config.clone(), spec.clone()),
+                                    &[assigned],
+                                )
+                                .map_err(|_| Halo2PlonkError::Synthesis)?;
+
+                                Ok(challenges)
+                            },
+                        )
+                        .unwrap()
+                        .iter()
+                        .map(|challenge| *challenge.value().unwrap().unwrap())
+                        .collect::<Vec<_>>();
+
+                    let off_circuit = pi
+                        .challenges
+                        .iter()
+                        .map(|challenge| crate::util::fe_to_fe(challenge).unwrap())
+                        .collect::<Vec<Base>>();
+
+                    prop_assert_eq!(on_circuit, off_circuit);
+                }
+            }
+        }
+
+        /// Malformed incoming instances are rejected by `verify_sps`
+        #[traced_test]
+        #[test]
+        fn verify_sps_malformed() {
+            use crate::group::Curve;
+
+            type Scalar = <Affine as CurveAffine>::ScalarExt;
+
+            struct TestCircuit {
+                instance: PlonkInstance<Affine>,
+            }
+
+            impl Circuit<Base> for TestCircuit {
+                type Config = MainGateConfig<T>;
+                type FloorPlanner = SimpleFloorPlanner;
+
+                fn without_witnesses(&self) -> Self {
+                    todo!()
+                }
+
+                fn configure(meta: &mut ConstraintSystem<Base>) -> Self::Config {
+                    MainGate::configure(meta)
+                }
+
+                fn synthesize(
+                    &self,
+                    config: Self::Config,
+                    mut layouter: impl Layouter<Base>,
+                ) -> Result<(), Halo2PlonkError> {
+                    layouter.assign_region(
+                        || "verify_sps",
+                        |region| {
+                            let mut region = RegionCtx::new(region, 0);
+                            let main_gate = MainGate::<Base, T>::new(config.clone());
+
+                            let incoming = [AssignedPlonkInstance::assign(
+                                &mut region,
+                                config.clone(),
+                                self.instance.clone(),
+                            )
+                            .unwrap()];
+                            region.next();
+
+                            verify_sps(
+                                &mut region,
+                                &main_gate,
+                                &mut PoseidonChip::new(
+                                    config.clone(),
+                                    Spec::<Base, T, RATE>::new(10, 10),
+                                ),
+                                &incoming,
+                            )
+                            .map_err(|err| match err {
+                                Error::SPS { err } => err,
+                                _ => Halo2PlonkError::Synthesis,
+                            })
+                        },
+                    )
+                }
+            }
+
+            let instance = |W_commitments, challenges| TestCircuit {
+                instance: PlonkInstance::<Affine> {
+                    W_commitments,
+                    instances: vec![vec![Scalar::from(1), Scalar::from(2)]],
+                    challenges,
+                },
+            };
+            let commitment = (Affine::generator() * Scalar::from(5)).to_affine();
+
+            MockProver::run(12, &instance(vec![commitment], vec![]), vec![])
+                .unwrap()
+                .verify()
+                .unwrap();
+
+            // Identity is accepted as off-circuit
+            MockProver::run(12, &instance(vec![Affine::identity()], vec![]), vec![])
+                .unwrap()
+                .verify()
+                .unwrap();
+
+            // Second commitment is not bound by the transcript
+            assert!(matches!(
+                MockProver::run(
+                    12,
+                    &instance(vec![commitment, commitment], vec![Scalar::ONE]),
+                    vec![]
+                ),
+                Err(Halo2PlonkError::Synthesis)
+            ));
+        }
+
+        /// Wrong challenge is reported with its position before it's constrained
+        #[traced_test]
+        #[test]
+        fn verify_sps_mismatch() {
+            use crate::{group::Curve, sps::SpecialSoundnessVerifier};
+
+            type Scalar = <Affine as CurveAffine>::ScalarExt;
+
+            let mut pi = PlonkInstance::<Affine> {
+                W_commitments: (1..=2u64)
+                    .map(|i| (Affine::generator() * Scalar::from(i)).to_affine())
+                    .collect(),
+                instances: vec![vec![Scalar::from(1u64), Scalar::from(2u64)]],
+                challenges: vec![Scalar::ZERO; 2],
+            };
+            pi.challenges = pi
+                .sps_derive_challenges(&mut PoseidonHash::<Base, T, RATE>::new(Spec::new(10, 10)));
+
+            let verify = |pi: PlonkInstance<Affine>| {
+                let (mut wc, config) = get_witness_collector();
+                let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+                layouter
+                    .assign_region(
+                        || "verify_sps",
+                        |region| {
+                            let mut region = RegionCtx::new(region, 0);
+                            let main_gate = MainGate::<Base, T>::new(config.clone());
+
+                            let incoming = [AssignedPlonkInstance::assign(
+                                &mut region,
+                                config.clone(),
+                                pi.clone(),
+                            )
+                            .unwrap()];
+                            region.next();
+
+                            Ok(verify_sps(
+                                &mut region,
+                                &main_gate,
+                                &mut PoseidonChip::new(
+                                    config.clone(),
+                                    Spec::<Base, T, RATE>::new(10, 10),
+                                ),
+                                &incoming,
+                            ))
+                        },
+                    )
+                    .unwrap()
+            };
+
+            assert!(verify(pi.clone()).is_ok());
+
+            pi.challenges[1] += Scalar::ONE;
+            assert!(matches!(
+                verify(pi),
+                Err(Error::SPSMismatch {
+                    instance_index: 0,
+                    challenge_index: 1
+                })
+            ));
+        }
+
+        #[traced_test]
+        #[test]
+        fn betas_stroke() {
+            let mut rnd = rand::thread_rng();
+            let mut rnd = iter::repeat_with(|| Base::random(&mut rnd));
+
+            let cha = PolyChallenges {
+                alpha: rnd.next().unwrap(),
+                delta: rnd.next().unwrap(),
+                betas: rnd.take(10).collect(),
+            };
+
+            fn assign_poly_challenges<F: PrimeField, const T: usize>(
+                region: &mut RegionCtx<F>,
+                main_gate_config: MainGateConfig<T>,
+                cha: &PolyChallenges<F>,
+            ) -> Result<PolyChallenges<AssignedCell<F, F>>, Halo2PlonkError> {
+                let mut assigner = main_gate_config.advice_cycle_assigner();
+
+                let PolyChallenges {
+                    betas,
+                    alpha,
+                    delta,
+                } = cha;
+
+                Ok(PolyChallenges {
+                    betas: assigner
+                        .assign_all_advice(region, || "betas", betas.iter().copied())?
+                        .into_boxed_slice(),
+                    alpha: assigner.assign_next_advice(region, || "alpha", *alpha)?,
+                    delta: assigner.assign_next_advice(region, || "delta", *delta)?,
+                })
+            }
+
+            let off_circuit_beta_strokes = cha.clone().iter_beta_stroke().collect::<Box<[_]>>();
+
+            let (mut wc, main_gate_config) = get_witness_collector();
+
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let on_circuit_beta_strokes = layouter
+                .assign_region(
+                    || "betas_stroke",
+                    move |region| {
+                        let mut region = RegionCtx::new(region, 0);
+                        let cha =
+                            assign_poly_challenges(&mut region, main_gate_config.clone(), &cha)
+                                .unwrap();
+                        let main_gate = MainGate::<Base, T>::new(main_gate_config.clone());
+
+                        Ok(
+                            calculate_betas_stroke::<Affine, T>(&mut region, &main_gate, cha)
+                                .unwrap(),
+                        )
+                    },
+                )
+                .unwrap()
                 .iter()
                 .map(|cell| *cell.value().unwrap().unwrap())
                 .collect::<Box<[_]>>();
 
-            assert_eq!(off_circuit_beta_strokes, on_circuit_beta_strokes);
+            assert_eq!(off_circuit_beta_strokes, on_circuit_beta_strokes);
+        }
+
+        #[traced_test]
+        #[test]
+        fn poly_eval() {
+            struct TestCircuit;
+
+            impl Circuit<Base> for TestCircuit {
+                type Config = MainGateConfig<T>;
+                type FloorPlanner = SimpleFloorPlanner;
+
+                fn without_witnesses(&self) -> Self {
+                    todo!()
+                }
+
+                fn configure(meta: &mut ConstraintSystem<Base>) -> Self::Config {
+                    MainGate::configure(meta)
+                }
+
+                fn synthesize(
+                    &self,
+                    main_gate_config: Self::Config,
+                    mut layouter: impl Layouter<Base>,
+                ) -> Result<(), Halo2PlonkError> {
+                    let cha = Base::from_u128(123);
+                    let poly = UnivariatePoly::from_iter((0..).map(Into::into).take(10));
+
+                    let off_circuit_res = poly.eval(cha);
+
+                    let on_circuit_res = layouter.assign_region(
+                        || "assigned_poly_eval",
+                        move |region| {
+                            let mut region = RegionCtx::new(region, 0);
+
+                            let cha = region
+                                .assign_advice(
+                                    || "",
+                                    main_gate_config.state[0],
+                                    Halo2Value::known(cha),
+                                )
+                                .unwrap();
+
+                            let one = region
+                                .assign_advice(
+                                    || "",
+                                    main_gate_config.state[1],
+                                    Halo2Value::known(Base::ONE),
+                                )
+                                .unwrap();
+
+                            region.next();
+
+                            let main_gate = MainGate::new(main_gate_config.clone());
+
+                            let mut cha = ValuePowers::new(&mut region, &main_gate, one, cha)?;
+
+                            let poly = AssignedUnivariatePoly::assign(
+                                &mut region,
+                                main_gate_config.clone(),
+                                "test poly",
+                                &poly,
+                            )
+                            .unwrap();
+
+                            Ok(poly.eval(&mut region, &main_gate, &mut cha).unwrap())
+                        },
+                    )?;
+
+                    assert_eq!(
+                        off_circuit_res,
+                        on_circuit_res.value().unwrap().copied().unwrap()
+                    );
+
+                    Ok(())
+                }
+            }
+
+            MockProver::run(12, &TestCircuit {}, vec![])
+                .unwrap()
+                .verify()
+                .unwrap();
+        }
+
+        #[traced_test]
+        #[test]
+        fn poly_eval_multi() {
+            struct TestCircuit;
+
+            impl Circuit<Base> for TestCircuit {
+                type Config = MainGateConfig<T>;
+                type FloorPlanner = SimpleFloorPlanner;
+
+                fn without_witnesses(&self) -> Self {
+                    todo!()
+                }
+
+                fn configure(meta: &mut ConstraintSystem<Base>) -> Self::Config {
+                    MainGate::configure(meta)
+                }
+
+                fn synthesize(
+                    &self,
+                    config: Self::Config,
+                    mut layouter: impl Layouter<Base>,
+                ) -> Result<(), Halo2PlonkError> {
+                    let challenges = [123, 456, 789].map(Base::from_u128);
+                    // Degree 10
+                    let poly = UnivariatePoly::from_iter((0..).map(Into::into).take(11));
+
+                    let (multi, single) = layouter.assign_region(
+                        || "assigned_poly_eval_multi",
+                        |region| {
+                            let mut region = RegionCtx::new(region, 0);
+                            let main_gate = MainGate::new(config.clone());
+
+                            let new_powers = |region: &mut RegionCtx<Base>| {
+                                challenges
+                                    .iter()
+                                    .map(|cha| {
+                                        let cha = region.assign_advice(
+                                            || "cha",
+                                            config.state[0],
+                                            Halo2Value::known(*cha),
+                                        )?;
+                                        let one = region.assign_advice(
+                                            || "one",
+                                            config.state[1],
+                                            Halo2Value::known(Base::ONE),
+                                        )?;
+                                        region.next();
+
+                                        ValuePowers::new(region, &main_gate, one, cha)
+                                    })
+                                    .collect::<Result<Vec<_>, _>>()
+                            };
+
+                            let poly = AssignedUnivariatePoly::assign(
+                                &mut region,
+                                config.clone(),
+                                "poly",
+                                &poly,
+                            )
+                            .unwrap();
+
+                            let multi = poly.eval_multi(
+                                &mut region,
+                                &main_gate,
+                                &mut new_powers(&mut region)?,
+                            )?;
+
+                            let single = new_powers(&mut region)?
+                                .iter_mut()
+                                .map(|powers| poly.eval(&mut region, &main_gate, powers))
+                                .collect::<Result<Vec<_>, _>>()?;
+
+                            Ok((multi, single))
+                        },
+                    )?;
+
+                    let values = |cells: Vec<AssignedValue<Base>>| {
+                        cells
+                            .iter()
+                            .map(|cell| cell.value().unwrap().copied().unwrap())
+                            .collect::<Vec<_>>()
+                    };
+
+                    let multi = values(multi);
+                    assert_eq!(multi, values(single));
+                    assert_eq!(multi, challenges.map(|cha| poly.eval(cha)));
+
+                    Ok(())
+                }
+            }
+
+            MockProver::run(12, &TestCircuit {}, vec![])
+                .unwrap()
+                .verify()
+                .unwrap();
+        }
+
+        #[test]
+        fn assign_padded() {
+            let cha = Base::from_u128(123);
+            let poly = UnivariatePoly::from_iter((1..).map(Into::into).take(7));
+
+            let (mut wc, config) = get_witness_collector();
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let (value, len, too_short) = layouter
+                .assign_region(
+                    || "assign_padded",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0);
+                        let main_gate = MainGate::new(config.clone());
+
+                        let cha = region.assign_advice(
+                            || "cha",
+                            config.state[0],
+                            Halo2Value::known(cha),
+                        )?;
+                        let one = region.assign_advice(
+                            || "one",
+                            config.state[1],
+                            Halo2Value::known(Base::ONE),
+                        )?;
+                        region.next();
+
+                        let mut powers = ValuePowers::new(&mut region, &main_gate, one, cha)?;
+
+                        let padded = AssignedUnivariatePoly::assign_padded(
+                            &mut region,
+                            config.clone(),
+                            "poly",
+                            &poly,
+                            16,
+                        )
+                        .unwrap();
+                        let value = padded.eval(&mut region, &main_gate, &mut powers)?;
+
+                        let too_short = AssignedUnivariatePoly::assign_padded(
+                            &mut region,
+                            config.clone(),
+                            "poly",
+                            &poly,
+                            4,
+                        )
+                        .err();
+
+                        Ok((value, padded.len(), too_short))
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(len, 16);
+            assert_eq!(value.value().unwrap().copied(), Some(poly.eval(cha)));
+            assert!(matches!(
+                too_short,
+                Some(Error::ShapeMismatch {
+                    field: "poly",
+                    expected: 4,
+                    got: 7
+                })
+            ));
         }
 
         #[traced_test]
         #[test]
-        fn poly_eval() {
+        fn eval_batch() {
             struct TestCircuit;
 
             impl Circuit<Base> for TestCircuit {
@@ -1068,66 +3564,472 @@ mod verify_chip {
 
                 fn synthesize(
                     &self,
-                    main_gate_config: Self::Config,
+                    config: Self::Config,
                     mut layouter: impl Layouter<Base>,
                 ) -> Result<(), Halo2PlonkError> {
-                    let cha = Base::from_u128(123);
-                    let poly = UnivariatePoly::from_iter((0..).map(Into::into).take(10));
+                    let challenges = [123, 456, 789].map(Base::from_u128);
+                    // odd count of coefficients, so the last row has one pair
+                    let poly = UnivariatePoly::from_iter((0..).map(Into::into).take(11));
 
-                    let off_circuit_res = poly.eval(cha);
-
-                    let on_circuit_res = layouter.assign_region(
-                        || "assigned_poly_eval",
-                        move |region| {
+                    let (batch, separate) = layouter.assign_region(
+                        || "eval_batch",
+                        |region| {
                             let mut region = RegionCtx::new(region, 0);
-
-                            let cha = region
-                                .assign_advice(
-                                    || "",
-                                    main_gate_config.state[0],
-                                    Halo2Value::known(cha),
-                                )
-                                .unwrap();
-
-                            let one = region
-                                .assign_advice(
-                                    || "",
-                                    main_gate_config.state[1],
-                                    Halo2Value::known(Base::ONE),
-                                )
-                                .unwrap();
-
-                            region.next();
-
-                            let mut cha = ValuePowers::new(one, cha);
+                            let main_gate = MainGate::new(config.clone());
+
+                            let new_powers = |region: &mut RegionCtx<Base>| {
+                                challenges
+                                    .iter()
+                                    .map(|cha| {
+                                        let cha = region.assign_advice(
+                                            || "cha",
+                                            config.state[0],
+                                            Halo2Value::known(*cha),
+                                        )?;
+                                        let one = region.assign_advice(
+                                            || "one",
+                                            config.state[1],
+                                            Halo2Value::known(Base::ONE),
+                                        )?;
+                                        region.next();
+
+                                        ValuePowers::new(region, &main_gate, one, cha)
+                                    })
+                                    .collect::<Result<Vec<_>, _>>()
+                            };
+                            let mut batch_powers = new_powers(&mut region)?;
+                            let mut separate_powers = new_powers(&mut region)?;
 
                             let poly = AssignedUnivariatePoly::assign(
                                 &mut region,
-                                main_gate_config.clone(),
-                                "test poly",
+                                config.clone(),
+                                "poly",
                                 &poly,
                             )
                             .unwrap();
 
-                            let main_gate = MainGate::new(main_gate_config.clone());
+                            let [alpha, gamma, blinding] = batch_powers.as_mut_slice() else {
+                                unreachable!()
+                            };
+                            let batch =
+                                poly.eval_batch(&mut region, &main_gate, [alpha, gamma, blinding])?;
 
-                            Ok(poly.eval(&mut region, &main_gate, &mut cha).unwrap())
+                            let separate = separate_powers
+                                .iter_mut()
+                                .map(|powers| poly.eval(&mut region, &main_gate, powers))
+                                .collect::<Result<Vec<_>, _>>()?;
+
+                            Ok((batch, separate))
                         },
                     )?;
 
+                    let values = |cells: &[AssignedValue<Base>]| {
+                        cells
+                            .iter()
+                            .map(|cell| cell.value().unwrap().copied().unwrap())
+                            .collect::<Vec<_>>()
+                    };
+
+                    let batch = values(&batch);
+                    assert_eq!(batch, values(&separate));
+                    assert_eq!(batch, challenges.map(|cha| poly.eval(cha)));
+
+                    Ok(())
+                }
+            }
+
+            MockProver::run(12, &TestCircuit {}, vec![])
+                .unwrap()
+                .verify()
+                .unwrap();
+        }
+
+        #[test]
+        fn eval_horner() {
+            struct TestCircuit {
+                len: usize,
+            }
+
+            impl Circuit<Base> for TestCircuit {
+                type Config = MainGateConfig<T>;
+                type FloorPlanner = SimpleFloorPlanner;
+
+                fn without_witnesses(&self) -> Self {
+                    todo!()
+                }
+
+                fn configure(meta: &mut ConstraintSystem<Base>) -> Self::Config {
+                    MainGate::configure(meta)
+                }
+
+                fn synthesize(
+                    &self,
+                    config: Self::Config,
+                    mut layouter: impl Layouter<Base>,
+                ) -> Result<(), Halo2PlonkError> {
+                    let cha = Base::from_u128(123);
+                    let poly = UnivariatePoly::from_iter((0..).map(Into::into).take(self.len));
+
+                    let (powers_value, horner_value, powers_rows, horner_rows) = layouter
+                        .assign_region(
+                            || "eval_horner",
+                            |region| {
+                                let mut region = RegionCtx::new(region, 0);
+                                let main_gate = MainGate::new(config.clone());
+
+                                let cha = region.assign_advice(
+                                    || "cha",
+                                    config.state[0],
+                                    Halo2Value::known(cha),
+                                )?;
+                                let one = region.assign_advice(
+                                    || "one",
+                                    config.state[1],
+                                    Halo2Value::known(Base::ONE),
+                                )?;
+                                region.next();
+
+                                let poly = AssignedUnivariatePoly::assign(
+                                    &mut region,
+                                    config.clone(),
+                                    "poly",
+                                    &poly,
+                                )
+                                .unwrap();
+
+                                let start = region.offset();
+                                let mut powers =
+                                    ValuePowers::new(&mut region, &main_gate, one, cha.clone())?;
+                                let powers_value = poly.eval_with(
+                                    &mut region,
+                                    &main_gate,
+                                    &mut powers,
+                                    PolyEvalMode::Powers,
+                                )?;
+                                let powers_rows = region.offset() - start;
+
+                                let start = region.offset();
+                                let horner_value =
+                                    poly.eval_horner(&mut region, &main_gate, &cha)?;
+                                let horner_rows = region.offset() - start;
+
+                                Ok((powers_value, horner_value, powers_rows, horner_rows))
+                            },
+                        )?;
+
                     assert_eq!(
-                        off_circuit_res,
-                        on_circuit_res.value().unwrap().copied().unwrap()
+                        powers_value.value().unwrap().copied(),
+                        horner_value.value().unwrap().copied(),
                     );
+                    assert_eq!(horner_value.value().unwrap().copied(), Some(poly.eval(cha)));
+
+                    // powers: one row to check `one`, `len - 2` rows for `cha^2..` & two
+                    // coefficients per row for the sum
+                    assert_eq!(powers_rows, 1 + (poly.len() - 2) + poly.len().div_ceil(2));
+                    assert_eq!(horner_rows, poly.len() - 1);
 
                     Ok(())
                 }
             }
 
-            MockProver::run(12, &TestCircuit {}, vec![])
+            // degree 9 & 10, i.e. even & odd count of coefficients
+            for len in [10, 11] {
+                MockProver::run(12, &TestCircuit { len }, vec![])
+                    .unwrap()
+                    .verify()
+                    .unwrap();
+            }
+        }
+
+        #[test]
+        fn with_assigned_one() {
+            struct TestCircuit {
+                one: Base,
+            }
+
+            impl Circuit<Base> for TestCircuit {
+                type Config = MainGateConfig<T>;
+                type FloorPlanner = SimpleFloorPlanner;
+
+                fn without_witnesses(&self) -> Self {
+                    todo!()
+                }
+
+                fn configure(meta: &mut ConstraintSystem<Base>) -> Self::Config {
+                    MainGate::configure(meta)
+                }
+
+                fn synthesize(
+                    &self,
+                    config: Self::Config,
+                    mut layouter: impl Layouter<Base>,
+                ) -> Result<(), Halo2PlonkError> {
+                    let cha = Base::from_u128(123);
+
+                    let power = layouter.assign_region(
+                        || "with_assigned_one",
+                        |region| {
+                            let mut region = RegionCtx::new(region, 0);
+                            let main_gate = MainGate::new(config.clone());
+
+                            let cha = region.assign_advice(
+                                || "cha",
+                                config.state[0],
+                                Halo2Value::known(cha),
+                            )?;
+                            region.next();
+
+                            let mut powers = ValuePowers::with_one_value(
+                                &mut region,
+                                &main_gate,
+                                Halo2Value::known(self.one),
+                                cha,
+                            )?;
+
+                            powers.get_or_eval(&mut region, &main_gate, 3)
+                        },
+                    )?;
+
+                    if self.one == Base::ONE {
+                        assert_eq!(power.value().unwrap().copied(), Some(cha.pow([3])));
+                    }
+
+                    Ok(())
+                }
+            }
+
+            MockProver::run(12, &TestCircuit { one: Base::ONE }, vec![])
                 .unwrap()
                 .verify()
                 .unwrap();
+
+            assert!(
+                MockProver::run(12, &TestCircuit { one: Base::from(2) }, vec![])
+                    .unwrap()
+                    .verify()
+                    .is_err()
+            );
+        }
+
+        #[test]
+        fn value_powers_try_new() {
+            let (mut wc, config) = get_witness_collector();
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let (valid, mismatched) = layouter
+                .assign_region(
+                    || "value_powers_try_new",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0);
+                        let main_gate = MainGate::new(config.clone());
+
+                        let mut try_new = |one: Base| {
+                            let one = region.assign_advice(
+                                || "one",
+                                config.state[0],
+                                Halo2Value::known(one),
+                            )?;
+                            let value = region.assign_advice(
+                                || "value",
+                                config.state[1],
+                                Halo2Value::known(Base::from(7)),
+                            )?;
+                            region.next();
+
+                            Ok::<_, Halo2PlonkError>(
+                                ValuePowers::try_new(&mut region, &main_gate, one, value)
+                                    .map(|powers| *powers.one().value().unwrap().unwrap()),
+                            )
+                        };
+
+                        Ok((try_new(Base::ONE)?, try_new(Base::from(2))?))
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(valid.unwrap(), Base::ONE);
+            assert!(matches!(mismatched, Err(Halo2PlonkError::Synthesis)));
+        }
+
+        #[test]
+        fn get_or_eval_by_squaring() {
+            const EXP: usize = 511;
+            let x = Base::from(7);
+
+            let (value, rows) = {
+                let (mut wc, config) = get_witness_collector();
+                let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+                layouter
+                    .assign_region(
+                        || "get_or_eval_by_squaring",
+                        |region| {
+                            let mut region = RegionCtx::new(region, 0);
+                            let main_gate = MainGate::new(config.clone());
+
+                            let x = region.assign_advice(
+                                || "x",
+                                config.state[0],
+                                Halo2Value::known(x),
+                            )?;
+                            region.next();
+
+                            let mut powers =
+                                ValuePowers::with_assigned_one(&mut region, &main_gate, x)?;
+
+                            let start = region.offset();
+                            let value = powers.get_or_eval(&mut region, &main_gate, EXP)?;
+
+                            Ok((value, region.offset() - start))
+                        },
+                    )
+                    .unwrap()
+            };
+
+            assert!(rows <= 20, "{rows} rows used for x^{EXP}");
+            assert_eq!(value.value().unwrap().copied(), Some(x.pow([EXP as u64])));
+        }
+
+        /// `x^{2^lagrange_domain}` of `eval_vanish_polynomial` takes `O(log n)` rows, while the
+        /// contiguous powers take `O(n)`
+        #[test]
+        fn get_or_eval_rows_vs_contiguous() {
+            const EXP: usize = 1024;
+            let x = Base::from(7);
+
+            let (mut wc, config) = get_witness_collector();
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let ((by_squaring, squaring_rows), (contiguous, contiguous_rows)) = layouter
+                .assign_region(
+                    || "get_or_eval_rows_vs_contiguous",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0);
+                        let main_gate = MainGate::new(config.clone());
+
+                        let x =
+                            region.assign_advice(|| "x", config.state[0], Halo2Value::known(x))?;
+                        region.next();
+
+                        let mut eval = |contiguous: bool| {
+                            let mut powers =
+                                ValuePowers::with_assigned_one(&mut region, &main_gate, x.clone())?;
+
+                            let start = region.offset();
+                            let value = if contiguous {
+                                powers.get_or_eval_all(&mut region, &main_gate, EXP)?[EXP].clone()
+                            } else {
+                                powers.get_or_eval(&mut region, &main_gate, EXP)?
+                            };
+
+                            Ok::<_, Halo2PlonkError>((value, region.offset() - start))
+                        };
+
+                        Ok((eval(false)?, eval(true)?))
+                    },
+                )
+                .unwrap();
+
+            let expected = Some(x.pow([EXP as u64]));
+            assert_eq!(by_squaring.value().unwrap().copied(), expected);
+            assert_eq!(contiguous.value().unwrap().copied(), expected);
+
+            // At most one squaring & one multiplication per bit
+            assert!(
+                squaring_rows <= 2 * EXP.ilog2() as usize,
+                "{squaring_rows} rows used for x^{EXP}"
+            );
+            assert_eq!(contiguous_rows, EXP - 1);
+        }
+
+        #[test]
+        fn get_or_eval_matches_repeated_mul() {
+            const EXP: usize = 17;
+            let x = Base::from(7);
+
+            let (mut wc, config) = get_witness_collector();
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let (by_squaring, repeated) = layouter
+                .assign_region(
+                    || "get_or_eval_matches_repeated_mul",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0);
+                        let main_gate = MainGate::new(config.clone());
+
+                        let x =
+                            region.assign_advice(|| "x", config.state[0], Halo2Value::known(x))?;
+                        region.next();
+
+                        let by_squaring =
+                            ValuePowers::with_assigned_one(&mut region, &main_gate, x.clone())?
+                                .get_or_eval(&mut region, &main_gate, EXP)?;
+
+                        let repeated = ValuePowers::with_assigned_one(&mut region, &main_gate, x)?
+                            .get_or_eval_all(&mut region, &main_gate, EXP)?[EXP]
+                            .clone();
+
+                        Ok((by_squaring, repeated))
+                    },
+                )
+                .unwrap();
+
+            let expected = iter::repeat(x).take(EXP).fold(Base::ONE, |acc, x| acc * x);
+            assert_eq!(by_squaring.value().unwrap().copied(), Some(expected));
+            assert_eq!(repeated.value().unwrap().copied(), Some(expected));
+        }
+
+        #[test]
+        fn get_or_eval_many() {
+            const EXPS: [usize; 3] = [4, 9, 0];
+            let cha = Base::from_u128(123);
+
+            let (mut wc, config) = get_witness_collector();
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let (values, prefilled_rows, total_rows) = layouter
+                .assign_region(
+                    || "get_or_eval_many",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0);
+                        let main_gate = MainGate::new(config.clone());
+
+                        let cha = region.assign_advice(
+                            || "cha",
+                            config.state[0],
+                            Halo2Value::known(cha),
+                        )?;
+                        let one = region.assign_advice(
+                            || "one",
+                            config.state[1],
+                            Halo2Value::known(Base::ONE),
+                        )?;
+                        region.next();
+
+                        let mut powers = ValuePowers::new(&mut region, &main_gate, one, cha)?;
+
+                        let start = region.offset();
+                        let values = powers.get_or_eval_many(&mut region, &main_gate, EXPS)?;
+                        let prefilled_rows = region.offset() - start;
+
+                        for exp in EXPS {
+                            powers.get_or_eval(&mut region, &main_gate, exp)?;
+                        }
+                        let total_rows = region.offset() - start;
+
+                        Ok((values, prefilled_rows, total_rows))
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(prefilled_rows, total_rows);
+            assert_eq!(
+                values
+                    .iter()
+                    .map(|value| value.value().unwrap().copied().unwrap())
+                    .collect::<Vec<_>>(),
+                EXPS.map(|exp| cha.pow([exp as u64])),
+            );
         }
 
         #[traced_test]
@@ -1196,10 +4098,10 @@ mod verify_chip {
                                 )
                                 .unwrap();
 
-                            let mut values = ValuePowers::new(one, cha);
-
                             region.next();
 
+                            let mut values = ValuePowers::new(&mut region, &main_gate, one, cha)?;
+
                             Ok((
                                 eval_lagrange_poly::<Fr, T, L>(
                                     &mut region,
@@ -1239,58 +4141,485 @@ mod verify_chip {
 
         #[traced_test]
         #[test]
-        fn vanishing() {
-            const DEGREE: usize = 10;
-            let cha = Base::from_u128(123);
+        fn vanishing() {
+            const DEGREE: usize = 10;
+            let cha = Base::from_u128(123);
+
+            let off_circuit_vanishing = polynomial::lagrange::eval_vanish_polynomial(DEGREE, cha);
+
+            let (mut wc, main_gate_config) = get_witness_collector();
+
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let on_circuit_vanishing = layouter
+                .assign_region(
+                    || "vanishing",
+                    move |region| {
+                        let mut region = RegionCtx::new(region, 0);
+                        let main_gate = MainGate::<Base, T>::new(main_gate_config.clone());
+
+                        let cha = region
+                            .assign_advice(|| "", main_gate_config.state[0], Halo2Value::known(cha))
+                            .unwrap();
+
+                        let one = region
+                            .assign_advice(
+                                || "",
+                                main_gate_config.state[1],
+                                Halo2Value::known(Base::ONE),
+                            )
+                            .unwrap();
+
+                        region.next();
+
+                        let mut cha = ValuePowers::new(&mut region, &main_gate, one, cha)?;
+
+                        eval_vanish_polynomial(&mut region, &main_gate, DEGREE, &mut cha)
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                off_circuit_vanishing,
+                on_circuit_vanishing.value().unwrap().copied().unwrap()
+            );
+        }
+
+        #[traced_test]
+        #[test]
+        fn test_e() {
+            use crate::halo2curves::bn256::Fr;
+
+            struct TestCircuit;
+
+            impl Circuit<Fr> for TestCircuit {
+                type Config = MainGateConfig<T>;
+                type FloorPlanner = SimpleFloorPlanner;
+
+                fn without_witnesses(&self) -> Self {
+                    todo!()
+                }
+
+                fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+                    MainGate::configure(meta)
+                }
+
+                fn synthesize(
+                    &self,
+                    main_gate_config: Self::Config,
+                    mut layouter: impl Layouter<Fr>,
+                ) -> Result<(), Halo2PlonkError> {
+                    const L: usize = 3;
+
+                    let mut values = (0..).map(Into::into);
+                    let proof = nifs::protogalaxy::Proof {
+                        poly_F: UnivariatePoly::from_iter(values.by_ref().take(10)),
+                        poly_K: UnivariatePoly::from_iter(values.by_ref().take(10)),
+                    };
+
+                    let gamma = values.next().unwrap();
+                    let alpha = values.next().unwrap();
+
+                    let log_n = PolyContext::<Fr>::get_lagrange_domain::<L>();
+
+                    let off_circuit_e = nifs::protogalaxy::calculate_e(
+                        &proof.poly_F,
+                        &proof.poly_K,
+                        gamma,
+                        alpha,
+                        log_n,
+                    );
+
+                    let on_circuit_e = layouter
+                        .assign_region(
+                            || "e",
+                            move |region| {
+                                let mut region = RegionCtx::new(region, 0);
+                                let main_gate = MainGate::<Fr, T>::new(main_gate_config.clone());
+
+                                let proof = AssignedProof::assign(
+                                    &mut region,
+                                    main_gate_config.clone(),
+                                    proof.clone(),
+                                )
+                                .unwrap();
+
+                                let one = region
+                                    .assign_advice(
+                                        || "",
+                                        main_gate_config.state[0],
+                                        Halo2Value::known(Fr::ONE),
+                                    )
+                                    .unwrap();
+                                let gamma = region
+                                    .assign_advice(
+                                        || "",
+                                        main_gate_config.state[1],
+                                        Halo2Value::known(gamma),
+                                    )
+                                    .unwrap();
+
+                                let alpha = region
+                                    .assign_advice(
+                                        || "",
+                                        main_gate_config.state[2],
+                                        Halo2Value::known(alpha),
+                                    )
+                                    .unwrap();
+
+                                region.next();
+
+                                let mut gamma =
+                                    ValuePowers::new(&mut region, &main_gate, one.clone(), gamma)?;
+                                let mut alpha =
+                                    ValuePowers::new(&mut region, &main_gate, one, alpha)?;
+
+                                calculate_e::<Fr, T, L>(
+                                    &mut region,
+                                    &main_gate,
+                                    &proof,
+                                    &mut gamma,
+                                    &mut alpha,
+                                )
+                                .map_err(|_| Halo2PlonkError::Synthesis)
+                            },
+                        )
+                        .unwrap();
+
+                    assert_eq!(
+                        off_circuit_e,
+                        on_circuit_e.value().unwrap().copied().unwrap()
+                    );
+
+                    Ok(())
+                }
+            }
+
+            MockProver::run(12, &TestCircuit {}, vec![])
+                .unwrap()
+                .verify()
+                .unwrap();
+        }
+
+        /// On-circuit [`calculate_e`] matches [`nifs::protogalaxy::calculate_e`] for boundary cases
+        #[test]
+        fn e_boundaries() {
+            use crate::halo2curves::bn256::Fr;
+
+            struct TestCircuit<const L: usize> {
+                proof: nifs::protogalaxy::Proof<Fr>,
+                gamma: Fr,
+                alpha: Fr,
+            }
+
+            impl<const L: usize> Circuit<Fr> for TestCircuit<L> {
+                type Config = MainGateConfig<T>;
+                type FloorPlanner = SimpleFloorPlanner;
+
+                fn without_witnesses(&self) -> Self {
+                    todo!()
+                }
+
+                fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+                    MainGate::configure(meta)
+                }
+
+                fn synthesize(
+                    &self,
+                    main_gate_config: Self::Config,
+                    mut layouter: impl Layouter<Fr>,
+                ) -> Result<(), Halo2PlonkError> {
+                    let Self {
+                        proof,
+                        gamma,
+                        alpha,
+                    } = self;
+
+                    let off_circuit_e = nifs::protogalaxy::calculate_e(
+                        &proof.poly_F,
+                        &proof.poly_K,
+                        *gamma,
+                        *alpha,
+                        PolyContext::<Fr>::get_lagrange_domain::<L>(),
+                    );
+
+                    let on_circuit_e = layouter.assign_region(
+                        || "e",
+                        |region| {
+                            let mut region = RegionCtx::new(region, 0);
+                            let main_gate = MainGate::<Fr, T>::new(main_gate_config.clone());
+
+                            let proof = AssignedProof::assign(
+                                &mut region,
+                                main_gate_config.clone(),
+                                proof.clone(),
+                            )
+                            .unwrap();
+
+                            let [one, gamma, alpha] = [Fr::ONE, *gamma, *alpha]
+                                .into_iter()
+                                .zip(main_gate_config.state)
+                                .map(|(value, column)| {
+                                    region.assign_advice(|| "", column, Halo2Value::known(value))
+                                })
+                                .collect::<Result<Vec<_>, _>>()?
+                                .try_into()
+                                .unwrap();
+                            region.next();
+
+                            let mut gamma =
+                                ValuePowers::new(&mut region, &main_gate, one.clone(), gamma)?;
+                            let mut alpha = ValuePowers::new(&mut region, &main_gate, one, alpha)?;
+
+                            calculate_e::<Fr, T, L>(
+                                &mut region,
+                                &main_gate,
+                                &proof,
+                                &mut gamma,
+                                &mut alpha,
+                            )
+                            .map_err(|_| Halo2PlonkError::Synthesis)
+                        },
+                    )?;
+
+                    assert_eq!(
+                        Some(off_circuit_e),
+                        on_circuit_e.value().unwrap().copied(),
+                        "gamma: {gamma:?}, alpha: {alpha:?}, F len: {}, K len: {}",
+                        proof.poly_F.len(),
+                        proof.poly_K.len(),
+                    );
+
+                    Ok(())
+                }
+            }
+
+            fn check<const L: usize>(poly_F: &[u64], poly_K: &[u64], gamma: Fr, alpha: Fr) {
+                let poly =
+                    |coeffs: &[u64]| UnivariatePoly::from_iter(coeffs.iter().map(|c| Fr::from(*c)));
+
+                MockProver::run(
+                    12,
+                    &TestCircuit::<L> {
+                        proof: nifs::protogalaxy::Proof {
+                            poly_F: poly(poly_F),
+                            poly_K: poly(poly_K),
+                        },
+                        gamma,
+                        alpha,
+                    },
+                    vec![],
+                )
+                .unwrap()
+                .verify()
+                .unwrap();
+            }
+
+            let log_n = PolyContext::<Fr>::get_lagrange_domain::<3>();
+            let roots = iter_cyclic_subgroup::<Fr>(log_n)
+                .take(1 << log_n)
+                .collect::<Vec<_>>();
+            let (gamma, alpha) = (Fr::from(123), Fr::from(456));
+            let long = (1..=10).collect::<Vec<_>>();
+
+            // constant `K(X)`
+            check::<3>(&long, &[7], gamma, alpha);
+            // `F(X)` shorter than `K(X)`
+            check::<3>(&[5, 6], &long, gamma, alpha);
+            // `K(X)` shorter than `F(X)`
+            check::<3>(&long, &[8, 9], gamma, alpha);
+            // single instance folding
+            check::<1>(&long, &[7], gamma, alpha);
+
+            // `gamma` in subgroup: `Z(gamma) = 0`, `L_0(1) = 1` & `L_0(omega^i) = 0` otherwise
+            for root in roots.iter() {
+                check::<3>(&long, &long, *root, alpha);
+                check::<3>(&[3], &[7], *root, alpha);
+            }
+
+            // `alpha` in subgroup
+            for root in roots.iter() {
+                check::<3>(&long, &long, gamma, *root);
+            }
+
+            // zero challenges
+            check::<3>(&long, &long, Fr::ZERO, Fr::ZERO);
+        }
+
+        /// Failed evaluation in [`calculate_e`] is reported by the variant of its polynomial
+        #[traced_test]
+        #[test]
+        fn e_eval_error() {
+            const L: usize = 3;
+
+            let proof = nifs::protogalaxy::Proof {
+                poly_F: UnivariatePoly::from_iter((1..=4u64).map(Base::from)),
+                // Empty `K(X)` has no value to evaluate
+                poly_K: UnivariatePoly::from_iter(iter::empty()),
+            };
+
+            let (mut wc, config) = get_witness_collector();
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let result = layouter
+                .assign_region(
+                    || "e_eval_error",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0);
+                        let main_gate = MainGate::<Base, T>::new(config.clone());
+
+                        let proof =
+                            AssignedProof::assign(&mut region, config.clone(), proof.clone())
+                                .unwrap();
+
+                        let [one, gamma, alpha] = [Base::ONE, Base::from(123), Base::from(456)]
+                            .into_iter()
+                            .zip(config.state)
+                            .map(|(value, column)| {
+                                region.assign_advice(|| "", column, Halo2Value::known(value))
+                            })
+                            .collect::<Result<Vec<_>, _>>()?
+                            .try_into()
+                            .unwrap();
+                        region.next();
+
+                        let mut gamma =
+                            ValuePowers::new(&mut region, &main_gate, one.clone(), gamma)?;
+                        let mut alpha = ValuePowers::new(&mut region, &main_gate, one, alpha)?;
+
+                        Ok(calculate_e::<Base, T, L>(
+                            &mut region,
+                            &main_gate,
+                            &proof,
+                            &mut gamma,
+                            &mut alpha,
+                        ))
+                    },
+                )
+                .unwrap();
+
+            assert!(matches!(
+                result,
+                Err(Error::EvalPolyK {
+                    len: 0,
+                    err: Halo2PlonkError::Synthesis
+                })
+            ));
+        }
+
+        #[traced_test]
+        #[test]
+        fn assign_all_advice_points() {
+            use crate::{group::Curve, halo2curves::bn256::Fr};
 
-            let off_circuit_vanishing = polynomial::lagrange::eval_vanish_polynomial(DEGREE, cha);
+            let points = (1..=8u64)
+                .map(|i| (Affine::generator() * Fr::from(i)).to_affine())
+                .collect::<Vec<_>>();
 
-            let (mut wc, main_gate_config) = get_witness_collector();
+            let (mut wc, config) = get_witness_collector();
+            let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
+
+            let mut assign = |batch: bool| {
+                layouter
+                    .assign_region(
+                        || "assign_all_advice_points",
+                        |region| {
+                            let mut region = RegionCtx::new(region, 0);
+                            let mut assigner = config.advice_cycle_assigner();
+
+                            let assigned = if batch {
+                                assigner.assign_all_advice_points(
+                                    &mut region,
+                                    || "points",
+                                    points.iter(),
+                                )?
+                            } else {
+                                points
+                                    .iter()
+                                    .map(|point| {
+                                        assigner.assign_next_advice_point(
+                                            &mut region,
+                                            || "point",
+                                            point,
+                                        )
+                                    })
+                                    .collect::<Result<Vec<_>, _>>()?
+                            };
+
+                            let coordinates = assigned
+                                .iter()
+                                .flat_map(|point| [&point.x, &point.y])
+                                .map(|cell| cell.value().unwrap().copied().unwrap())
+                                .collect::<Vec<_>>();
+
+                            Ok((coordinates, region.offset()))
+                        },
+                    )
+                    .unwrap()
+            };
+
+            let (batch, batch_last_row) = assign(true);
+            let (one_by_one, one_by_one_last_row) = assign(false);
+
+            assert_eq!(batch, one_by_one);
+            // 16 coordinates are packed into all `T + 2` advice columns
+            assert_eq!(batch_last_row, 16usize.div_ceil(T + 2) - 1);
+            assert_eq!(batch_last_row, one_by_one_last_row);
+        }
+
+        #[traced_test]
+        #[test]
+        fn iter_wrap_value_rev() {
+            use crate::{group::Curve, halo2curves::bn256::Fr};
+
+            let pi = PlonkInstance::<Affine> {
+                W_commitments: (1..=2u64)
+                    .map(|i| (Affine::generator() * Fr::from(i)).to_affine())
+                    .collect(),
+                instances: vec![vec![Fr::from(3), Fr::from(4)], vec![Fr::from(5)]],
+                challenges: vec![Fr::from(6), Fr::from(7)],
+            };
 
+            let (mut wc, config) = get_witness_collector();
             let mut layouter = SingleChipLayouter::new(&mut wc, vec![]).unwrap();
 
-            let on_circuit_vanishing = layouter
+            let (forward, backward) = layouter
                 .assign_region(
-                    || "vanishing",
-                    move |region| {
+                    || "iter_wrap_value_rev",
+                    |region| {
                         let mut region = RegionCtx::new(region, 0);
-                        let main_gate = MainGate::<Base, T>::new(main_gate_config.clone());
-
-                        let cha = region
-                            .assign_advice(|| "", main_gate_config.state[0], Halo2Value::known(cha))
-                            .unwrap();
-
-                        let one = region
-                            .assign_advice(
-                                || "",
-                                main_gate_config.state[1],
-                                Halo2Value::known(Base::ONE),
-                            )
-                            .unwrap();
 
-                        region.next();
+                        let assigned =
+                            AssignedPlonkInstance::assign(&mut region, config.clone(), pi.clone())
+                                .unwrap();
 
-                        let mut cha = ValuePowers::new(one, cha);
+                        let values = |iter: &mut dyn Iterator<Item = WrapValue<Base>>| {
+                            iter.map(|value| value.value().unwrap().unwrap())
+                                .collect::<Vec<_>>()
+                        };
 
-                        eval_vanish_polynomial(&mut region, &main_gate, DEGREE, &mut cha)
+                        Ok((
+                            values(&mut assigned.iter_wrap_value()),
+                            values(&mut assigned.iter_wrap_value_rev()),
+                        ))
                     },
                 )
                 .unwrap();
 
-            assert_eq!(
-                off_circuit_vanishing,
-                on_circuit_vanishing.value().unwrap().copied().unwrap()
-            );
+            assert_eq!(forward.len(), 2 * 2 + 3 + 2);
+            assert_eq!(forward.into_iter().rev().collect::<Vec<_>>(), backward);
         }
 
         #[traced_test]
         #[test]
-        fn test_e() {
+        fn export_cells() {
             use crate::halo2curves::bn256::Fr;
 
-            struct TestCircuit;
+            struct TestCircuit {
+                external: Base,
+            }
 
-            impl Circuit<Fr> for TestCircuit {
+            impl Circuit<Base> for TestCircuit {
                 type Config = MainGateConfig<T>;
                 type FloorPlanner = SimpleFloorPlanner;
 
@@ -1298,102 +4627,420 @@ mod verify_chip {
                     todo!()
                 }
 
-                fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+                fn configure(meta: &mut ConstraintSystem<Base>) -> Self::Config {
                     MainGate::configure(meta)
                 }
 
                 fn synthesize(
                     &self,
                     main_gate_config: Self::Config,
-                    mut layouter: impl Layouter<Fr>,
+                    mut layouter: impl Layouter<Base>,
                 ) -> Result<(), Halo2PlonkError> {
-                    const L: usize = 3;
+                    let pi = PlonkInstance::<Affine> {
+                        W_commitments: vec![],
+                        instances: vec![vec![Fr::from(1), Fr::from(2)], vec![Fr::from(3)]],
+                        challenges: vec![Fr::from(4)],
+                    };
 
-                    let mut values = (0..).map(Into::into);
-                    let proof = nifs::protogalaxy::Proof {
-                        poly_F: UnivariatePoly::from_iter(values.by_ref().take(10)),
-                        poly_K: UnivariatePoly::from_iter(values.by_ref().take(10)),
+                    layouter.assign_region(
+                        || "export_cells",
+                        move |region| {
+                            let mut region = RegionCtx::new(region, 0);
+
+                            let assigned = AssignedPlonkInstance::assign(
+                                &mut region,
+                                main_gate_config.clone(),
+                                pi.clone(),
+                            )
+                            .unwrap();
+
+                            let exported = assigned.export_cells();
+                            assert_eq!(
+                                exported
+                                    .iter()
+                                    .map(|cell| cell.value().unwrap().copied().unwrap())
+                                    .collect::<Vec<_>>(),
+                                (1..=4).map(Base::from).collect::<Vec<_>>()
+                            );
+
+                            region.next();
+
+                            let external = region.assign_advice(
+                                || "external",
+                                main_gate_config.state[0],
+                                Halo2Value::known(self.external),
+                            )?;
+
+                            region.constrain_equal(exported.last().unwrap().cell(), external.cell())
+                        },
+                    )
+                }
+            }
+
+            MockProver::run(
+                12,
+                &TestCircuit {
+                    external: Base::from(4),
+                },
+                vec![],
+            )
+            .unwrap()
+            .verify()
+            .unwrap();
+
+            assert!(MockProver::run(
+                12,
+                &TestCircuit {
+                    external: Base::from(5),
+                },
+                vec![],
+            )
+            .unwrap()
+            .verify()
+            .is_err());
+        }
+
+        /// Instance folded in one region is copied into another one & constrained there
+        #[test]
+        fn reassign_into() {
+            use crate::halo2curves::bn256::Fr;
+
+            struct TestCircuit {
+                /// Value constrained to the first copied instance cell in the second region
+                external: Base,
+            }
+
+            impl Circuit<Base> for TestCircuit {
+                type Config = MainGateConfig<T>;
+                type FloorPlanner = SimpleFloorPlanner;
+
+                fn without_witnesses(&self) -> Self {
+                    todo!()
+                }
+
+                fn configure(meta: &mut ConstraintSystem<Base>) -> Self::Config {
+                    MainGate::configure(meta)
+                }
+
+                fn synthesize(
+                    &self,
+                    config: Self::Config,
+                    mut layouter: impl Layouter<Base>,
+                ) -> Result<(), Halo2PlonkError> {
+                    let instance = |value: u64| PlonkInstance::<Affine> {
+                        W_commitments: vec![Affine::generator()],
+                        instances: vec![vec![Fr::from(value), Fr::from(value + 1)]],
+                        challenges: vec![Fr::from(value + 2)],
                     };
 
-                    let gamma = values.next().unwrap();
-                    let alpha = values.next().unwrap();
+                    let folded = layouter.assign_region(
+                        || "fold",
+                        |region| {
+                            let mut region = RegionCtx::new(region, 0);
+                            let main_gate = MainGate::<Base, T>::new(config.clone());
 
-                    let log_n = PolyContext::<Fr>::get_lagrange_domain::<L>();
+                            let acc = AssignedPlonkInstance::assign(
+                                &mut region,
+                                config.clone(),
+                                instance(1),
+                            )
+                            .unwrap();
+                            let incoming = [AssignedPlonkInstance::assign(
+                                &mut region,
+                                config.clone(),
+                                instance(1),
+                            )
+                            .unwrap()];
+                            region.next();
 
-                    let off_circuit_e = nifs::protogalaxy::calculate_e(
-                        &proof.poly_F,
-                        &proof.poly_K,
-                        gamma,
-                        alpha,
-                        log_n,
-                    );
+                            let one = region.assign_advice(
+                                || "one",
+                                config.state[0],
+                                Halo2Value::known(Base::ONE),
+                            )?;
+                            let gamma = region.assign_advice(
+                                || "gamma",
+                                config.state[1],
+                                Halo2Value::known(Base::from(123)),
+                            )?;
+                            region.next();
 
-                    let on_circuit_e = layouter
-                        .assign_region(
-                            || "e",
-                            move |region| {
-                                let mut region = RegionCtx::new(region, 0);
-                                let main_gate = MainGate::<Fr, T>::new(main_gate_config.clone());
+                            let mut gamma = ValuePowers::new(&mut region, &main_gate, one, gamma)?;
 
-                                let proof = AssignedProof::assign(
-                                    &mut region,
-                                    main_gate_config.clone(),
-                                    proof.clone(),
-                                )
-                                .unwrap();
+                            fold_instances::<Affine, T, 1>(
+                                &mut region,
+                                &main_gate,
+                                &acc,
+                                &incoming,
+                                &mut gamma,
+                                &CommitmentFoldMode::Delegate,
+                            )
+                        },
+                    )?;
 
-                                let one = region
-                                    .assign_advice(
-                                        || "",
-                                        main_gate_config.state[0],
-                                        Halo2Value::known(Fr::ONE),
-                                    )
-                                    .unwrap();
-                                let gamma = region
-                                    .assign_advice(
-                                        || "",
-                                        main_gate_config.state[1],
-                                        Halo2Value::known(gamma),
-                                    )
-                                    .unwrap();
+                    layouter.assign_region(
+                        || "reuse",
+                        |region| {
+                            let mut region = RegionCtx::new(region, 0);
 
-                                let alpha = region
-                                    .assign_advice(
-                                        || "",
-                                        main_gate_config.state[2],
-                                        Halo2Value::known(alpha),
-                                    )
-                                    .unwrap();
+                            let copy = folded.reassign_into(&mut region, config.clone()).unwrap();
+                            region.next();
 
-                                let mut gamma = ValuePowers::new(one.clone(), gamma);
-                                let mut alpha = ValuePowers::new(one, alpha);
+                            let values = |instance: &AssignedPlonkInstance<Affine>| {
+                                instance
+                                    .export_cells()
+                                    .iter()
+                                    .map(|cell| cell.value().unwrap().copied().unwrap())
+                                    .collect::<Vec<_>>()
+                            };
+                            // Lagrange polynomials sum up to one, so the fold of equal
+                            // instances is the same instance
+                            assert_eq!(values(&copy), [1, 2, 3].map(Base::from));
+                            assert_eq!(values(&copy), values(&folded));
+                            assert_eq!(
+                                copy.W_commitments[0].coordinates_values(),
+                                folded.W_commitments[0].coordinates_values()
+                            );
+
+                            let external = region.assign_advice(
+                                || "external",
+                                config.state[0],
+                                Halo2Value::known(self.external),
+                            )?;
+                            region.constrain_equal(copy.instances[0][0].cell(), external.cell())
+                        },
+                    )
+                }
+            }
 
-                                region.next();
+            MockProver::run(
+                12,
+                &TestCircuit {
+                    external: Base::from(1),
+                },
+                vec![],
+            )
+            .unwrap()
+            .verify()
+            .unwrap();
 
-                                calculate_e::<Fr, T, L>(
-                                    &mut region,
-                                    &main_gate,
-                                    &proof,
-                                    &mut gamma,
-                                    &mut alpha,
-                                )
-                            },
-                        )
-                        .unwrap();
+            assert!(MockProver::run(
+                12,
+                &TestCircuit {
+                    external: Base::from(2),
+                },
+                vec![],
+            )
+            .unwrap()
+            .verify()
+            .is_err());
+        }
 
-                    assert_eq!(
-                        off_circuit_e,
-                        on_circuit_e.value().unwrap().copied().unwrap()
-                    );
+        /// Folds accumulator with `L` incoming instances with `W_commitments` & compares folded
+        /// commitments with `expected`, that is the off-circuit fold in the on-circuit mode
+        struct FoldCommitmentsCircuit<const L: usize> {
+            on_circuit: bool,
+            gamma: Base,
+            acc: PlonkInstance<Affine>,
+            incoming: [PlonkInstance<Affine>; L],
+            expected: Vec<Affine>,
+        }
 
-                    Ok(())
+        impl<const L: usize> FoldCommitmentsCircuit<L> {
+            fn new(on_circuit: bool) -> Self {
+                Self::with_identity(on_circuit, false)
+            }
+
+            /// If `with_identity` is set, the first commitment of the first incoming instance is
+            /// the identity point
+            fn with_identity(on_circuit: bool, with_identity: bool) -> Self {
+                use crate::halo2_proofs::arithmetic::best_multiexp;
+
+                let gamma = Base::from_u128(123);
+                let instance = |i: usize| PlonkInstance::<Affine> {
+                    W_commitments: (1..=2)
+                        .map(|j| {
+                            best_multiexp(
+                                &[<Affine as CurveAffine>::ScalarExt::from(
+                                    (10 * i + j) as u64,
+                                )],
+                                &[Affine::generator()],
+                            )
+                            .into()
+                        })
+                        .collect(),
+                    instances: vec![vec![<Affine as CurveAffine>::ScalarExt::from(i as u64)]],
+                    challenges: vec![],
+                };
+                let acc = instance(0);
+                let mut incoming: [_; L] = array::from_fn(|i| instance(i + 1));
+                if with_identity {
+                    incoming[0].W_commitments[0] = Affine::identity();
+                }
+
+                let expected = if on_circuit {
+                    protogalaxy::ProtoGalaxy::<Affine, L>::fold_instance(
+                        acc.clone(),
+                        incoming.iter(),
+                        polynomial::lagrange::iter_eval_lagrange_poly_for_cyclic_group(
+                            Affine::base_to_scalar(&gamma).unwrap(),
+                            PolyContext::<Base>::get_lagrange_domain::<L>(),
+                        ),
+                    )
+                    .W_commitments
+                } else {
+                    acc.W_commitments.clone()
+                };
+
+                Self {
+                    on_circuit,
+                    gamma,
+                    acc,
+                    incoming,
+                    expected,
                 }
             }
+        }
 
-            MockProver::run(12, &TestCircuit {}, vec![])
+        impl<const L: usize> Circuit<Base> for FoldCommitmentsCircuit<L> {
+            type Config = MainGateConfig<T>;
+            type FloorPlanner = SimpleFloorPlanner;
+
+            fn without_witnesses(&self) -> Self {
+                todo!()
+            }
+
+            fn configure(meta: &mut ConstraintSystem<Base>) -> Self::Config {
+                MainGate::configure(meta)
+            }
+
+            fn synthesize(
+                &self,
+                main_gate_config: Self::Config,
+                mut layouter: impl Layouter<Base>,
+            ) -> Result<(), Halo2PlonkError> {
+                let folded = layouter.assign_region(
+                    || "fold_commitments",
+                    |region| {
+                        let mut region = RegionCtx::new(region, 0);
+                        let main_gate = MainGate::<Base, T>::new(main_gate_config.clone());
+                        let ecc_chip = EccChip::new(main_gate_config.clone());
+                        let bn_chip = BigUintMulModChip::new(
+                            main_gate_config.into_smaller_size().unwrap(),
+                            NonZeroUsize::new(32).unwrap(),
+                            NonZeroUsize::new(10).unwrap(),
+                        );
+
+                        let mut assign = |pi: PlonkInstance<Affine>| {
+                            AssignedPlonkInstance::assign(&mut region, main_gate_config.clone(), pi)
+                                .unwrap()
+                        };
+                        let acc = assign(self.acc.clone());
+                        let incoming = self.incoming.clone().map(assign);
+
+                        region.next();
+
+                        let one = region.assign_advice(
+                            || "one",
+                            main_gate_config.state[0],
+                            Halo2Value::known(Base::ONE),
+                        )?;
+                        let gamma = region.assign_advice(
+                            || "gamma",
+                            main_gate_config.state[1],
+                            Halo2Value::known(self.gamma),
+                        )?;
+                        region.next();
+
+                        let mut gamma = ValuePowers::new(&mut region, &main_gate, one, gamma)?;
+
+                        let mode = if self.on_circuit {
+                            CommitmentFoldMode::OnCircuit {
+                                ecc_chip: &ecc_chip,
+                                bn_chip: &bn_chip,
+                            }
+                        } else {
+                            CommitmentFoldMode::Delegate
+                        };
+
+                        fold_instances::<Affine, T, L>(
+                            &mut region,
+                            &main_gate,
+                            &acc,
+                            &incoming,
+                            &mut gamma,
+                            &mode,
+                        )
+                    },
+                )?;
+
+                let actual = folded
+                    .W_commitments
+                    .iter()
+                    .map(|W| W.coordinates_values().unwrap())
+                    .collect::<Vec<_>>();
+                let expected = self
+                    .expected
+                    .iter()
+                    .map(|W| {
+                        let coordinates = W.coordinates().unwrap();
+                        (*coordinates.x(), *coordinates.y())
+                    })
+                    .collect::<Vec<_>>();
+
+                assert_eq!(actual, expected);
+
+                Ok(())
+            }
+        }
+
+        #[traced_test]
+        #[test]
+        fn fold_commitments_delegate() {
+            MockProver::run(12, &FoldCommitmentsCircuit::<3>::new(false), vec![])
+                .unwrap()
+                .verify()
+                .unwrap();
+        }
+
+        #[traced_test]
+        #[test]
+        fn fold_commitments_on_circuit() {
+            MockProver::run(17, &FoldCommitmentsCircuit::<3>::new(true), vec![])
                 .unwrap()
                 .verify()
                 .unwrap();
         }
+
+        #[traced_test]
+        #[test]
+        fn fold_commitments_on_circuit_single_incoming() {
+            MockProver::run(17, &FoldCommitmentsCircuit::<1>::new(true), vec![])
+                .unwrap()
+                .verify()
+                .unwrap();
+        }
+
+        #[traced_test]
+        #[test]
+        fn fold_commitments_on_circuit_with_identity() {
+            MockProver::run(
+                17,
+                &FoldCommitmentsCircuit::<2>::with_identity(true, true),
+                vec![],
+            )
+            .unwrap()
+            .verify()
+            .unwrap();
+
+            MockProver::run(
+                17,
+                &FoldCommitmentsCircuit::<3>::with_identity(true, true),
+                vec![],
+            )
+            .unwrap()
+            .verify()
+            .unwrap();
+        }
     }
 }
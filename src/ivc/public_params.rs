@@ -245,7 +245,7 @@ where
         secondary: CircuitPublicParamsInput<'key, '_, A2, C2, RP2::Args, SC2>,
         limb_width: NonZeroUsize,
         limbs_count: NonZeroUsize,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, crate::Error> {
         let primary_num_io = iter::once(CONSISTENCY_MARKERS_COUNT)
             .chain(primary.step_circuit.instances().iter().map(Vec::len))
             .collect::<Box<[_]>>();
@@ -332,7 +332,7 @@ where
                 &mut RP1::OffCircuit::new(primary.ro_constant.clone()),
             )?;
 
-            Result::<_, Error>::Ok((secondary_S, secondary_initial_plonk_trace))
+            Result::<_, crate::Error>::Ok((secondary_S, secondary_initial_plonk_trace))
         }?;
 
         debug!("primary & secondary pp created");
@@ -360,7 +360,8 @@ where
 
         {
             let _primary_span = info_span!("digest").entered();
-            let digest = digest::DefaultHasher::digest_to_bits(&self_)?;
+            let digest =
+                digest::DefaultHasher::digest_to_bits(&self_).map_err(Error::WhileDigest)?;
 
             self_.digest_1 = into_curve_from_bits(digest.deref(), NUM_HASH_BITS);
             self_.digest_2 = into_curve_from_bits(digest.deref(), NUM_HASH_BITS);
@@ -148,7 +148,7 @@ where
         secondary: &SC2,
         secondary_z_0: [C2::Scalar; A2],
         num_steps: NonZeroUsize,
-    ) -> Result<(), Error>
+    ) -> Result<(), crate::Error>
     where
         RP1: ROPair<C1::Scalar, Config = MainGateConfig<T>>,
         RP2: ROPair<C2::Scalar, Config = MainGateConfig<T>>,
@@ -174,7 +174,7 @@ where
         secondary: &SC2,
         secondary_z_0: [C2::Scalar; A2],
         num_steps: NonZeroUsize,
-    ) -> Result<(), Error>
+    ) -> Result<(), crate::Error>
     where
         RP1: ROPair<C1::Scalar, Config = MainGateConfig<T>>,
         RP2: ROPair<C2::Scalar, Config = MainGateConfig<T>>,
@@ -202,7 +202,7 @@ where
         secondary: &SC2,
         secondary_z_0: [C2::Scalar; A2],
         debug_mode: bool,
-    ) -> Result<Self, Error>
+    ) -> Result<Self, crate::Error>
     where
         RP1: ROPair<C1::Scalar, Config = MainGateConfig<T>>,
         RP2: ROPair<C2::Scalar, Config = MainGateConfig<T>>,
@@ -409,7 +409,7 @@ where
         pp: &PublicParams<'_, A1, A2, T, C1, C2, SC1, SC2, RP1, RP2>,
         primary: &SC1,
         secondary: &SC2,
-    ) -> Result<(), Error>
+    ) -> Result<(), crate::Error>
     where
         RP1: ROPair<C1::Scalar, Config = MainGateConfig<T>>,
         RP2: ROPair<C2::Scalar, Config = MainGateConfig<T>>,
@@ -598,7 +598,7 @@ where
     pub fn verify<const T: usize, RP1, RP2>(
         &mut self,
         pp: &PublicParams<'_, A1, A2, T, C1, C2, SC1, SC2, RP1, RP2>,
-    ) -> Result<(), Error>
+    ) -> Result<(), crate::Error>
     where
         RP1: ROPair<C1::Scalar, Config = MainGateConfig<T>>,
         RP2: ROPair<C2::Scalar, Config = MainGateConfig<T>>,
@@ -689,7 +689,7 @@ where
         if errors.is_empty() {
             Ok(())
         } else {
-            Err(Error::VerifyFailed(errors))
+            Err(Error::VerifyFailed(errors).into())
         }
     }
 }
@@ -625,6 +625,14 @@ where
                 Halo2PlonkError::Synthesis
             })?;
 
+        #[cfg(debug_assertions)]
+        self.step_circuit
+            .validate_z_out(&assigned_input, &z_output)
+            .map_err(|err| {
+                error!("while validate z_out: {err:?}");
+                Halo2PlonkError::Synthesis
+            })?;
+
         let output_hash = layouter
             .assign_region(
                 || "generate output hash",
@@ -18,6 +18,10 @@ pub enum SynthesisError {
     Halo2(#[from] halo2_proofs::plonk::Error),
     #[error(transparent)]
     FoldError(#[from] fold_relaxed_plonk_instance_chip::Error),
+    #[error("`z_out[{index}]` reuses `z_in` cell, but step circuit requires distinct output")]
+    ZOutReusesZIn { index: usize },
+    #[error("`z_out[{index}]` has no value, step circuit must assign known witness")]
+    UnknownZOut { index: usize },
 }
 
 /// The `StepCircuit` trait represents a step in incremental computation in
@@ -76,6 +80,35 @@ pub trait StepCircuit<const ARITY: usize, F: PrimeField> {
     /// This setup is crucial for the functioning of the IVC-based system.
     fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config;
 
+    /// If `true`, cells of `z_out` returned by [`StepCircuit::synthesize_step`] must be distinct
+    /// from cells of `z_in`
+    ///
+    /// Step circuits that are allowed to pass input through unchanged (e.g. [`trivial::Circuit`])
+    /// must leave it `false`
+    const DISTINCT_Z_OUT: bool = false;
+
+    /// Checks `z_out` returned by [`StepCircuit::synthesize_step`] at runtime
+    ///
+    /// Called by augmented synthesis in debug builds to catch step circuits that accidentally
+    /// return their inputs, when [`StepCircuit::DISTINCT_Z_OUT`] is set
+    fn validate_z_out(
+        &self,
+        z_i: &[AssignedCell<F, F>; ARITY],
+        z_out: &[AssignedCell<F, F>; ARITY],
+    ) -> Result<(), SynthesisError> {
+        if !Self::DISTINCT_Z_OUT {
+            return Ok(());
+        }
+
+        match z_out
+            .iter()
+            .position(|z_out| z_i.iter().any(|z_i| z_i.cell() == z_out.cell()))
+        {
+            Some(index) => Err(SynthesisError::ZOutReusesZIn { index }),
+            None => Ok(()),
+        }
+    }
+
     /// Sythesize the circuit for a computation step and return variable
     /// that corresponds to the output of the step z_{i+1}
     /// this method will be called when we synthesize the IVC_Circuit
@@ -98,6 +131,12 @@ pub trait StepCircuit<const ARITY: usize, F: PrimeField> {
     /// equal to that specified in the IVC fold call. However, if these calculations are long and resource
     /// intensive, it is possible to implement this logic off-circuit "honestly" with regular code, which may
     /// be more lightweight, but will require consistency testing.
+    ///
+    /// Instance columns created by [`StepCircuit::configure`] are filled with
+    /// [`StepCircuit::instances`], so a step reading a missing instance fails with
+    /// [`SynthesisError::Halo2`] instead of panicking.
+    ///
+    /// Returns [`SynthesisError::UnknownZOut`] if some cell of `z_out` is assigned without value.
     #[instrument(skip_all)]
     fn process_step(
         &self,
@@ -109,7 +148,7 @@ pub trait StepCircuit<const ARITY: usize, F: PrimeField> {
         let config = Self::configure(&mut cs);
 
         let mut witness = WitnessCollector {
-            instances: vec![vec![F::ZERO, F::ZERO]],
+            instances: self.instances(),
             advice: vec![vec![F::ZERO.into(); 1 << k_table_size as usize]; cs.num_advice_columns()],
         };
         let mut layouter =
@@ -141,8 +180,182 @@ pub trait StepCircuit<const ARITY: usize, F: PrimeField> {
                 SynthesisError::Halo2(err)
             })?;
 
-        self.synthesize_step(config, &mut layouter, &assigned_z_i.try_into().unwrap())
-            .map(|z_out| z_out.map(|cell| cell.value().unwrap().copied().unwrap()))
+        let assigned_z_i = assigned_z_i.try_into().unwrap();
+        let z_out = self.synthesize_step(config, &mut layouter, &assigned_z_i)?;
+
+        #[cfg(debug_assertions)]
+        self.validate_z_out(&assigned_z_i, &z_out)?;
+
+        let z_out = z_out
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                cell.value()
+                    .unwrap()
+                    .copied()
+                    .ok_or(SynthesisError::UnknownZOut { index })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(z_out.try_into().unwrap())
+    }
+}
+
+pub mod memoized {
+    use std::sync::Mutex;
+
+    use halo2_proofs::{
+        circuit::{AssignedCell, Layouter},
+        plonk::ConstraintSystem,
+    };
+
+    use super::{StepCircuit, SynthesisError};
+    use crate::ff::PrimeField;
+
+    /// Input of the last [`StepCircuit::process_step`] call with its output
+    struct Cached<const ARITY: usize, F> {
+        z_i: [F; ARITY],
+        k_table_size: u32,
+        z_out: [F; ARITY],
+    }
+
+    /// Wrapper of step circuit that memoizes the last [`StepCircuit::process_step`] result
+    ///
+    /// Repeated call with the same `z_i` & `k_table_size` returns cached `z_out` without
+    /// synthesis of the inner circuit. All other methods are delegated to the inner circuit.
+    pub struct Memoized<const ARITY: usize, F: PrimeField, SC: StepCircuit<ARITY, F>> {
+        inner: SC,
+        last: Mutex<Option<Cached<ARITY, F>>>,
+    }
+
+    impl<const ARITY: usize, F: PrimeField, SC: StepCircuit<ARITY, F>> Memoized<ARITY, F, SC> {
+        pub fn new(inner: SC) -> Self {
+            Self {
+                inner,
+                last: Mutex::new(None),
+            }
+        }
+
+        pub fn inner(&self) -> &SC {
+            &self.inner
+        }
+
+        pub fn into_inner(self) -> SC {
+            self.inner
+        }
+    }
+
+    impl<const ARITY: usize, F: PrimeField, SC: StepCircuit<ARITY, F>> StepCircuit<ARITY, F>
+        for Memoized<ARITY, F, SC>
+    {
+        type Config = SC::Config;
+
+        const DISTINCT_Z_OUT: bool = SC::DISTINCT_Z_OUT;
+
+        fn instances(&self) -> Vec<Vec<F>> {
+            self.inner.instances()
+        }
+
+        fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+            SC::configure(cs)
+        }
+
+        fn validate_z_out(
+            &self,
+            z_i: &[AssignedCell<F, F>; ARITY],
+            z_out: &[AssignedCell<F, F>; ARITY],
+        ) -> Result<(), SynthesisError> {
+            self.inner.validate_z_out(z_i, z_out)
+        }
+
+        fn synthesize_step(
+            &self,
+            config: Self::Config,
+            layouter: &mut impl Layouter<F>,
+            z_i: &[AssignedCell<F, F>; ARITY],
+        ) -> Result<[AssignedCell<F, F>; ARITY], SynthesisError> {
+            self.inner.synthesize_step(config, layouter, z_i)
+        }
+
+        fn process_step(
+            &self,
+            z_i: &[F; ARITY],
+            k_table_size: u32,
+        ) -> Result<[F; ARITY], SynthesisError> {
+            let mut last = self.last.lock().unwrap();
+
+            if let Some(cached) = last
+                .as_ref()
+                .filter(|cached| cached.z_i.eq(z_i) && cached.k_table_size == k_table_size)
+            {
+                return Ok(cached.z_out);
+            }
+
+            let z_out = self.inner.process_step(z_i, k_table_size)?;
+            *last = Some(Cached {
+                z_i: *z_i,
+                k_table_size,
+                z_out,
+            });
+
+            Ok(z_out)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use halo2_proofs::{
+            circuit::{AssignedCell, Layouter},
+            halo2curves::pasta::Fq,
+            plonk::ConstraintSystem,
+        };
+
+        use super::{
+            super::{trivial, StepCircuit, SynthesisError},
+            Memoized,
+        };
+
+        /// Trivial circuit that counts its synthesis
+        #[derive(Default)]
+        struct CountingCircuit {
+            synthesized: AtomicUsize,
+        }
+
+        impl StepCircuit<2, Fq> for CountingCircuit {
+            type Config = ();
+
+            fn configure(_cs: &mut ConstraintSystem<Fq>) -> Self::Config {}
+
+            fn synthesize_step(
+                &self,
+                config: Self::Config,
+                layouter: &mut impl Layouter<Fq>,
+                z_i: &[AssignedCell<Fq, Fq>; 2],
+            ) -> Result<[AssignedCell<Fq, Fq>; 2], SynthesisError> {
+                self.synthesized.fetch_add(1, Ordering::Relaxed);
+                trivial::Circuit::default().synthesize_step(config, layouter, z_i)
+            }
+        }
+
+        #[test]
+        fn process_step_cached() {
+            let circuit = Memoized::new(CountingCircuit::default());
+            let z_i = [Fq::from(1), Fq::from(2)];
+            let synthesized = || circuit.inner().synthesized.load(Ordering::Relaxed);
+
+            assert_eq!(circuit.process_step(&z_i, 10).unwrap(), z_i);
+            assert_eq!(circuit.process_step(&z_i, 10).unwrap(), z_i);
+            assert_eq!(synthesized(), 1);
+
+            let z_other = [Fq::from(3), Fq::from(4)];
+            assert_eq!(circuit.process_step(&z_other, 10).unwrap(), z_other);
+            assert_eq!(synthesized(), 2);
+
+            assert_eq!(circuit.process_step(&z_other, 11).unwrap(), z_other);
+            assert_eq!(synthesized(), 3);
+        }
     }
 }
 
@@ -198,8 +411,13 @@ pub mod trivial {
     mod tests {
         use std::array;
 
-        use halo2_proofs::halo2curves::pasta::Fq;
+        use halo2_proofs::{
+            circuit::{AssignedCell, Layouter},
+            halo2curves::pasta::Fq,
+            plonk::ConstraintSystem,
+        };
 
+        use super::super::{StepCircuit, SynthesisError};
         use crate::util::mock_prover::MockProver;
 
         #[test]
@@ -210,5 +428,303 @@ pub mod trivial {
                 .verify(z_in)
                 .unwrap();
         }
+
+        /// Erroneous step circuit, which must calculate new values, but returns `z_in`
+        struct ReturnsInputCircuit;
+
+        impl StepCircuit<2, Fq> for ReturnsInputCircuit {
+            type Config = ();
+
+            const DISTINCT_Z_OUT: bool = true;
+
+            fn configure(_cs: &mut ConstraintSystem<Fq>) -> Self::Config {}
+
+            fn synthesize_step(
+                &self,
+                _config: Self::Config,
+                _layouter: &mut impl Layouter<Fq>,
+                z_i: &[AssignedCell<Fq, Fq>; 2],
+            ) -> Result<[AssignedCell<Fq, Fq>; 2], SynthesisError> {
+                Ok(z_i.clone())
+            }
+        }
+
+        #[cfg(debug_assertions)]
+        #[test]
+        fn z_out_reuses_z_in() {
+            assert!(matches!(
+                ReturnsInputCircuit.process_step(&[Fq::from(1), Fq::from(2)], 10),
+                Err(SynthesisError::ZOutReusesZIn { index: 0 })
+            ));
+
+            assert!(
+                MockProver::run(10, &ReturnsInputCircuit, vec![], [Fq::from(1), Fq::from(2)])
+                    .is_err()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{
+        circuit::{AssignedCell, Layouter, Value},
+        halo2curves::pasta::Fq,
+        plonk::{Advice, Column, ConstraintSystem, Expression, Instance, Selector, TableColumn},
+        poly::Rotation,
+    };
+
+    use super::{StepCircuit, SynthesisError};
+    use crate::{ff::Field, main_gate::RegionCtx, util::mock_prover::MockProver};
+
+    /// Step circuit calculating `z_out = z_in^2`, with `z_out` value taken from `square`
+    struct SquareCircuit {
+        square: fn(Value<Fq>) -> Value<Fq>,
+    }
+
+    #[derive(Clone)]
+    struct SquareConfig {
+        s: Selector,
+        col: Column<Advice>,
+    }
+
+    impl StepCircuit<1, Fq> for SquareCircuit {
+        type Config = SquareConfig;
+
+        fn configure(cs: &mut ConstraintSystem<Fq>) -> Self::Config {
+            let config = SquareConfig {
+                s: cs.selector(),
+                col: cs.advice_column(),
+            };
+            cs.enable_equality(config.col);
+
+            cs.create_gate("square", |meta| {
+                let s = meta.query_selector(config.s);
+                let z_in = meta.query_advice(config.col, Rotation::cur());
+                let z_out = meta.query_advice(config.col, Rotation::next());
+
+                vec![s * (z_in.clone() * z_in - z_out)]
+            });
+
+            config
+        }
+
+        fn synthesize_step(
+            &self,
+            config: Self::Config,
+            layouter: &mut impl Layouter<Fq>,
+            z_i: &[AssignedCell<Fq, Fq>; 1],
+        ) -> Result<[AssignedCell<Fq, Fq>; 1], SynthesisError> {
+            let z_out = layouter.assign_region(
+                || "square",
+                |region| {
+                    let mut region = RegionCtx::new(region, 0);
+
+                    region.enable_selector(&config.s)?;
+                    let z_in = z_i[0].copy_advice(|| "z_in", &mut region.region, config.col, 0)?;
+                    region.next();
+
+                    region.assign_advice(
+                        || "z_out",
+                        config.col,
+                        (self.square)(z_in.value().copied()),
+                    )
+                },
+            )?;
+
+            Ok([z_out])
+        }
+    }
+
+    #[test]
+    fn process_step_square() {
+        let circuit = SquareCircuit {
+            square: |z_in| z_in.map(|z_in| z_in.square()),
+        };
+
+        assert_eq!(
+            circuit.process_step(&[Fq::from(3)], 10).unwrap(),
+            [Fq::from(9)]
+        );
+    }
+
+    #[test]
+    fn process_step_square_matches_mock_prover() {
+        let circuit = SquareCircuit {
+            square: |z_in| z_in.map(|z_in| z_in.square()),
+        };
+        let z_in = [Fq::from(3)];
+
+        MockProver::run(10, &circuit, vec![], z_in)
+            .unwrap()
+            .verify(circuit.process_step(&z_in, 10).unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn process_step_unknown_z_out() {
+        let circuit = SquareCircuit {
+            square: |_| Value::unknown(),
+        };
+
+        assert!(matches!(
+            circuit.process_step(&[Fq::from(3)], 10),
+            Err(SynthesisError::UnknownZOut { index: 0 })
+        ));
+    }
+
+    /// Step circuit calculating `z_out = z_in + 1`, with `z_in` range-checked by lookup
+    struct AddOneCircuit;
+
+    #[derive(Clone)]
+    struct AddOneConfig {
+        s: Selector,
+        input: Column<Advice>,
+        output: Column<Advice>,
+        range: TableColumn,
+    }
+
+    const RANGE: u64 = 16;
+
+    impl StepCircuit<2, Fq> for AddOneCircuit {
+        type Config = AddOneConfig;
+
+        fn configure(cs: &mut ConstraintSystem<Fq>) -> Self::Config {
+            let config = AddOneConfig {
+                s: cs.complex_selector(),
+                input: cs.advice_column(),
+                output: cs.advice_column(),
+                range: cs.lookup_table_column(),
+            };
+            cs.enable_equality(config.input);
+            cs.enable_equality(config.output);
+
+            cs.create_gate("add one", |meta| {
+                let s = meta.query_selector(config.s);
+                let input = meta.query_advice(config.input, Rotation::cur());
+                let output = meta.query_advice(config.output, Rotation::cur());
+
+                vec![s * (input + Expression::Constant(Fq::ONE) - output)]
+            });
+
+            cs.lookup("range", |meta| {
+                let s = meta.query_selector(config.s);
+                let input = meta.query_advice(config.input, Rotation::cur());
+
+                vec![(s * input, config.range)]
+            });
+
+            config
+        }
+
+        fn synthesize_step(
+            &self,
+            config: Self::Config,
+            layouter: &mut impl Layouter<Fq>,
+            z_i: &[AssignedCell<Fq, Fq>; 2],
+        ) -> Result<[AssignedCell<Fq, Fq>; 2], SynthesisError> {
+            layouter.assign_table(
+                || "range",
+                |mut table| {
+                    (0..RANGE).try_for_each(|value| {
+                        table.assign_cell(
+                            || "range",
+                            config.range,
+                            value as usize,
+                            || Value::known(Fq::from(value)),
+                        )
+                    })
+                },
+            )?;
+
+            let z_out = layouter.assign_region(
+                || "add one",
+                |region| {
+                    let mut region = RegionCtx::new(region, 0);
+
+                    z_i.iter()
+                        .map(|z_i| {
+                            region.enable_selector(&config.s)?;
+                            let input = z_i.copy_advice(
+                                || "z_in",
+                                &mut region.region,
+                                config.input,
+                                region.offset,
+                            )?;
+                            let output = region.assign_advice(
+                                || "z_out",
+                                config.output,
+                                input.value().map(|input| *input + Fq::ONE),
+                            )?;
+                            region.next();
+
+                            Ok(output)
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                },
+            )?;
+
+            Ok(z_out.try_into().unwrap())
+        }
+    }
+
+    #[test]
+    fn process_step_matches_mock_prover() {
+        let z_in = [Fq::from(3), Fq::from(RANGE - 1)];
+
+        let z_out = AddOneCircuit.process_step(&z_in, 10).unwrap();
+        assert_eq!(z_out, [Fq::from(4), Fq::from(RANGE)]);
+
+        MockProver::run(10, &AddOneCircuit, vec![], z_in)
+            .unwrap()
+            .verify(z_out)
+            .unwrap();
+    }
+
+    /// Step circuit returning its only instance as `z_out`
+    struct InstanceCircuit(Fq);
+
+    impl StepCircuit<1, Fq> for InstanceCircuit {
+        type Config = (Column<Instance>, Column<Advice>);
+
+        fn instances(&self) -> Vec<Vec<Fq>> {
+            vec![vec![self.0]]
+        }
+
+        fn configure(cs: &mut ConstraintSystem<Fq>) -> Self::Config {
+            let instance = cs.instance_column();
+            let advice = cs.advice_column();
+            cs.enable_equality(instance);
+            cs.enable_equality(advice);
+
+            (instance, advice)
+        }
+
+        fn synthesize_step(
+            &self,
+            (instance, advice): Self::Config,
+            layouter: &mut impl Layouter<Fq>,
+            _z_i: &[AssignedCell<Fq, Fq>; 1],
+        ) -> Result<[AssignedCell<Fq, Fq>; 1], SynthesisError> {
+            let z_out = layouter.assign_region(
+                || "z_out",
+                |mut region| region.assign_advice_from_instance(|| "z_out", instance, 0, advice, 0),
+            )?;
+
+            Ok([z_out])
+        }
+    }
+
+    #[test]
+    fn process_step_with_instances() {
+        let circuit = InstanceCircuit(Fq::from(5));
+
+        let z_out = circuit.process_step(&[Fq::ONE], 10).unwrap();
+        assert_eq!(z_out, [Fq::from(5)]);
+
+        MockProver::run(10, &circuit, circuit.instances(), [Fq::ONE])
+            .unwrap()
+            .verify(z_out)
+            .unwrap();
     }
 }
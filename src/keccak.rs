@@ -0,0 +1,207 @@
+//! Off-circuit random oracle on top of Keccak-256, e.g. for transcripts checked by an EVM verifier
+//!
+//! Absorbed elements are buffered & each squeeze hashes the whole buffer, as
+//! [`crate::poseidon::PoseidonHash`] does, so the transcript is the same sequence of absorbs &
+//! squeezes for both of them.
+//!
+//! Encoding follows the EVM: each element is a 32-byte big-endian word, as `abi.encodePacked` of
+//! `uint256` values, & a challenge is the digest as `uint256` truncated to its low `num_bits`.
+//!
+//! # Not implemented
+//!
+//! Only the off-circuit [`ROTrait`] is provided. The on-circuit
+//! [`crate::poseidon::ROCircuitTrait`] gadget is not implemented, and neither is the test that
+//! checks on-circuit challenges against off-circuit ones. Keccak on top of the main gate needs a
+//! bitwise lookup table chip, which the crate doesn't have. Until then [`KeccakHash`] can't be
+//! used by the IVC step circuit, only by off-circuit verifiers.
+
+use std::num::NonZeroUsize;
+
+use sha3::{Digest, Keccak256};
+use tracing::*;
+
+use crate::{
+    ff::PrimeField,
+    halo2curves::CurveAffine,
+    poseidon::{ROConstantsTrait, ROTrait},
+};
+
+/// [`KeccakHash`] has no parameters, `r_f` & `r_p` are ignored
+#[derive(Clone, Debug, Default)]
+pub struct KeccakConstants;
+
+impl ROConstantsTrait for KeccakConstants {
+    fn new(_r_f: usize, _r_p: usize) -> Self {
+        Self
+    }
+}
+
+/// [`PrimeField::Repr`] is little-endian for the curves of the crate, the EVM word is big-endian
+fn to_be_word<F: PrimeField>(value: &F) -> F::Repr {
+    let mut repr = value.to_repr();
+    repr.as_mut().reverse();
+    repr
+}
+
+#[derive(Clone, Debug)]
+pub struct KeccakHash<F: PrimeField> {
+    buf: Vec<F>,
+}
+
+impl<F: PrimeField> KeccakHash<F> {
+    /// Keccak-256 of the big-endian words of all absorbed elements, truncated to `num_bits`
+    ///
+    /// The digest is read as a big-endian `uint256` & its low `num_bits` are taken, as
+    /// `uint256(keccak256(..)) & ((1 << num_bits) - 1)` on the EVM side
+    pub fn output<F1: PrimeField>(&self, num_bits: NonZeroUsize) -> F1 {
+        debug!("Off circuit input of keccak: {:?}", self.buf);
+
+        let digest = self
+            .buf
+            .iter()
+            .fold(Keccak256::new(), |hasher, value| {
+                hasher.chain_update(to_be_word(value))
+            })
+            .finalize();
+
+        // From the least significant bit of the big-endian digest
+        digest
+            .iter()
+            .rev()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+            .take(num_bits.get())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .fold(F1::ZERO, |acc, bit| {
+                acc.double() + if bit { F1::ONE } else { F1::ZERO }
+            })
+    }
+}
+
+impl<F: PrimeField> ROTrait<F> for KeccakHash<F> {
+    type Constants = KeccakConstants;
+
+    fn new(_constants: Self::Constants) -> Self {
+        Self { buf: Vec::new() }
+    }
+
+    fn absorb_field(&mut self, base: F) -> &mut Self {
+        self.buf.push(base);
+        self
+    }
+
+    fn absorb_point<C: CurveAffine<Base = F>>(&mut self, point: &C) -> &mut Self {
+        let coordinates: Option<_> = point.coordinates().into();
+        match coordinates {
+            Some(coordinates) => self.buf.extend([*coordinates.x(), *coordinates.y()]),
+            // C is infinity
+            None => self.buf.extend([F::ZERO, F::ZERO]),
+        }
+
+        self
+    }
+
+    fn inspect(&mut self, inspect: impl FnOnce(&[F])) -> &mut Self {
+        inspect(&self.buf);
+        self
+    }
+
+    #[instrument(skip_all)]
+    fn squeeze<C: CurveAffine<Base = F>>(&mut self, num_bits: NonZeroUsize) -> C::Scalar {
+        self.output::<C::Scalar>(num_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::NUM_CHALLENGE_BITS,
+        ff::Field,
+        group::{prime::PrimeCurveAffine, Curve},
+        halo2curves::bn256::{Fq as Base, Fr as Scalar, G1Affine as Affine},
+        plonk::PlonkInstance,
+        sps::{Error as SpsError, SpecialSoundnessVerifier},
+        util::fe_to_bits_le,
+    };
+
+    fn ro() -> KeccakHash<Base> {
+        KeccakHash::new(KeccakConstants)
+    }
+
+    #[test]
+    fn squeeze_truncates_to_num_bits() {
+        let mut ro = ro();
+        ro.absorb_field_iter((0..5u64).map(Base::from));
+
+        let challenge = ro.squeeze::<Affine>(NUM_CHALLENGE_BITS);
+        assert!(fe_to_bits_le(&challenge)
+            .iter()
+            .skip(NUM_CHALLENGE_BITS.get())
+            .all(|bit| !bit));
+
+        // Same transcript, same challenge
+        assert_eq!(ro.squeeze::<Affine>(NUM_CHALLENGE_BITS), challenge);
+
+        let mut other = self::ro();
+        other.absorb_field_iter((1..6u64).map(Base::from));
+        assert_ne!(other.squeeze::<Affine>(NUM_CHALLENGE_BITS), challenge);
+    }
+
+    /// Same challenge as `uint256(keccak256(abi.encode(1, 2))) & ((1 << 128) - 1)` in Solidity
+    #[test]
+    fn evm_compatible() {
+        let mut ro = ro();
+        ro.absorb_field_iter([Base::from(1), Base::from(2)].into_iter());
+
+        assert_eq!(
+            ro.squeeze::<Affine>(NUM_CHALLENGE_BITS),
+            Scalar::from_u128(0xc83a08bbccc01a0644d599ccd2a7c2e0)
+        );
+    }
+
+    #[test]
+    fn identity_is_absorbed_as_zeros() {
+        let mut with_identity = ro();
+        with_identity.absorb_point(&Affine::identity());
+
+        let mut with_zeros = ro();
+        with_zeros.absorb_field_iter([Base::ZERO, Base::ZERO].into_iter());
+
+        assert_eq!(
+            with_identity.squeeze::<Affine>(NUM_CHALLENGE_BITS),
+            with_zeros.squeeze::<Affine>(NUM_CHALLENGE_BITS)
+        );
+    }
+
+    #[test]
+    fn sps_verify() {
+        let W_commitments = (1..=2u64)
+            .map(|i| (Affine::generator() * Scalar::from(i)).to_affine())
+            .collect::<Vec<_>>();
+
+        let mut transcript = ro();
+        let challenges = W_commitments
+            .iter()
+            .map(|W| {
+                transcript
+                    .absorb_point(W)
+                    .squeeze::<Affine>(NUM_CHALLENGE_BITS)
+            })
+            .collect();
+
+        let mut instance = PlonkInstance {
+            W_commitments,
+            instances: vec![],
+            challenges,
+        };
+        assert_eq!(instance.sps_verify(&mut ro()), Ok(()));
+
+        instance.challenges[1] += Scalar::ONE;
+        assert_eq!(
+            instance.sps_verify(&mut ro()),
+            Err(SpsError::ChallengeNotMatch { challenge_index: 1 })
+        );
+    }
+}
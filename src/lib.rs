@@ -9,16 +9,22 @@ pub mod digest;
 pub mod fft;
 pub mod gadgets;
 pub mod ivc;
+pub mod keccak;
 pub mod main_gate;
+pub mod metrics;
 pub mod nifs;
 pub mod plonk;
 pub mod polynomial;
 pub mod poseidon;
 pub mod sps;
 pub mod table;
+pub mod testing;
 pub mod util;
 
 pub mod error;
+pub use error::SiriusError as Error;
+
+pub use nifs::protogalaxy::debug_dump;
 
 pub use halo2_proofs::{
     self, halo2curves,
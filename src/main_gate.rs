@@ -32,6 +32,11 @@ impl<'a, F: PrimeField> RegionCtx<'a, F> {
         self.offset
     }
 
+    /// Rows assigned since `start`, an [`RegionCtx::offset`] taken before the assignment
+    pub fn rows_used(&self, start: usize) -> usize {
+        self.offset.saturating_sub(start)
+    }
+
     pub fn into_region(self) -> Region<'a, F> {
         self.region
     }
@@ -195,6 +200,13 @@ mod assign_advice_from {
 }
 pub use assign_advice_from::AssignAdviceFrom;
 
+/// Affine coordinates of `point` & `is_infinity` flag, the identity point is `(0, 0)`
+fn point_coordinates<C: CurveAffine>(point: &C) -> (C::Base, C::Base, bool) {
+    Option::<Coordinates<C>>::from(point.coordinates())
+        .map(|coordinates| (*coordinates.x(), *coordinates.y(), false))
+        .unwrap_or((C::Base::ZERO, C::Base::ZERO, true))
+}
+
 #[derive(Clone, Debug)]
 pub enum WrapValue<F: PrimeField> {
     Assigned(AssignedValue<F>),
@@ -366,7 +378,11 @@ impl<const T: usize> MainGateConfig<T> {
 
     /// Return an auxiliary struct that allow to cyclically assign values to any [`Advice`] column,
     /// inrement rows via [`RegionCtx::next`] when run out of columns.
-    pub fn advice_cycle_assigner<'s, F: PrimeField>(&'s self) -> impl 's + AdviceCyclicAssignor<F> {
+    ///
+    /// The same assigner also copies cells, see [`AdviceCyclicCopier`]
+    pub fn advice_cycle_assigner<'s, F: PrimeField>(
+        &'s self,
+    ) -> impl 's + AdviceCyclicAssignor<F> + AdviceCyclicCopier<F> {
         AdviceCyclicAssignorIter::<'s, _> {
             iter: self.iter_advice_columns().enumerate().cycle(),
             first_pass: true,
@@ -391,6 +407,7 @@ macro_rules! create_column_cycle {
         $column_type:ty,
         $assign_next_fn_name:ident,
         $assign_point_fn_name:ident,
+        $assign_all_points_fn_name:ident,
         $assign_next_collection_fn_name:ident,
         $region_assign_fn:ident,
         $value_wrapper:expr
@@ -400,6 +417,24 @@ macro_rules! create_column_cycle {
             first_pass: bool,
         }
 
+        impl<'a, I: Iterator<Item = (usize, &'a Column<$column_type>)>> $struct_name<'a, I> {
+            /// Next column of the cycle, moves `region` to the next row when all are used
+            fn next_column<F: PrimeField>(
+                &mut self,
+                region: &mut RegionCtx<'_, F>,
+            ) -> Column<$column_type> {
+                let (index, column) = self.iter.by_ref().next().expect("Safe because cycle");
+
+                if !self.first_pass && index == 0 {
+                    region.next();
+                }
+
+                self.first_pass = false;
+
+                *column
+            }
+        }
+
         pub trait $trait_name<F: PrimeField> {
             fn $assign_next_fn_name<AR: Into<String>>(
                 &mut self,
@@ -421,6 +456,16 @@ macro_rules! create_column_cycle {
                 annotation: impl Fn() -> AR,
                 point: &C,
             ) -> Result<AssignedPoint<C>, halo2_proofs::plonk::Error>;
+
+            /// Assign `points` one after another, so their coordinates take consecutive cells
+            ///
+            /// Annotation of point `i` is `annotation[i]`
+            fn $assign_all_points_fn_name<'p, C: 'p + CurveAffine, AR: Into<String>>(
+                &mut self,
+                region: &mut RegionCtx<'_, C::Base>,
+                annotation: impl Fn() -> AR,
+                points: impl Iterator<Item = &'p C>,
+            ) -> Result<Vec<AssignedPoint<C>>, halo2_proofs::plonk::Error>;
         }
 
         impl<'a, I, F> $trait_name<F> for $struct_name<'a, I>
@@ -434,16 +479,10 @@ macro_rules! create_column_cycle {
                 annotation: impl Fn() -> AR,
                 value: F,
             ) -> Result<AssignedCell<F, F>, halo2_proofs::plonk::Error> {
-                let (index, column) = self.iter.by_ref().next().expect("Safe because cycle");
-
-                if !self.first_pass && index == 0 {
-                    region.next();
-                }
-
-                self.first_pass = false;
+                let column = self.next_column(region);
 
                 let wrapper = $value_wrapper;
-                region.$region_assign_fn(annotation, *column, wrapper(value))
+                region.$region_assign_fn(annotation, column, wrapper(value))
             }
 
             fn $assign_next_collection_fn_name<AR: Into<String>>(
@@ -464,21 +503,29 @@ macro_rules! create_column_cycle {
                 point: &C,
             ) -> Result<AssignedPoint<C>, halo2_proofs::plonk::Error> {
                 let annotation = annotation().into();
-                let coordinates = point.coordinates().unwrap();
+                let (x, y, _is_infinity) = point_coordinates(point);
 
                 Ok(AssignedPoint {
-                    x: self.$assign_next_fn_name(
-                        region,
-                        || format!("{}.x", annotation),
-                        *coordinates.x(),
-                    )?,
-                    y: self.$assign_next_fn_name(
-                        region,
-                        || format!("{}.y", annotation),
-                        *coordinates.y(),
-                    )?,
+                    x: self.$assign_next_fn_name(region, || format!("{}.x", annotation), x)?,
+                    y: self.$assign_next_fn_name(region, || format!("{}.y", annotation), y)?,
                 })
             }
+
+            fn $assign_all_points_fn_name<'p, C: 'p + CurveAffine, AR: Into<String>>(
+                &mut self,
+                region: &mut RegionCtx<'_, C::Base>,
+                annotation: impl Fn() -> AR,
+                points: impl Iterator<Item = &'p C>,
+            ) -> Result<Vec<AssignedPoint<C>>, halo2_proofs::plonk::Error> {
+                let annotation = annotation().into();
+
+                points
+                    .enumerate()
+                    .map(|(i, point)| {
+                        self.$assign_point_fn_name(region, || format!("{annotation}[{i}]"), point)
+                    })
+                    .collect()
+            }
         }
     };
 }
@@ -489,6 +536,7 @@ create_column_cycle!(
     Fixed,
     assign_next_fixed,
     assign_next_fixed_point,
+    assign_all_fixed_points,
     assign_all_fixed,
     assign_fixed,
     |value| value
@@ -500,11 +548,39 @@ create_column_cycle!(
     Advice,
     assign_next_advice,
     assign_next_advice_point,
+    assign_all_advice_points,
     assign_all_advice,
     assign_advice,
     |value| Value::known(value)
 );
 
+/// Copies of already assigned cells, placed like the values of [`AdviceCyclicAssignor`]
+pub trait AdviceCyclicCopier<F: PrimeField> {
+    /// Assign a copy of `cell` in the next advice cell, constrained to be equal to `cell`
+    fn copy_next_advice<AR: Into<String>>(
+        &mut self,
+        region: &mut RegionCtx<'_, F>,
+        annotation: impl Fn() -> AR,
+        cell: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error>;
+}
+
+impl<'a, I, F> AdviceCyclicCopier<F> for AdviceCyclicAssignorIter<'a, I>
+where
+    I: Iterator<Item = (usize, &'a Column<Advice>)>,
+    F: PrimeField,
+{
+    fn copy_next_advice<AR: Into<String>>(
+        &mut self,
+        region: &mut RegionCtx<'_, F>,
+        annotation: impl Fn() -> AR,
+        cell: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let column = self.next_column(region);
+        region.assign_advice_from(annotation, column, cell)
+    }
+}
+
 #[derive(Debug)]
 pub struct MainGate<F: PrimeField, const T: usize> {
     config: MainGateConfig<T>,
@@ -925,4 +1001,25 @@ mod tests {
             "r_1 * r_1 * r_1 * (Z_14 * Z_13 * Z_4 + r_1 * Z_15 * Z_6 + r_1 * r_1 * Z_8 + r_1 * Z_16 * Z_7) + r_1 * r_1 * r_1 * r_1 * Z_13 * Z_0 + Z_13 * Z_13 * Z_13 * Z_13 * Z_13 * Z_2 + r_1 * r_1 * r_1 * r_1 * Z_14 * Z_1 + Z_14 * Z_14 * Z_14 * Z_14 * Z_14 * Z_3"
         );
     }
+
+    #[test]
+    fn point_coordinates() {
+        use crate::{
+            ff::Field,
+            group::prime::PrimeCurveAffine,
+            halo2curves::bn256::{Fq, G1Affine},
+        };
+
+        assert_eq!(
+            super::point_coordinates(&G1Affine::identity()),
+            (Fq::ZERO, Fq::ZERO, true)
+        );
+
+        let generator = G1Affine::generator();
+        let coordinates = generator.coordinates().unwrap();
+        assert_eq!(
+            super::point_coordinates(&generator),
+            (*coordinates.x(), *coordinates.y(), false)
+        );
+    }
 }
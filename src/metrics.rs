@@ -0,0 +1,116 @@
+//! Prover & verifier metrics
+//!
+//! With the `metrics` feature enabled, counters, gauges & histograms are emitted through the
+//! [`metrics`](https://docs.rs/metrics) facade, so any recorder (e.g. prometheus exporter) can be
+//! installed by the user. Without the feature all functions of this module are no-op.
+//!
+//! Names of metrics are stable & listed below as constants.
+
+// `std::time::Instant::now` panics on `wasm32-unknown-unknown`, `web-time` is `std` elsewhere
+#[cfg(feature = "metrics")]
+use web_time::Instant;
+
+/// Counter, number of [`crate::nifs::protogalaxy::ProtoGalaxy`] folds
+pub const FOLD_TOTAL: &str = "sirius_fold_total";
+/// Histogram, duration of the whole fold in seconds
+pub const FOLD_DURATION_SECONDS: &str = "sirius_fold_duration_seconds";
+/// Histogram, duration of the fold phase in seconds, labeled with `phase`:
+/// `compute_F`, `compute_K`, `fold_trace`
+pub const FOLD_PHASE_DURATION_SECONDS: &str = "sirius_fold_phase_duration_seconds";
+/// Gauge, size of the accumulator witness in bytes after the last fold
+pub const ACCUMULATOR_WITNESS_BYTES: &str = "sirius_accumulator_witness_bytes";
+/// Counter, number of [`crate::commitment::CommitmentKey::commit`] calls
+pub const COMMIT_TOTAL: &str = "sirius_commit_total";
+/// Histogram, number of points in the MSM of each commitment
+pub const MSM_SIZE: &str = "sirius_msm_size";
+/// Counter, number of rounds of special soundness protocol
+pub const SPS_ROUNDS_TOTAL: &str = "sirius_sps_rounds_total";
+/// Counter, number of [`crate::nifs::protogalaxy::ProtoGalaxy`] verifications
+pub const VERIFY_TOTAL: &str = "sirius_verify_total";
+/// Histogram, duration of the verification in seconds
+pub const VERIFY_DURATION_SECONDS: &str = "sirius_verify_duration_seconds";
+
+/// Label of [`FOLD_PHASE_DURATION_SECONDS`]
+pub const PHASE_LABEL: &str = "phase";
+
+#[inline]
+pub(crate) fn increment_counter(name: &'static str, value: u64) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(name).increment(value);
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = (name, value);
+}
+
+#[inline]
+pub(crate) fn record_histogram(name: &'static str, value: f64) {
+    #[cfg(feature = "metrics")]
+    ::metrics::histogram!(name).record(value);
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = (name, value);
+}
+
+#[inline]
+pub(crate) fn set_gauge(name: &'static str, value: f64) {
+    #[cfg(feature = "metrics")]
+    ::metrics::gauge!(name).set(value);
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = (name, value);
+}
+
+/// Records elapsed time into histogram on drop
+#[must_use]
+pub(crate) struct Timer {
+    #[cfg(feature = "metrics")]
+    name: &'static str,
+    #[cfg(feature = "metrics")]
+    phase: Option<&'static str>,
+    #[cfg(feature = "metrics")]
+    start: Instant,
+}
+
+impl Timer {
+    #[inline]
+    pub(crate) fn start(name: &'static str) -> Self {
+        Self::new(name, None)
+    }
+
+    /// Timer of [`FOLD_PHASE_DURATION_SECONDS`] with `phase` label
+    #[inline]
+    pub(crate) fn phase(phase: &'static str) -> Self {
+        Self::new(FOLD_PHASE_DURATION_SECONDS, Some(phase))
+    }
+
+    #[inline]
+    fn new(name: &'static str, phase: Option<&'static str>) -> Self {
+        #[cfg(not(feature = "metrics"))]
+        let _ = (name, phase);
+
+        Self {
+            #[cfg(feature = "metrics")]
+            name,
+            #[cfg(feature = "metrics")]
+            phase,
+            #[cfg(feature = "metrics")]
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Timer {
+    #[inline]
+    fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        {
+            let elapsed = self.start.elapsed().as_secs_f64();
+            match self.phase {
+                Some(phase) => {
+                    ::metrics::histogram!(self.name, PHASE_LABEL => phase).record(elapsed)
+                }
+                None => ::metrics::histogram!(self.name).record(elapsed),
+            }
+        }
+    }
+}
@@ -1,30 +1,42 @@
 use std::iter;
 
+use serde::{Deserialize, Serialize};
+
+use super::{
+    field_byte_size, point_byte_size,
+    poly::{get_count_of_valuation, PolyChallenges},
+    seq_byte_size, Challenges, Proof, ProtoGalaxy, ProverParam, SEQ_LEN_BYTES,
+};
 use crate::{
-    ff::Field,
+    commitment::CommitmentKey,
+    ff::{Field, PrimeField},
     halo2curves::CurveAffine,
-    plonk::{self, PlonkInstance, PlonkTrace, PlonkWitness},
+    plonk::{self, PlonkInstance, PlonkStructure, PlonkTrace, PlonkWitness},
     poseidon::{AbsorbInRO, ROTrait},
-    util::ScalarToBase,
+    sps::{self, SpecialSoundnessVerifier},
+    util::{parallel, serde_repr, ScalarToBase},
 };
 
 /// Represents an accumulator for folding multiple instances into a single instance,
 /// following the accumulation schemes.
 ///
 /// TODO#266 Docs
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct Accumulator<C: CurveAffine> {
     /// `φ`: Represents the combined state of all instances & witnesses. It is a summary that
     /// captures the essential data and relationships from the instances being merged.
-    pub(super) trace: PlonkTrace<C>,
+    pub(crate) trace: PlonkTrace<C>,
 
     /// `β`: A random value used in the folding process. It helps ensure the unique
     /// and secure combination of instances, preventing manipulation.
-    pub(super) betas: Box<[C::ScalarExt]>,
+    #[serde(with = "serde_repr::fields")]
+    pub(crate) betas: Box<[C::ScalarExt]>,
 
     /// `e`: an accumulated value that encapsulates the result of the folding operation. it serves
     /// as a concise representation of the correctness and properties of the folded instances.
-    pub(super) e: C::ScalarExt,
+    #[serde(with = "serde_repr::field")]
+    pub(crate) e: C::ScalarExt,
 }
 
 impl<C: CurveAffine, RO: ROTrait<C::Base>> AbsorbInRO<C::Base, RO> for Accumulator<C> {
@@ -48,11 +60,124 @@ impl<C: CurveAffine> Accumulator<C> {
             trace: PlonkTrace::new(args),
         }
     }
+
+    /// Public part & witness of the accumulator, e.g. for a custom prover, nothing is cloned
+    ///
+    /// Inverse of [`Accumulator::from_parts`]
+    pub fn split(self) -> (AccumulatorInstance<C>, PlonkWitness<C::ScalarExt>) {
+        let Self {
+            trace: PlonkTrace { u: ins, w },
+            betas,
+            e,
+        } = self;
+
+        (AccumulatorInstance { ins, betas, e }, w)
+    }
+
+    /// Accumulator from the parts returned by [`Accumulator::split`]
+    ///
+    /// Nothing is checked: it's up to the caller to pass the witness of this instance,
+    /// otherwise the accumulator is invalid, see [`ProtoGalaxy::is_sat`]
+    pub fn from_parts(
+        instance: AccumulatorInstance<C>,
+        witness: PlonkWitness<C::ScalarExt>,
+    ) -> Self {
+        instance.into_acc(witness)
+    }
+
+    /// Size in bytes of the serialized accumulator for structure `S`, known before any fold
+    ///
+    /// Field elements take `|F|` bytes of [`crate::ff::PrimeField::Repr`], points take `|C|`
+    /// bytes of the compressed encoding, each sequence is prefixed with its `u64` length:
+    /// ```text
+    /// W_commitments: 8 + round_sizes.len() * |C|
+    /// instances:     8 + sum(8 + num_io[i] * |F|)
+    /// challenges:    8 + num_challenges * |F|
+    /// W:             8 + sum(8 + round_sizes[i] * |F|)
+    /// betas:         8 + 2^k * gates.len() * |F|
+    /// e:             |F|
+    /// ```
+    pub fn byte_size(S: &PlonkStructure<C::ScalarExt>) -> usize {
+        let field = field_byte_size::<C::ScalarExt>();
+        let nested_seq_byte_size = |lens: &[usize]| {
+            SEQ_LEN_BYTES
+                + lens
+                    .iter()
+                    .map(|len| seq_byte_size(*len, field))
+                    .sum::<usize>()
+        };
+        let count_of_evaluation = get_count_of_valuation(S).map_or(0, |count| count.get());
+
+        seq_byte_size(S.round_sizes.len(), point_byte_size::<C>())
+            + nested_seq_byte_size(&S.num_io)
+            + seq_byte_size(S.num_challenges, field)
+            + nested_seq_byte_size(&S.round_sizes)
+            + seq_byte_size(count_of_evaluation, field)
+            + field
+    }
+
+    /// Computes the proof of folding `incoming` into `self`, without folding
+    ///
+    /// Useful when the proof is needed before committing to the next accumulator, e.g. to feed
+    /// the verifier circuit. The folded accumulator is produced by [`Accumulator::apply_fold`]
+    pub fn prove_fold<const L: usize>(
+        &self,
+        ck: &CommitmentKey<C>,
+        pp: &ProverParam<C>,
+        incoming: &[PlonkTrace<C>; L],
+        ro_acc: &mut (impl ROTrait<C::Base> + Send),
+    ) -> Result<Proof<C::ScalarExt>, crate::Error> {
+        parallel::install(pp.parallelism, || {
+            ProtoGalaxy::<C, L>::prove_fold(ck, pp, ro_acc, self, incoming).map(|(proof, _)| proof)
+        })
+    }
+
+    /// Folds `incoming` into `self` with the `proof` of [`Accumulator::prove_fold`]
+    ///
+    /// Challenges are derived again from `ro_acc`, so it must be in the same state as the one
+    /// passed to [`Accumulator::prove_fold`]
+    pub fn apply_fold<const L: usize>(
+        self,
+        pp: &ProverParam<C>,
+        incoming: &[PlonkTrace<C>; L],
+        proof: &Proof<C::ScalarExt>,
+        ro_acc: &mut impl ROTrait<C::Base>,
+    ) -> Self {
+        let challenges =
+            Challenges::generate::<_, C>(pp, ro_acc, &self, incoming.iter().map(|tr| &tr.u), proof);
+
+        ProtoGalaxy::<C, L>::apply_fold(self, incoming, proof, &challenges)
+    }
+
+    /// [`Accumulator::prove_fold`] & [`Accumulator::apply_fold`] at once, challenges are
+    /// derived only once
+    pub fn fold<const L: usize>(
+        self,
+        ck: &CommitmentKey<C>,
+        pp: &ProverParam<C>,
+        incoming: &[PlonkTrace<C>; L],
+        ro_acc: &mut (impl ROTrait<C::Base> + Send),
+    ) -> Result<(Self, Proof<C::ScalarExt>), crate::Error> {
+        ProtoGalaxy::<C, L>::prove(ck, pp, ro_acc, self, incoming)
+    }
+}
+
+/// Gate evaluation at a row, see [`plonk::iter_evaluate_witness`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GateRow {
+    pub gate: usize,
+    pub row: usize,
 }
 
 /// Represents an accumulator for folding multiple instances into a single instance,
 /// following the accumulation schemes.
-#[derive(Debug, PartialEq, Eq, Clone)]
+///
+/// This is the relaxed instance of protogalaxy: the relaxation is carried by `betas` & the
+/// scalar error `e`, while `ins` stays a plain [`PlonkInstance`]. It's not interchangeable with
+/// [`crate::nifs::sangria::accumulator::RelaxedPlonkInstance`], which relaxes the relation of
+/// sangria with the commitment to the error vector & the scalar `u`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct AccumulatorInstance<C: CurveAffine> {
     /// `φ`: Represents the combined state of all instances. It is a summary that captures the
     /// essential data and relationships from the instances being merged.
@@ -60,14 +185,38 @@ pub struct AccumulatorInstance<C: CurveAffine> {
 
     /// `β`: A random value used in the folding process. It helps ensure the unique
     /// and secure combination of instances, preventing manipulation.
+    #[serde(with = "serde_repr::fields")]
     pub(crate) betas: Box<[C::ScalarExt]>,
 
     /// `e`: an accumulated value that encapsulates the result of the folding operation. it serves
     /// as a concise representation of the correctness and properties of the folded instances.
+    #[serde(with = "serde_repr::field")]
     pub(crate) e: C::ScalarExt,
 }
 
 impl<C: CurveAffine> AccumulatorInstance<C> {
+    /// Checks that `self.betas` were updated from `prev.betas` with challenges `alpha` & `delta`
+    ///
+    /// ```math
+    /// \beta^*_i = \beta_i + \alpha \cdot \delta^{2^i}
+    /// ```
+    ///
+    /// Useful as a standalone check of deserialized accumulators
+    pub fn check_betas_update(
+        &self,
+        prev: &AccumulatorInstance<C>,
+        alpha: C::ScalarExt,
+        delta: C::ScalarExt,
+    ) -> bool {
+        PolyChallenges {
+            betas: prev.betas.clone(),
+            alpha,
+            delta,
+        }
+        .iter_beta_stroke()
+        .eq(self.betas.iter().copied())
+    }
+
     pub fn into_acc(self, w: PlonkWitness<C::Scalar>) -> Accumulator<C> {
         let Self { ins, betas, e } = self;
         Accumulator {
@@ -100,3 +249,173 @@ impl<C: CurveAffine, RO: ROTrait<C::Base>> AbsorbInRO<C::Base, RO> for Accumulat
         );
     }
 }
+
+/// [`AccumulatorInstance`] with the structure it was folded for, to check it as
+/// [`SpecialSoundnessVerifier`]
+///
+/// In addition to the sps check of the inner [`PlonkInstance`]:
+/// - there must be at least as many betas as `pow_i` of the structure needs, extra betas are not
+///   used
+/// - `e` must be representable in the base field, since it's absorbed as a base field element
+pub struct AccumulatorInstanceVerifier<'a, C: CurveAffine>(
+    pub &'a AccumulatorInstance<C>,
+    pub &'a PlonkStructure<C::ScalarExt>,
+);
+
+impl<C: CurveAffine> AccumulatorInstanceVerifier<'_, C> {
+    fn check_structure(&self) -> Result<(), sps::Error> {
+        let Self(acc, S) = self;
+
+        let expected = get_count_of_valuation(S)
+            .map_or(0, |count| count.get().next_power_of_two().ilog2() as usize);
+        if acc.betas.len() < expected {
+            return Err(sps::Error::LackOfBetas {
+                betas_count: acc.betas.len(),
+                expected,
+            });
+        }
+
+        if C::scalar_to_base(&acc.e).is_none() {
+            return Err(sps::Error::NotInBaseField {
+                annotation: "AccumulatorInstance::e",
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: CurveAffine, RO: ROTrait<C::Base>> SpecialSoundnessVerifier<C, RO>
+    for AccumulatorInstanceVerifier<'_, C>
+{
+    fn sps_derive_challenges(&self, ro_nark: &mut RO) -> Vec<C::ScalarExt> {
+        self.0.ins.sps_derive_challenges(ro_nark)
+    }
+
+    fn sps_verify(&self, ro_nark: &mut RO) -> Result<(), sps::Error> {
+        self.check_structure()?;
+        self.0.ins.sps_verify(ro_nark)
+    }
+
+    fn sps_verify_ct(&self, ro_nark: &mut RO) -> Result<(), sps::Error> {
+        self.check_structure()?;
+        self.0.ins.sps_verify_ct(ro_nark)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::halo2curves::bn256::G1Affine as Affine;
+
+    type Scalar = <Affine as CurveAffine>::ScalarExt;
+
+    #[test]
+    fn check_betas_update() {
+        let mut rnd = rand::thread_rng();
+
+        let prev = AccumulatorInstance::<Affine> {
+            ins: PlonkInstance {
+                W_commitments: vec![],
+                instances: vec![],
+                challenges: vec![],
+            },
+            betas: (0..4).map(|_| Scalar::random(&mut rnd)).collect(),
+            e: Scalar::ZERO,
+        };
+        let alpha = Scalar::random(&mut rnd);
+        let delta = Scalar::random(&mut rnd);
+
+        let mut next = AccumulatorInstance {
+            betas: prev
+                .betas
+                .iter()
+                .zip(iter::successors(Some(delta), |d| Some(d.square())))
+                .map(|(beta, delta_power)| *beta + alpha * delta_power)
+                .collect(),
+            ..prev.clone()
+        };
+        assert!(next.check_betas_update(&prev, alpha, delta));
+        assert!(!next.check_betas_update(&prev, delta, alpha));
+
+        next.betas[2] += Scalar::ONE;
+        assert!(!next.check_betas_update(&prev, alpha, delta));
+    }
+
+    #[test]
+    fn verifier_checks_betas_count() {
+        use crate::{
+            polynomial::Expression,
+            poseidon::{PoseidonHash, Spec},
+        };
+
+        let ro = || PoseidonHash::<<Affine as CurveAffine>::Base, 3, 2>::new(Spec::new(4, 3));
+
+        // 4 rows & 3 gates are padded to 16 evaluations, so `pow_i` needs 4 betas
+        let S = PlonkStructure::<Scalar> {
+            k: 2,
+            gates: vec![Expression::Constant(Scalar::ONE); 3],
+            ..Default::default()
+        };
+        let acc = |betas_count: usize| AccumulatorInstance::<Affine> {
+            ins: PlonkInstance {
+                W_commitments: vec![],
+                instances: vec![vec![Scalar::from(1)]],
+                challenges: vec![],
+            },
+            betas: vec![Scalar::ONE; betas_count].into_boxed_slice(),
+            e: Scalar::ZERO,
+        };
+
+        for betas_count in [4, 12] {
+            assert_eq!(
+                AccumulatorInstanceVerifier(&acc(betas_count), &S).sps_verify(&mut ro()),
+                Ok(())
+            );
+        }
+
+        assert_eq!(
+            AccumulatorInstanceVerifier(&acc(3), &S).sps_verify(&mut ro()),
+            Err(sps::Error::LackOfBetas {
+                betas_count: 3,
+                expected: 4
+            })
+        );
+        assert_eq!(
+            AccumulatorInstanceVerifier(&acc(3), &S).sps_verify_ct(&mut ro()),
+            Err(sps::Error::LackOfBetas {
+                betas_count: 3,
+                expected: 4
+            })
+        );
+    }
+
+    #[test]
+    fn split_round_trip() {
+        use crate::group::{prime::PrimeCurveAffine, Curve};
+
+        let mut rnd = rand::thread_rng();
+        let mut field = || Scalar::random(&mut rnd);
+
+        let acc = Accumulator::<Affine> {
+            trace: PlonkTrace {
+                u: PlonkInstance {
+                    W_commitments: vec![(Affine::generator() * field()).to_affine()],
+                    instances: vec![vec![field(), field()]],
+                    challenges: vec![field()],
+                },
+                w: PlonkWitness {
+                    W: vec![vec![field(), field(), field()]],
+                },
+            },
+            betas: Box::new([field(), field()]),
+            e: field(),
+        };
+
+        let (instance, witness) = acc.clone().split();
+        assert_eq!(instance, AccumulatorInstance::from(acc.clone()));
+        assert_eq!(witness, acc.trace.w);
+
+        assert_eq!(Accumulator::from_parts(instance, witness), acc);
+    }
+}
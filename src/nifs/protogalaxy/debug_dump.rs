@@ -0,0 +1,203 @@
+//! Human-readable JSON dump of the [`ProtoGalaxy::verify`] inputs
+//!
+//! Used as a portable artifact for folding bug reports: the verifier params, accumulator
+//! instance, incoming instances & proof are stored with the encoding of [`serde_repr`], i.e.
+//! field elements & points are big-endian hex strings, the same as in serialized proofs.
+//! Witnesses are deliberately excluded, so the dump is small & doesn't leak private inputs.
+
+use std::{fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::{AccumulatorInstance, Proof, ProtoGalaxy, VerifierParam};
+use crate::{halo2curves::CurveAffine, plonk::PlonkInstance, poseidon::ROTrait, util::serde_repr};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Expected {expected} incoming instances, but dump has {actual}")]
+    WrongIncomingCount { expected: usize, actual: usize },
+}
+
+/// Inputs of [`ProtoGalaxy::verify`] for `L` incoming instances
+pub struct FoldInputs<C: CurveAffine, const L: usize> {
+    pub vp: VerifierParam<C>,
+    pub accumulator: AccumulatorInstance<C>,
+    pub incoming: [PlonkInstance<C>; L],
+    pub proof: Proof<C::ScalarExt>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct FoldDump<C: CurveAffine> {
+    #[serde(with = "serde_repr::point")]
+    pp_digest: C,
+    accumulator: AccumulatorInstance<C>,
+    incoming: Vec<PlonkInstance<C>>,
+    proof: Proof<C::ScalarExt>,
+}
+
+impl<C: CurveAffine> FoldDump<C> {
+    fn decode<const L: usize>(self) -> Result<FoldInputs<C, L>, Error> {
+        let incoming =
+            self.incoming
+                .try_into()
+                .map_err(|incoming: Vec<_>| Error::WrongIncomingCount {
+                    expected: L,
+                    actual: incoming.len(),
+                })?;
+
+        Ok(FoldInputs {
+            vp: VerifierParam {
+                pp_digest: self.pp_digest,
+            },
+            accumulator: self.accumulator,
+            incoming,
+            proof: self.proof,
+        })
+    }
+}
+
+/// Writes inputs of [`ProtoGalaxy::verify`] to `path` as pretty JSON
+pub fn dump_fold_inputs<C: CurveAffine, const L: usize>(
+    path: &Path,
+    vp: &VerifierParam<C>,
+    accumulator: &AccumulatorInstance<C>,
+    incoming: &[PlonkInstance<C>; L],
+    proof: &Proof<C::ScalarExt>,
+) -> Result<(), Error> {
+    let dump = FoldDump {
+        pp_digest: vp.pp_digest,
+        accumulator: accumulator.clone(),
+        incoming: incoming.to_vec(),
+        proof: proof.clone(),
+    };
+    fs::write(path, serde_json::to_string_pretty(&dump)?)?;
+    Ok(())
+}
+
+/// Reads inputs of [`ProtoGalaxy::verify`] written by [`dump_fold_inputs`]
+pub fn load_fold_inputs<C: CurveAffine, const L: usize>(
+    path: &Path,
+) -> Result<FoldInputs<C, L>, Error> {
+    serde_json::from_slice::<FoldDump<C>>(&fs::read(path)?)?.decode()
+}
+
+/// Re-runs off-circuit [`ProtoGalaxy::verify`] on the loaded dump
+pub fn verify_dump<C: CurveAffine, const L: usize>(
+    inputs: &FoldInputs<C, L>,
+    ro_nark: &mut impl ROTrait<C::Base>,
+    ro_acc: &mut impl ROTrait<C::Base>,
+) -> Result<AccumulatorInstance<C>, crate::Error> {
+    ProtoGalaxy::<C, L>::verify(
+        &inputs.vp,
+        ro_nark,
+        ro_acc,
+        &inputs.accumulator,
+        &inputs.incoming,
+        &inputs.proof,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::array;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::{
+        ff::Field,
+        group::{prime::PrimeCurveAffine, Curve},
+        halo2curves::bn256::G1Affine as Affine,
+        polynomial::univariate::UnivariatePoly,
+    };
+
+    type Scalar = <Affine as CurveAffine>::ScalarExt;
+
+    const L: usize = 3;
+
+    #[test]
+    fn round_trip() {
+        let field = || Scalar::random(rand::thread_rng());
+        let instance = || PlonkInstance::<Affine> {
+            W_commitments: vec![
+                (Affine::generator() * field()).to_affine(),
+                Affine::identity(),
+            ],
+            instances: vec![vec![field(), field()], vec![field()]],
+            challenges: vec![field()],
+        };
+
+        let vp = VerifierParam {
+            pp_digest: instance().W_commitments[0],
+        };
+        let accumulator = AccumulatorInstance {
+            ins: instance(),
+            betas: Box::new([field(), field()]),
+            e: field(),
+        };
+        let incoming: [_; L] = array::from_fn(|_| instance());
+        let proof = Proof {
+            poly_F: UnivariatePoly(Box::new([field(), field()])),
+            poly_K: UnivariatePoly(Box::new([field()])),
+        };
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fold.json");
+        dump_fold_inputs(&path, &vp, &accumulator, &incoming, &proof).unwrap();
+
+        let loaded = load_fold_inputs::<Affine, L>(&path).unwrap();
+        assert_eq!(loaded.vp.pp_digest, vp.pp_digest);
+        assert_eq!(loaded.accumulator, accumulator);
+        assert_eq!(loaded.incoming, incoming);
+        assert_eq!(loaded.proof, proof);
+
+        assert!(matches!(
+            load_fold_inputs::<Affine, 1>(&path),
+            Err(Error::WrongIncomingCount {
+                expected: 1,
+                actual: L
+            })
+        ));
+    }
+
+    #[test]
+    fn big_endian_hex() {
+        let instance = PlonkInstance::<Affine> {
+            W_commitments: vec![Affine::identity()],
+            instances: vec![vec![Scalar::ONE]],
+            challenges: vec![],
+        };
+        let accumulator = AccumulatorInstance {
+            ins: instance.clone(),
+            betas: Box::new([]),
+            e: Scalar::ONE,
+        };
+        let proof = Proof {
+            poly_F: UnivariatePoly(Box::new([])),
+            poly_K: UnivariatePoly(Box::new([])),
+        };
+        let vp = VerifierParam {
+            pp_digest: Affine::identity(),
+        };
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fold.json");
+        dump_fold_inputs(&path, &vp, &accumulator, &[instance], &proof).unwrap();
+
+        let one = format!("\"{}01\"", "00".repeat(31));
+        let json = fs::read_to_string(&path).unwrap();
+        // Instance column of the accumulator & incoming, `e`
+        assert_eq!(json.matches(&one).count(), 3);
+
+        fs::write(&path, json.replacen(&one, "\"zz\"", 1)).unwrap();
+        assert!(matches!(
+            load_fold_inputs::<Affine, 1>(&path),
+            Err(Error::Json(_))
+        ));
+    }
+}
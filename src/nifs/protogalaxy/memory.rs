@@ -0,0 +1,87 @@
+//! Memory accounting hooks of the folding hot paths
+//!
+//! [`MemoryObserver`] receives [`MemorySample`]s at the boundaries of [`Phase`]s & sizes of the
+//! big allocations ([`Buffer`]) made inside them. Hooks are compiled only with the
+//! `memory-observer` feature, without it [`MemoryAccounting`] can't be attached to the prover.
+
+use std::sync::Arc;
+
+/// Memory usage at the moment of sampling
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemorySample {
+    /// Bytes in use, e.g. RSS
+    pub current: usize,
+    /// Peak of bytes in use
+    pub peak: usize,
+}
+
+/// Source of [`MemorySample`], e.g. reader of `/proc/self/status` or counting allocator
+pub trait MemorySampler: Send + Sync {
+    fn sample(&self) -> MemorySample;
+}
+
+/// Phase of [`super::ProtoGalaxy::prove`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    ComputeF,
+    ComputeG,
+    ComputeK,
+}
+
+/// Big allocation made inside of [`Phase`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Buffer {
+    /// Powers of challenges for each point of `F(X)` domain
+    ChallengesPowers,
+    /// Witnesses folded for each point of `G(X)` domain
+    FoldedWitness,
+    /// Points of the nodes of the evaluation tree alive at once
+    NodePoints,
+    /// Witness columns of the folded trace
+    WitnessColumns,
+}
+
+pub trait MemoryObserver: Send + Sync {
+    fn on_phase_start(&self, _phase: Phase, _sample: MemorySample) {}
+    fn on_phase_end(&self, _phase: Phase, _sample: MemorySample) {}
+    fn on_buffer(&self, _phase: Phase, _buffer: Buffer, _bytes: usize) {}
+}
+
+/// [`MemoryObserver`] with the [`MemorySampler`] used at phase boundaries
+#[derive(Clone)]
+pub struct MemoryAccounting {
+    observer: Arc<dyn MemoryObserver>,
+    sampler: Arc<dyn MemorySampler>,
+}
+
+impl MemoryAccounting {
+    pub fn new(observer: Arc<dyn MemoryObserver>, sampler: Arc<dyn MemorySampler>) -> Self {
+        Self { observer, sampler }
+    }
+
+    pub(crate) fn phase(&self, phase: Phase) -> PhaseGuard<'_> {
+        self.observer.on_phase_start(phase, self.sampler.sample());
+        PhaseGuard {
+            accounting: self,
+            phase,
+        }
+    }
+
+    pub(crate) fn buffer(&self, phase: Phase, buffer: Buffer, bytes: usize) {
+        self.observer.on_buffer(phase, buffer, bytes);
+    }
+}
+
+/// Calls [`MemoryObserver::on_phase_end`] on drop
+pub(crate) struct PhaseGuard<'a> {
+    accounting: &'a MemoryAccounting,
+    phase: Phase,
+}
+
+impl Drop for PhaseGuard<'_> {
+    fn drop(&mut self) {
+        self.accounting
+            .observer
+            .on_phase_end(self.phase, self.accounting.sampler.sample());
+    }
+}
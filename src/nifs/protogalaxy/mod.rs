@@ -1,25 +1,39 @@
-use std::{iter, marker::PhantomData};
+use std::{collections::BTreeSet, io, iter, marker::PhantomData, num::NonZeroUsize, sync::Arc};
 
 use itertools::Itertools;
-use tracing::{debug, instrument, warn};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument, trace, warn};
 
 use crate::{
     commitment::CommitmentKey,
     constants::MAX_BITS,
+    digest::{DefaultHasher, DigestToCurve},
     ff::PrimeField,
+    fft::FftBackend,
     halo2_proofs::arithmetic::{self, CurveAffine, Field},
+    metrics,
     nifs::protogalaxy::poly::PolyContext,
     plonk::{self, PlonkInstance, PlonkStructure, PlonkTrace, PlonkWitness},
-    polynomial::{lagrange, sparse, univariate::UnivariatePoly},
+    polynomial::{lagrange, sparse, univariate::UnivariatePoly, ColumnIndex},
     poseidon::{AbsorbInRO, ROTrait},
-    sps::{self, SpecialSoundnessVerifier},
-    util::ScalarToBase,
+    sps,
+    util::{
+        parallel::{self, *},
+        ScalarToBase, TryTreeReduce,
+    },
 };
 
 mod accumulator;
+pub mod debug_dump;
+pub mod memory;
 pub(crate) mod poly;
+pub mod progress;
+mod transcript;
 
-pub use accumulator::{Accumulator, AccumulatorArgs, AccumulatorInstance};
+pub use accumulator::{
+    Accumulator, AccumulatorArgs, AccumulatorInstance, AccumulatorInstanceVerifier, GateRow,
+};
+pub use transcript::{transcript_elements, TranscriptElement};
 
 /// ProtoGalaxy: Non-Interactive Folding Scheme that implements the main protocol defined in the
 /// paper [protogalaxy.pdf](https://eprint.iacr.org/2023/1106).
@@ -30,12 +44,15 @@ pub use accumulator::{Accumulator, AccumulatorArgs, AccumulatorInstance};
 ///                  Circuit will be proved in `C::Scalar` field
 ///
 /// - `L`: 'Length' - constant representing the number of instances to
-///                   fold in a single `prove`. `L-1` be power of two
+///                   fold in a single `prove`. If `L + 1` is not a power of two,
+///                   the lagrange domain is padded with zero traces, so the gates
+///                   must vanish on the zero witness
 #[derive(Clone, Debug)]
 pub struct ProtoGalaxy<C: CurveAffine, const L: usize> {
     _marker: PhantomData<C>,
 }
 
+#[derive(Clone, Copy)]
 pub(crate) struct Challenges<F: PrimeField> {
     pub delta: F,
     pub alpha: F,
@@ -78,7 +95,7 @@ impl<F: PrimeField> Challenges<F> {
                     proof
                         .poly_F
                         .iter()
-                        .inspect(|coeff| debug!("coeff {coeff:?}"))
+                        .inspect(|coeff| trace!("coeff {coeff:?}"))
                         .map(|coeff| C::scalar_to_base(coeff).unwrap()),
                 )
                 .squeeze::<C>(MAX_BITS),
@@ -87,7 +104,7 @@ impl<F: PrimeField> Challenges<F> {
                     proof
                         .poly_K
                         .iter()
-                        .inspect(|coeff| debug!("coeff {coeff:?}"))
+                        .inspect(|coeff| trace!("coeff {coeff:?}"))
                         .map(|coeff| C::scalar_to_base(coeff).unwrap()),
                 )
                 .squeeze::<C>(MAX_BITS),
@@ -103,7 +120,8 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
         count_of_rows * count_of_gates
     }
 
-    pub(crate) fn new_accumulator(
+    /// Accumulator of zero trace with `betas` derived from `ro_acc`, the start of folding
+    pub fn new_accumulator(
         args: AccumulatorArgs,
         params: &ProverParam<C>,
         ro_acc: &mut impl ROTrait<C::Base>,
@@ -121,6 +139,7 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
         accumulator
     }
 
+    #[instrument(skip_all)]
     fn fold_witness<'i>(
         acc: PlonkWitness<C::Scalar>,
         incoming: impl Iterator<Item = &'i PlonkWitness<C::Scalar>>,
@@ -157,6 +176,7 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
             })
     }
 
+    #[instrument(skip_all)]
     pub(crate) fn fold_instance<'i>(
         acc: PlonkInstance<C>,
         incoming: impl Iterator<Item = &'i PlonkInstance<C>>,
@@ -218,20 +238,14 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
             })
     }
 
+    /// Same as [`sps::sps_verify_batch`], the transcript of each instance continues the previous
+    /// one, so only the first invalid instance is reported
+    #[instrument(skip_all)]
     pub fn verify_sps<'l>(
         incoming: impl Iterator<Item = &'l PlonkInstance<C>>,
         ro_nark: &mut impl ROTrait<C::Base>,
     ) -> Result<(), Error> {
-        let errors = incoming
-            .enumerate()
-            .filter_map(|(i, plonk_instance)| Some((i, plonk_instance.sps_verify(ro_nark).err()?)))
-            .collect::<Box<[_]>>();
-
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(Error::VerifySps(errors))
-        }
+        sps::sps_verify_batch(incoming, ro_nark).map_err(|err| Error::VerifySps(Box::new([err])))
     }
 }
 
@@ -239,6 +253,51 @@ pub struct ProverParam<C: CurveAffine> {
     pub(crate) S: PlonkStructure<C::ScalarExt>,
     /// Digest of public parameter of IVC circuit
     pub(crate) pp_digest: C,
+    /// Backend of polynomial FFTs in [`ProtoGalaxy::prove`], [`crate::fft`] is used if `None`
+    pub(crate) fft: Option<Arc<dyn FftBackend<C::ScalarExt>>>,
+    /// Cap of threads of [`ProtoGalaxy::prove`], all threads of the current pool are used if `None`
+    pub(crate) parallelism: Option<NonZeroUsize>,
+    #[cfg(feature = "memory-observer")]
+    pub(crate) memory: Option<memory::MemoryAccounting>,
+    /// Progress callback of [`ProtoGalaxy::prove`], nothing is reported if `None`
+    pub(crate) progress: Option<Arc<dyn Fn(progress::ProgressEvent) + Send + Sync>>,
+}
+
+impl<C: CurveAffine> ProverParam<C> {
+    /// Use `backend` for FFTs of [`ProtoGalaxy::prove`]
+    pub fn with_fft_backend(mut self, backend: Arc<dyn FftBackend<C::ScalarExt>>) -> Self {
+        self.fft = Some(backend);
+        self
+    }
+
+    /// Cap the count of threads of [`ProtoGalaxy::prove`]
+    ///
+    /// The whole prove runs in a dedicated pool of `n` threads & `F(X)`, `G(X)` evaluation is
+    /// split into at most `n` tasks. `1` forces single-threaded prove, e.g. for debugging; `0`
+    /// removes the cap. The result of [`ProtoGalaxy::prove`] doesn't depend on it
+    pub fn with_parallelism(mut self, n: usize) -> Self {
+        self.parallelism = NonZeroUsize::new(n);
+        self
+    }
+
+    /// Report progress of `F(X)`, `G(X)` & `K(X)` evaluation in [`ProtoGalaxy::prove`] into
+    /// `progress`, see [`progress::ProgressEvent`]
+    pub fn with_progress(
+        mut self,
+        progress: Arc<dyn Fn(progress::ProgressEvent) + Send + Sync>,
+    ) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+}
+
+#[cfg(feature = "memory-observer")]
+impl<C: CurveAffine> ProverParam<C> {
+    /// Report memory usage of [`ProtoGalaxy::prove`] phases into `memory`
+    pub fn with_memory_accounting(mut self, memory: memory::MemoryAccounting) -> Self {
+        self.memory = Some(memory);
+        self
+    }
 }
 
 impl<C: CurveAffine, RO: ROTrait<C::Base>> AbsorbInRO<C::Base, RO> for ProverParam<C> {
@@ -258,28 +317,107 @@ impl<C: CurveAffine, RO: ROTrait<C::Base>> AbsorbInRO<C::Base, RO> for VerifierP
     }
 }
 
-#[derive(Clone)]
+#[derive(Serialize)]
+#[serde(bound(serialize = "C: Serialize, C::ScalarExt: Serialize"))]
+struct PpDigestInput<'l, C: CurveAffine> {
+    S: &'l PlonkStructure<C::ScalarExt>,
+    ck: &'l CommitmentKey<C>,
+}
+
+/// Digest of `S` & `ck`, to be used as `pp_digest` of [`ProverParam`] & [`VerifierParam`]
+///
+/// Both are serialized & hashed with [`DefaultHasher`] like [`crate::ivc::PublicParams`], so
+/// prover & verifier derive the same point from the same structure & key.
+pub fn compute_pp_digest<C>(
+    S: &PlonkStructure<C::ScalarExt>,
+    ck: &CommitmentKey<C>,
+) -> Result<C, io::Error>
+where
+    C: CurveAffine + Serialize,
+    C::ScalarExt: Serialize,
+{
+    DefaultHasher::digest_to_curve(&PpDigestInput { S, ck })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct Proof<F: PrimeField> {
     pub poly_F: UnivariatePoly<F>,
     pub poly_K: UnivariatePoly<F>,
 }
 
+impl<F: PrimeField> Proof<F> {
+    /// Size of the serialized proof in bytes
+    ///
+    /// Each polynomial is a sequence of coefficients, see [`Accumulator::byte_size`] for
+    /// the encoding:
+    /// ```text
+    /// (8 + |poly_F| * |F|) + (8 + |poly_K| * |F|)
+    /// ```
+    pub fn byte_size(&self) -> usize {
+        seq_byte_size(self.poly_F.len(), field_byte_size::<F>())
+            + seq_byte_size(self.poly_K.len(), field_byte_size::<F>())
+    }
+}
+
+/// Length prefix of the serialized sequence, `u64` like in `bincode`
+const SEQ_LEN_BYTES: usize = std::mem::size_of::<u64>();
+
+/// Size of [`PrimeField::Repr`]
+fn field_byte_size<F: PrimeField>() -> usize {
+    F::Repr::default().as_ref().len()
+}
+
+/// Size of the compressed point, i.e. [`crate::group::GroupEncoding::Repr`]
+fn point_byte_size<C: CurveAffine>() -> usize {
+    C::Repr::default().as_ref().len()
+}
+
+fn seq_byte_size(len: usize, item_byte_size: usize) -> usize {
+    SEQ_LEN_BYTES + len * item_byte_size
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
     Sps(#[from] sps::Error),
     #[error(transparent)]
     Poly(#[from] poly::Error),
+    #[error(transparent)]
+    PolyContext(#[from] poly::PolyContextError),
+    #[error(transparent)]
+    Chip(#[from] crate::ivc::protogalaxy::VerifyChipError),
     #[error("Error while verify plonk instance with sps: {0:?}")]
     VerifySps(Box<[(usize, sps::Error)]>),
+    #[error("Incoming instance {index} has another shape than the accumulator")]
+    IncomingShape { index: usize },
+    #[error("Degree of F(X) is {degree}, but at most {max} for {max} betas")]
+    PolyFDegree { degree: usize, max: usize },
+    #[error("Fold {index} has {actual} incoming instances, but {expected} expected")]
+    FoldIncomingCount {
+        index: usize,
+        expected: usize,
+        actual: usize,
+    },
 }
 
 impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
-    fn setup_params(
+    pub fn setup_params(
         pp_digest: C,
         S: PlonkStructure<C::ScalarExt>,
     ) -> Result<(ProverParam<C>, VerifierParam<C>), Error> {
-        Ok((ProverParam { S, pp_digest }, VerifierParam { pp_digest }))
+        Ok((
+            ProverParam {
+                S,
+                pp_digest,
+                fft: None,
+                parallelism: None,
+                #[cfg(feature = "memory-observer")]
+                memory: None,
+                progress: None,
+            },
+            VerifierParam { pp_digest },
+        ))
     }
 
     pub fn generate_plonk_trace(
@@ -322,28 +460,63 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
     ///
     /// 7. **Fold the Trace:**
     ///     - [`ProtoGalaxy::fold_witness`] & [`ProtoGalaxy::fold_instance`]
-    fn prove(
+    #[instrument(skip_all, fields(L = L, k = pp.S.k))]
+    pub fn prove(
+        ck: &CommitmentKey<C>,
+        pp: &ProverParam<C>,
+        ro_acc: &mut (impl ROTrait<C::Base> + Send),
+        accumulator: Accumulator<C>,
+        incoming: &[PlonkTrace<C>; L],
+    ) -> Result<(Accumulator<C>, Proof<C::ScalarExt>), crate::Error> {
+        let _timer = metrics::Timer::start(metrics::FOLD_DURATION_SECONDS);
+        metrics::increment_counter(metrics::FOLD_TOTAL, 1);
+
+        parallel::install(pp.parallelism, || {
+            let (proof, challenges) = Self::prove_fold(ck, pp, ro_acc, &accumulator, incoming)?;
+
+            Ok((
+                Self::apply_fold(accumulator, incoming, &proof, &challenges),
+                proof,
+            ))
+        })
+    }
+
+    /// Steps 1-6 of [`ProtoGalaxy::prove`]: `accumulator` is left untouched, only the proof &
+    /// challenges of the fold are returned
+    fn prove_fold(
         _ck: &CommitmentKey<C>,
         pp: &ProverParam<C>,
         ro_acc: &mut impl ROTrait<C::Base>,
-        accumulator: Accumulator<C>,
+        accumulator: &Accumulator<C>,
         incoming: &[PlonkTrace<C>; L],
-    ) -> Result<(Accumulator<C>, Proof<C::ScalarExt>), Error> {
-        let ctx = PolyContext::new(&pp.S, incoming);
+    ) -> Result<(Proof<C::ScalarExt>, Challenges<C::ScalarExt>), crate::Error> {
+        let mut ctx = PolyContext::try_new(&pp.S, incoming)?
+            .with_fft_backend(pp.fft.as_deref())
+            .with_progress(
+                pp.progress
+                    .as_deref()
+                    .map(|callback| callback as &(dyn Fn(progress::ProgressEvent) + Sync)),
+            );
+        ctx.set_parallelism(pp.parallelism.map_or(0, NonZeroUsize::get));
+        #[cfg(feature = "memory-observer")]
+        let ctx = ctx.with_memory_accounting(pp.memory.as_ref());
 
         let delta = Challenges::generate_one::<_, C>(
             pp,
             ro_acc,
-            &accumulator,
+            accumulator,
             incoming.iter().map(|t| &t.u),
         );
 
-        let poly_F = poly::compute_F::<C::ScalarExt>(
-            &ctx,
-            accumulator.betas.iter().copied(),
-            delta,
-            &accumulator.trace,
-        )?;
+        let poly_F = {
+            let _timer = metrics::Timer::phase("compute_F");
+            poly::compute_F::<C::ScalarExt>(
+                &ctx,
+                accumulator.betas.iter().copied(),
+                delta,
+                &accumulator.trace,
+            )?
+        };
 
         let alpha = ro_acc
             .absorb_field_iter(poly_F.iter().map(|v| C::scalar_to_base(v).unwrap()))
@@ -357,13 +530,16 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
         .iter_beta_stroke()
         .collect::<Box<[_]>>();
 
-        let poly_K = poly::compute_K::<C::ScalarExt>(
-            &ctx,
-            poly_F.eval(alpha),
-            betas_stroke.iter().copied(),
-            &accumulator.trace,
-            incoming,
-        )?;
+        let poly_K = {
+            let _timer = metrics::Timer::phase("compute_K");
+            poly::compute_K::<C::ScalarExt>(
+                &ctx,
+                poly_F.eval(alpha),
+                betas_stroke.iter().copied(),
+                &accumulator.trace,
+                incoming,
+            )?
+        };
 
         let gamma = ro_acc
             .absorb_field_iter(poly_K.iter().map(|v| C::scalar_to_base(v).unwrap()))
@@ -377,36 +553,77 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
         "
         );
 
+        Ok((
+            Proof { poly_F, poly_K },
+            Challenges {
+                delta,
+                alpha,
+                gamma,
+            },
+        ))
+    }
+
+    /// Step 7 of [`ProtoGalaxy::prove`]: folds `incoming` into `accumulator` with the `proof` &
+    /// `challenges` of [`ProtoGalaxy::prove_fold`]
+    fn apply_fold(
+        accumulator: Accumulator<C>,
+        incoming: &[PlonkTrace<C>; L],
+        proof: &Proof<C::ScalarExt>,
+        challenges: &Challenges<C::ScalarExt>,
+    ) -> Accumulator<C> {
+        let Challenges {
+            delta,
+            alpha,
+            gamma,
+        } = *challenges;
+        let lagrange_domain = PolyContext::<C::ScalarExt>::get_lagrange_domain::<L>();
+
         let polys_L_in_gamma =
-            lagrange::iter_eval_lagrange_poly_for_cyclic_group(gamma, ctx.lagrange_domain())
+            lagrange::iter_eval_lagrange_poly_for_cyclic_group(gamma, lagrange_domain)
                 .take(L + 1)
                 .collect::<Box<[_]>>();
 
         let Accumulator {
             trace: PlonkTrace { u, w },
-            betas: _,
+            betas,
             e: _,
         } = accumulator;
 
-        Ok((
-            Accumulator {
-                e: calculate_e(&poly_F, &poly_K, gamma, alpha, ctx.lagrange_domain()),
-                betas: betas_stroke,
-                trace: PlonkTrace {
-                    u: Self::fold_instance(
-                        u,
-                        incoming.iter().map(|tr| &tr.u),
-                        polys_L_in_gamma.iter().copied(),
-                    ),
-                    w: Self::fold_witness(
-                        w,
-                        incoming.iter().map(|tr| &tr.w),
-                        polys_L_in_gamma.iter().copied(),
-                    ),
-                },
-            },
-            Proof { poly_F, poly_K },
-        ))
+        let betas_stroke = poly::PolyChallenges {
+            betas,
+            delta,
+            alpha,
+        }
+        .iter_beta_stroke()
+        .collect::<Box<[_]>>();
+
+        let trace = {
+            let _timer = metrics::Timer::phase("fold_trace");
+            PlonkTrace {
+                u: Self::fold_instance(
+                    u,
+                    incoming.iter().map(|tr| &tr.u),
+                    polys_L_in_gamma.iter().copied(),
+                ),
+                w: Self::fold_witness(
+                    w,
+                    incoming.iter().map(|tr| &tr.w),
+                    polys_L_in_gamma.iter().copied(),
+                ),
+            }
+        };
+
+        metrics::set_gauge(
+            metrics::ACCUMULATOR_WITNESS_BYTES,
+            (trace.w.W.iter().map(Vec::len).sum::<usize>() * std::mem::size_of::<C::ScalarExt>())
+                as f64,
+        );
+
+        Accumulator {
+            e: calculate_e(&proof.poly_F, &proof.poly_K, gamma, alpha, lagrange_domain),
+            betas: betas_stroke,
+            trace,
+        }
     }
 
     /// Verifies a statement using the ProtoGalaxy protocol.
@@ -435,18 +652,57 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
     ///
     /// 6. **Fold the Instance:**
     ///     - [`ProtoGalaxy::fold_instance`]
-    fn verify(
+    ///
+    /// # Errors
+    ///
+    /// Transcript mismatch & arithmetic failure are distinguished, all of them are wrapped into
+    /// [`crate::Error::ProtoGalaxy`]:
+    /// - [`Error::IncomingShape`]: some of `incoming` can't be folded with `accumulator`
+    /// - [`Error::VerifySps`]: challenges of `incoming` don't match their transcript in `ro_nark`
+    /// - [`Error::PolyFDegree`]: `proof.poly_F` can't be the `F(X)` of `accumulator.betas`
+    #[instrument(skip_all, fields(L = L))]
+    pub fn verify(
         vp: &VerifierParam<C>,
         ro_nark: &mut impl ROTrait<C::Base>,
         ro_acc: &mut impl ROTrait<C::Base>,
         accumulator: &AccumulatorInstance<C>,
         incoming: &[PlonkInstance<C>; L],
         proof: &Proof<C::ScalarExt>,
-    ) -> Result<AccumulatorInstance<C>, Error> {
+    ) -> Result<AccumulatorInstance<C>, crate::Error> {
+        let _timer = metrics::Timer::start(metrics::VERIFY_DURATION_SECONDS);
+        metrics::increment_counter(metrics::VERIFY_TOTAL, 1);
+
         let lagrange_domain = PolyContext::<C::Base>::get_lagrange_domain::<L>();
 
+        let shape = |instance: &PlonkInstance<C>| {
+            (
+                instance.W_commitments.len(),
+                instance
+                    .instances
+                    .iter()
+                    .map(Vec::len)
+                    .collect::<Box<[_]>>(),
+                instance.challenges.len(),
+            )
+        };
+        let accumulator_shape = shape(&accumulator.ins);
+        if let Some(index) = incoming
+            .iter()
+            .position(|instance| shape(instance) != accumulator_shape)
+        {
+            return Err(Error::IncomingShape { index }.into());
+        }
+
         Self::verify_sps(incoming.iter(), ro_nark)?;
 
+        // `F(X) = sum_i pow_i(beta + X * delta) * f_i` & `pow_i` is a product of at most one
+        // `beta_j + X * delta^{2^j}` for each beta
+        let max = accumulator.betas.len();
+        let degree = proof.poly_F.degree();
+        if degree > max {
+            return Err(Error::PolyFDegree { degree, max }.into());
+        }
+
         let Challenges {
             delta,
             alpha,
@@ -478,14 +734,74 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
             e: calculate_e(&proof.poly_F, &proof.poly_K, gamma, alpha, lagrange_domain),
         })
     }
+
+    /// [`ProtoGalaxy::verify`] of independent folds, each with its own `accumulator`, `incoming`
+    /// instances & `proof`
+    ///
+    /// Challenges of each fold are bound to its own transcript, so every fold gets fresh random
+    /// oracles from `new_ro_nark` & `new_ro_acc` & the folds are verified in parallel. The result
+    /// is the same as of [`ProtoGalaxy::verify`] called for each fold in order.
+    ///
+    /// Nothing is batched: the folds aren't combined & each one costs a full
+    /// [`ProtoGalaxy::verify`]
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::FoldIncomingCount`]: some fold has not `L` incoming instances
+    /// - the first error of [`ProtoGalaxy::verify`] by the order of `folds`
+    #[instrument(skip_all, fields(L = L, folds = folds.len()))]
+    pub fn par_verify<NarkRO: ROTrait<C::Base>, AccRO: ROTrait<C::Base>>(
+        vp: &VerifierParam<C>,
+        new_ro_nark: impl Fn() -> NarkRO + Sync,
+        new_ro_acc: impl Fn() -> AccRO + Sync,
+        folds: &[(
+            AccumulatorInstance<C>,
+            &[PlonkInstance<C>],
+            Proof<C::ScalarExt>,
+        )],
+    ) -> Result<Vec<AccumulatorInstance<C>>, crate::Error> {
+        folds
+            .par_iter()
+            .enumerate()
+            .map(|(index, (accumulator, incoming, proof))| {
+                let incoming = <&[PlonkInstance<C>; L]>::try_from(*incoming).map_err(|_| {
+                    Error::FoldIncomingCount {
+                        index,
+                        expected: L,
+                        actual: incoming.len(),
+                    }
+                })?;
+
+                Self::verify(
+                    vp,
+                    &mut new_ro_nark(),
+                    &mut new_ro_acc(),
+                    accumulator,
+                    incoming,
+                    proof,
+                )
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect()
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum VerifyError<F: PrimeField> {
     #[error("Error while evaluate witness: {0:?}")]
     PlonkEval(plonk::eval::Error),
-    #[error("Expected `e` {expected_e:?}, but evaluated is {evaluated_e:?}")]
-    MismatchE { expected_e: F, evaluated_e: F },
+    #[error("Expected `e` {expected_e:?}, but evaluated is {evaluated_e:?}, first non-zero gate: {first_offending:?}")]
+    MismatchE {
+        expected_e: F,
+        evaluated_e: F,
+        /// The first gate with non-zero evaluation for an accumulator with zero `e`
+        ///
+        /// Gates of a folded witness don't vanish, so for non-zero `e` it's the first gate
+        /// changed by a single corrupted witness cell, if such a cell is found, see
+        /// [`ProtoGalaxy::find_corrupted_cell`]
+        first_offending: Option<GateRow>,
+    },
     #[error("Permutation check failed")]
     PermCheckFailed { mismatch_count: usize },
     #[error("Commitment of")]
@@ -494,6 +810,7 @@ pub enum VerifyError<F: PrimeField> {
 
 impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
     fn is_sat_accumulation(
+        ck: &CommitmentKey<C>,
         S: &PlonkStructure<C::ScalarExt>,
         acc: &Accumulator<C>,
     ) -> Result<(), VerifyError<C::ScalarExt>> {
@@ -507,10 +824,7 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
                 result_with_evaluated_gate.map(|value| Node { value, height: 0 })
             })
             // TODO #324 Migrate to a parallel algorithm
-            // TODO #324 Implement `try_tree_reduce` to stop on the first error
-            .tree_reduce(|left_w, right_w| {
-                let (mut left_n, right_n) = (left_w?, right_w?);
-
+            .try_tree_reduce(|mut left_n, right_n| {
                 if left_n.height != right_n.height {
                     unreachable!(
                         "must be unreachable, since the number of rows is the degree of 2, but: {l_height} != {r_height}",
@@ -530,13 +844,164 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
             .unwrap_or_default();
 
         if evaluated_e == acc.e {
-            Ok(())
+            return Ok(());
+        }
+
+        let row_size = 1 << S.k;
+        let first_offending = if acc.e.is_zero_vartime() {
+            plonk::iter_evaluate_witness::<C::ScalarExt>(S, &acc.trace)
+                .position(|value| value.map_or(true, |value| !value.is_zero_vartime()))
         } else {
-            Err(VerifyError::MismatchE {
-                expected_e: acc.e,
-                evaluated_e,
+            Self::find_corrupted_cell(ck, S, acc, evaluated_e)
+        }
+        .map(|index| GateRow {
+            gate: index / row_size,
+            row: index % row_size,
+        });
+
+        Err(VerifyError::MismatchE {
+            expected_e: acc.e,
+            evaluated_e,
+            first_offending,
+        })
+    }
+
+    /// Index of the first gate evaluation changed by a single corrupted witness cell
+    ///
+    /// Gates of a folded witness don't vanish, so the corrupted row can't be seen from the gates
+    /// alone. Each cell `W[k]` of a round with mismatched commitment is a candidate: it's the
+    /// corrupted one if some `t` both restores `e`, i.e. is a root of
+    /// `P(t) = evaluated_e - e + sum_i pow_i(betas) * (f_i(W[k] - t) - f_i(W[k]))` over the gates
+    /// reading the cell, & explains the commitment: `commit(W) - W_commitment = t * ck[k]`.
+    ///
+    /// Only `P` of degree up to two is solved, cells of gates with higher degree in them are
+    /// skipped. Every cell of a mismatched round is tried, so it's only for diagnostics.
+    fn find_corrupted_cell(
+        ck: &CommitmentKey<C>,
+        S: &PlonkStructure<C::ScalarExt>,
+        acc: &Accumulator<C>,
+        evaluated_e: C::ScalarExt,
+    ) -> Option<usize> {
+        let row_size = 1usize << S.k;
+
+        let rotations = S
+            .gates
+            .iter()
+            .flat_map(|gate| {
+                let mut columns = BTreeSet::new();
+                gate.poly_set(&mut columns);
+                columns
+            })
+            .filter_map(|column| match column {
+                ColumnIndex::Polynominal { rotation, .. } => Some(rotation),
+                ColumnIndex::Challenge { .. } => None,
             })
+            .collect::<BTreeSet<_>>();
+
+        // The weight of `tree_reduce` of `is_sat_accumulation`: `betas[j]` for each bit `j`
+        let pow_i = |index: usize| {
+            acc.betas
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| (index >> bit) & 1 == 1)
+                .map(|(_, beta)| *beta)
+                .product::<C::ScalarExt>()
+        };
+
+        // Gates reading `W[k] - t` at `indexes`
+        let eval_at = |trace: &mut PlonkTrace<C>,
+                       round: usize,
+                       k: usize,
+                       t: C::ScalarExt,
+                       indexes: &BTreeSet<usize>| {
+            let value = trace.w.W[round][k];
+            trace.w.W[round][k] = value - t;
+            let evaluated = indexes
+                .iter()
+                .map(|index| {
+                    plonk::iter_evaluate_witness_range(S, &*trace, *index..*index + 1)
+                        .next()?
+                        .ok()
+                })
+                .collect::<Option<Vec<_>>>();
+            trace.w.W[round][k] = value;
+            evaluated
+        };
+
+        let mismatch = evaluated_e - acc.e;
+        let mut trace = acc.trace.clone();
+
+        let PlonkTrace { u, w } = &acc.trace;
+        for (round, (W_commitment, W)) in u.W_commitments.iter().zip_eq(&w.W).enumerate() {
+            let commitment = ck.commit(W).ok()?;
+            if commitment == *W_commitment {
+                continue;
+            }
+            let diff = commitment.to_curve() - W_commitment.to_curve();
+
+            for k in 0..W.len() {
+                let row = (k % row_size) as i32;
+                let indexes = (0..S.gates.len())
+                    .flat_map(|gate| {
+                        rotations.iter().map(move |rotation| {
+                            gate * row_size + (row - rotation).rem_euclid(row_size as i32) as usize
+                        })
+                    })
+                    .collect::<BTreeSet<_>>();
+
+                let Some(evaluated) = (0..4u64)
+                    .map(|t| eval_at(&mut trace, round, k, C::ScalarExt::from(t), &indexes))
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    continue;
+                };
+
+                let weighted = |values: &[C::ScalarExt]| {
+                    indexes
+                        .iter()
+                        .zip_eq(values)
+                        .map(|(index, value)| pow_i(*index) * value)
+                        .sum::<C::ScalarExt>()
+                };
+                let p = evaluated
+                    .iter()
+                    .map(|values| mismatch + weighted(values) - weighted(&evaluated[0]))
+                    .collect::<Box<[_]>>();
+
+                // `P(t) = a * t^2 + b * t + c` through `t = 0, 1, 2`, of higher degree if `P(3)`
+                // doesn't match
+                let a = (p[2] - p[1].double() + p[0]) * C::ScalarExt::from(2).invert().unwrap();
+                let b = p[1] - p[0] - a;
+                let c = p[0];
+                if a * C::ScalarExt::from(9) + b * C::ScalarExt::from(3) + c != p[3] {
+                    continue;
+                }
+
+                let roots = if a.is_zero_vartime() {
+                    Option::from(b.invert())
+                        .map(|b_inv: C::ScalarExt| vec![-c * b_inv])
+                        .unwrap_or_default()
+                } else {
+                    let a2_inv = a.double().invert().unwrap();
+                    Option::from((b.square() - (a * c).double().double()).sqrt())
+                        .map(|sqrt: C::ScalarExt| vec![(sqrt - b) * a2_inv, (-sqrt - b) * a2_inv])
+                        .unwrap_or_default()
+                };
+
+                let Some(t) = roots.into_iter().find(|t| diff == ck[k] * *t) else {
+                    continue;
+                };
+
+                let repaired = eval_at(&mut trace, round, k, t, &indexes)?;
+                return indexes
+                    .iter()
+                    .zip_eq(evaluated[0].iter().zip_eq(&repaired))
+                    .find(|(_, (corrupted, repaired))| corrupted != repaired)
+                    .map(|(index, _)| *index);
+            }
         }
+
+        None
     }
 
     fn is_sat_permutation(
@@ -614,7 +1079,7 @@ impl<C: CurveAffine, const L: usize> ProtoGalaxy<C, L> {
     ) -> Result<(), Vec<VerifyError<C::ScalarExt>>> {
         let mut errors = vec![];
 
-        if let Err(err) = Self::is_sat_accumulation(S, acc) {
+        if let Err(err) = Self::is_sat_accumulation(ck, S, acc) {
             errors.push(err);
         }
 
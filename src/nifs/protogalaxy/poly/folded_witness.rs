@@ -1,13 +1,12 @@
-use std::iter;
+use std::{iter, mem};
 
 use itertools::*;
-use rayon::prelude::*;
 
 use crate::{
     ff::PrimeField,
     plonk::{GetChallenges, GetWitness, PlonkWitness},
     polynomial::lagrange,
-    util::MultiCartesianProduct,
+    util::{parallel::*, MultiCartesianProduct},
 };
 
 pub(crate) struct FoldedWitness<F: PrimeField> {
@@ -16,6 +15,11 @@ pub(crate) struct FoldedWitness<F: PrimeField> {
 }
 
 impl<F: PrimeField> FoldedWitness<F> {
+    /// For each `X` from `points_for_fft` folds `accumulator` & `traces` with coefficients
+    /// `L_i(X)`, where `L_0` is for `accumulator`
+    ///
+    /// If the lagrange domain is bigger than `traces.len() + 1`, the rest of it is padded with
+    /// zero traces, which add nothing to the sum, so only `traces` are folded
     pub(crate) fn new(
         points_for_fft: &[F],
         lagrange_domain: u32,
@@ -30,10 +34,120 @@ impl<F: PrimeField> FoldedWitness<F> {
             })
             .collect::<Box<[_]>>();
 
+        Self::fold(&polys_L_in_challenges, accumulator, traces)
+    }
+
+    /// Same as [`FoldedWitness::new`], but contribution of `traces[i]` is scaled by `weights[i]`,
+    /// i.e. coefficient of `traces[i]` is `weights[i] * L_{i + 1}(X)`
+    ///
+    /// `accumulator` is not weighted, so with all weights equal to one it matches
+    /// [`FoldedWitness::new`]
+    ///
+    /// # Panics
+    ///
+    /// If `weights.len() != traces.len()`
+    pub(crate) fn new_weighted(
+        points_for_fft: &[F],
+        lagrange_domain: u32,
+        accumulator: &(impl Sync + GetChallenges<F> + GetWitness<F>),
+        traces: &[(impl Sync + GetChallenges<F> + GetWitness<F>)],
+        weights: &[F],
+    ) -> Box<[Self]> {
+        assert_eq!(weights.len(), traces.len(), "one weight per trace expected");
+
+        let polys_L_in_challenges = points_for_fft
+            .iter()
+            .map(|X| {
+                lagrange::iter_eval_lagrange_poly_for_cyclic_group(*X, lagrange_domain)
+                    .zip(iter::once(&F::ONE).chain(weights))
+                    .map(|(poly_L_in_X, weight)| poly_L_in_X * weight)
+                    .collect::<Box<[_]>>()
+            })
+            .collect::<Box<[_]>>();
+
+        Self::fold(&polys_L_in_challenges, accumulator, traces)
+    }
+
+    /// Same as [`FoldedWitness::new`], but lazy: each point is folded independently, when the
+    /// iterator reaches it
+    pub(crate) fn iter<'l>(
+        points_for_fft: &'l [F],
+        lagrange_domain: u32,
+        accumulator: &'l (impl Sync + GetChallenges<F> + GetWitness<F>),
+        traces: &'l [(impl Sync + GetChallenges<F> + GetWitness<F>)],
+    ) -> impl 'l + Iterator<Item = Self> {
+        points_for_fft
+            .iter()
+            .map(move |X| Self::fold_in_point(*X, lagrange_domain, accumulator, traces))
+    }
+
+    /// Parallel version of [`FoldedWitness::iter`], points are distributed across threads
+    ///
+    /// The order is the same as the order of `points_for_fft`
+    #[cfg(feature = "parallel")]
+    pub(crate) fn par_iter<'l>(
+        points_for_fft: &'l [F],
+        lagrange_domain: u32,
+        accumulator: &'l (impl Sync + GetChallenges<F> + GetWitness<F>),
+        traces: &'l [(impl Sync + GetChallenges<F> + GetWitness<F>)],
+    ) -> impl 'l + IndexedParallelIterator<Item = Self> {
+        points_for_fft
+            .par_iter()
+            .map(move |X| Self::fold_in_point(*X, lagrange_domain, accumulator, traces))
+    }
+
+    /// `accumulator` & `traces` folded with coefficients `L_i(X)`
+    fn fold_in_point(
+        X: F,
+        lagrange_domain: u32,
+        accumulator: &(impl GetChallenges<F> + GetWitness<F>),
+        traces: &[(impl GetChallenges<F> + GetWitness<F>)],
+    ) -> Self {
+        let mut witness = PlonkWitness {
+            W: accumulator
+                .get_witness()
+                .iter()
+                .map(|column| vec![F::ZERO; column.len()])
+                .collect(),
+        };
+        let mut challenges = vec![F::ZERO; accumulator.get_challenges().len()];
+
+        // Padded zero traces have no pair in `zip` & add nothing
+        iter::once((accumulator.get_witness(), accumulator.get_challenges()))
+            .chain(
+                traces
+                    .iter()
+                    .map(|trace| (trace.get_witness(), trace.get_challenges())),
+            )
+            .zip(lagrange::iter_eval_lagrange_poly_for_cyclic_group(
+                X,
+                lagrange_domain,
+            ))
+            .for_each(|((W, trace_challenges), poly_L_in_X)| {
+                witness
+                    .W
+                    .iter_mut()
+                    .flatten()
+                    .zip_eq(W.iter().flatten())
+                    .chain(challenges.iter_mut().zip_eq(trace_challenges))
+                    .for_each(|(folded, value)| *folded += poly_L_in_X * value);
+            });
+
+        Self {
+            witness,
+            challenges,
+        }
+    }
+
+    fn fold(
+        polys_L_in_challenges: &[Box<[F]>],
+        accumulator: &(impl Sync + GetChallenges<F> + GetWitness<F>),
+        traces: &[(impl Sync + GetChallenges<F> + GetWitness<F>)],
+    ) -> Box<[Self]> {
         let folded_witnesses_collection =
-            fold_witnesses(&polys_L_in_challenges, accumulator, traces);
+            fold_witnesses(polys_L_in_challenges, accumulator, traces);
         let folded_challenges_collection =
-            fold_plonk_challenges(&polys_L_in_challenges, accumulator, traces);
+            fold_plonk_challenges(polys_L_in_challenges, accumulator, traces);
 
         folded_witnesses_collection
             .into_iter()
@@ -45,6 +159,14 @@ impl<F: PrimeField> FoldedWitness<F> {
             .collect()
     }
 }
+impl<F: PrimeField> FoldedWitness<F> {
+    /// Size of the folded witness & challenges in bytes
+    pub(crate) fn bytes(&self) -> usize {
+        (self.witness.W.iter().map(Vec::len).sum::<usize>() + self.challenges.len())
+            * mem::size_of::<F>()
+    }
+}
+
 impl<F: PrimeField> GetChallenges<F> for FoldedWitness<F> {
     fn get_challenges(&self) -> &[F] {
         &self.challenges
@@ -1,7 +1,7 @@
 use std::{
-    iter,
+    iter, mem,
     num::NonZeroUsize,
-    ops::{Add, Mul},
+    ops::{Add, Mul, Range},
 };
 
 use itertools::*;
@@ -9,11 +9,21 @@ use tracing::*;
 
 use crate::{
     ff::PrimeField,
-    fft,
+    fft::{self, FftBackend},
     group::ff::WithSmallOrderMulGroup,
     plonk::{self, eval, GetChallenges, GetWitness, PlonkStructure},
-    polynomial::{expression::QueryIndexContext, lagrange, univariate::UnivariatePoly},
-    util::TryMultiProduct,
+    polynomial::{
+        lagrange,
+        univariate::{DivisionError, UnivariatePoly},
+    },
+    util::{parallel::*, TryMultiProduct, TryTreeReduce},
+};
+
+#[cfg(feature = "memory-observer")]
+use super::memory::MemoryAccounting;
+use super::{
+    memory::{Buffer, Phase, PhaseGuard},
+    progress::{ProgressEvent, ProgressTracker},
 };
 
 mod folded_witness;
@@ -25,6 +35,24 @@ pub enum Error {
     Eval(#[from] eval::Error),
     #[error("You can't fold 0 traces")]
     EmptyTracesNotAllowed,
+    #[error("F(X) evaluated in {actual} points, but {expected} points are required for ifft")]
+    WrongPointsCountF { expected: usize, actual: usize },
+}
+
+/// Broken precondition of [`PolyContext::try_new`]
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
+pub enum PolyContextError {
+    #[error("Structure has no gates, so there is nothing to fold")]
+    EmptyGates,
+    /// Domain of `F(X)` or `G(X)` is bigger than the biggest cyclic subgroup of the field
+    #[error("FFT domain of 2^{log_domain} points exceeds two-adicity {two_adicity} of the field")]
+    FftDomainTooLarge { log_domain: u32, two_adicity: u32 },
+    /// Lagrange domain must be padded with zero traces, but they don't satisfy the gates, see
+    /// [`PlonkStructure::zero_trace_violation`]
+    #[error("Gate {gate} doesn't vanish on the zero trace at row {row}, lagrange domain can't be padded")]
+    ZeroTraceNotSatisfying { gate: usize, row: usize },
+    #[error("Failed to evaluate gates on the zero trace: {0:?}")]
+    ZeroTraceEval(eval::Error),
 }
 
 /// This function calculates F(X), which mathematically looks like this:
@@ -37,7 +65,7 @@ pub enum Error {
 ///
 /// # Algorithm
 ///
-/// We use [`Itertools::tree_reduce`] & create `points_count` iterators for `pow_i`, where each
+/// We use [`par_tree_reduce`] & create `points_count` iterators for `pow_i`, where each
 /// iterator uses a different challenge (`X`) from the cyclic group, and then iterate over all
 /// these iterators at once.
 ///
@@ -65,13 +93,24 @@ pub enum Error {
 /// Unlike [`compute_G`] where `X` challenge affects the nodes of the tree and generates multiple
 /// values from them, here multiple values are generated by edges, and they are stored everywhere
 /// except leaves.
-#[instrument(skip_all)]
+#[instrument(
+    skip_all,
+    fields(
+        count_of_evaluation = ctx.count_of_evaluation_with_padding,
+        points_count = ctx.fft_points_count_F()
+    )
+)]
 pub(crate) fn compute_F<F: PrimeField>(
     ctx: &PolyContext<'_, F>,
     betas: impl Iterator<Item = F>,
     delta: F,
     trace: &(impl Sync + GetChallenges<F> + GetWitness<F>),
 ) -> Result<UnivariatePoly<F>, Error> {
+    let _phase = ctx.observe_phase(Phase::ComputeF);
+    ctx.report_buffer(Phase::ComputeF, Buffer::WitnessColumns, || {
+        trace.get_witness().iter().map(Vec::len).sum::<usize>() * mem::size_of::<F>()
+    });
+
     // `n` in paper
     let Some(count_of_evaluation) = get_count_of_valuation_with_padding(ctx.S) else {
         return Ok(UnivariatePoly::new_zeroed(0));
@@ -80,11 +119,6 @@ pub(crate) fn compute_F<F: PrimeField>(
     // `t` in paper
     let fft_points_count_F = ctx.fft_points_count_F();
 
-    debug!(
-        "count_of_evaluation: {count_of_evaluation};
-        points_count: {fft_points_count_F}"
-    );
-
     // Use the elements of the cyclic group together with beta & delta as challenge and calculate them
     // degrees
     //
@@ -110,6 +144,12 @@ pub(crate) fn compute_F<F: PrimeField>(
         })
         .collect::<Box<[_]>>();
     debug!("challenges powers ready ready");
+    ctx.report_buffer(Phase::ComputeF, Buffer::ChallengesPowers, || {
+        fft_points_count_F * ctx.betas_count() * mem::size_of::<F>()
+    });
+    ctx.report_buffer(Phase::ComputeF, Buffer::NodePoints, || {
+        ctx.live_nodes_count(count_of_evaluation.get()) * fft_points_count_F * mem::size_of::<F>()
+    });
 
     /// Auxiliary wrapper for using the tree to evaluate polynomials
     #[derive(Debug)]
@@ -124,26 +164,35 @@ pub(crate) fn compute_F<F: PrimeField>(
         },
     }
 
-    let evaluated = plonk::iter_evaluate_witness::<F>(ctx.S, trace)
-        .chain(iter::repeat(Ok(F::ZERO)))
-        .take(count_of_evaluation.get())
-        .map(|result_with_evaluated_gate| {
-            debug!("witness row: {:?}", result_with_evaluated_gate);
-            result_with_evaluated_gate.map(Node::Leaf)
-        })
-        // TODO #324 Migrate to a parallel algorithm
-        // TODO #324 Implement `try_tree_reduce` to stop on the first error
-        .tree_reduce(|left_w, right_w| {
-            let (left_w, right_w) = (left_w?, right_w?);
-
-            match (left_w, right_w) {
-                (Node::Leaf(left), Node::Leaf(right)) => Ok(Node::Calculated {
+    let progress = ctx.track_progress(Phase::ComputeF, count_of_evaluation.get());
+    let progress = progress.as_ref();
+
+    let evaluated = par_tree_reduce(
+        ctx.chunks_count(count_of_evaluation.get()),
+        count_of_evaluation.get(),
+        |range| {
+            let len = range.len();
+            plonk::iter_evaluate_witness_range::<F>(ctx.S, trace, range)
+                .chain(iter::repeat(Ok(F::ZERO)))
+                .take(len)
+                .enumerate()
+                .map(move |(index, result_with_evaluated_gate)| {
+                    trace!("witness row: {:?}", result_with_evaluated_gate);
+                    if let Some(progress) = progress {
+                        progress.on_leaf(index, len);
+                    }
+                    result_with_evaluated_gate.map(Node::Leaf)
+                })
+        },
+        |left_w: Node<F>, right_w: Node<F>| {
+            let merged = match (left_w, right_w) {
+                (Node::Leaf(left), Node::Leaf(right)) => Node::Calculated {
                     points: challenges_powers
                         .iter()
                         .map(|challenge_powers| left + (right * challenge_powers[0]))
                         .collect(),
                     height: NonZeroUsize::new(1).unwrap(),
-                }),
+                },
                 (
                     Node::Calculated {
                         points: mut left,
@@ -153,25 +202,44 @@ pub(crate) fn compute_F<F: PrimeField>(
                         points: right,
                         height: r_height,
                     },
+                ) => {
                     // The tree must be binary, so we only calculate at the one node level
-                ) if l_height.eq(&r_height) => {
+                    debug_assert_eq!(l_height, r_height);
+
                     itertools::multizip((challenges_powers.iter(), left.iter_mut(), right.iter()))
                         .for_each(|(challenge_powers, left, right)| {
                             *left += *right * challenge_powers[l_height.get()]
                         });
 
-                    Ok(Node::Calculated {
+                    Node::Calculated {
                         points: left,
                         height: l_height.saturating_add(1),
-                    })
+                    }
                 }
                 other => unreachable!("this case must be unreachable: {other:?}"),
+            };
+
+            if let (Some(progress), Node::Calculated { height, .. }) = (progress, &merged) {
+                progress.on_merge(height.get());
             }
-        });
+
+            Ok(merged)
+        },
+    );
 
     match evaluated {
         Some(Ok(Node::Calculated { mut points, .. })) => {
-            fft::ifft(&mut points);
+            // Each node holds one point per element of the cyclic subgroup of size
+            // `fft_points_count_F`, whether `betas_count + 1` is a power of two or not
+            if points.len() != fft_points_count_F {
+                return Err(Error::WrongPointsCountF {
+                    expected: fft_points_count_F,
+                    actual: points.len(),
+                });
+            }
+
+            debug_span!("ifft", len = points.len()).in_scope(|| ctx.ifft(&mut points));
+            ctx.report_done(Phase::ComputeF);
             Ok(UnivariatePoly(points))
         }
         Some(Err(err)) => Err(err.into()),
@@ -181,43 +249,234 @@ pub(crate) fn compute_F<F: PrimeField>(
 
 pub struct PolyContext<'s, F: PrimeField> {
     S: &'s PlonkStructure<F>,
-    /// Equal to the number of incoming traces plus one (accumulator)
-    /// Must be a power of two
+    /// Size of the lagrange domain: the number of incoming traces plus one (accumulator), padded
+    /// to the next power of two
+    ///
+    /// Padded slots are zero traces, they contribute nothing to the folded witness & instance
     instances_to_fold: usize,
     /// The number of points used in G(X)
     ///
     /// Used in [`compute_G`]
     ///
-    /// Equal to `(traces_len * max_gate_degree + 1).next_power_of_two()`
+    /// Equal to `((instances_to_fold - 1) * max_gate_degree + 1).next_power_of_two()`
     fft_points_count_G: usize,
     /// Number of calculations, padding with zeros to the nearest power of two
     count_of_evaluation_with_padding: usize,
+    /// [`fft::DefaultFftBackend`] is used if `None`
+    fft: Option<&'s dyn FftBackend<F>>,
+    /// Cap of parallel tasks in [`par_tree_reduce`], [`current_num_threads`] is used if `None`
+    parallelism: Option<NonZeroUsize>,
+    #[cfg(feature = "memory-observer")]
+    memory: Option<&'s MemoryAccounting>,
+    /// Callback of [`PolyContext::with_progress`], nothing is reported if `None`
+    progress: Option<&'s (dyn Fn(ProgressEvent) + Sync)>,
 }
 
 impl<'s, F: PrimeField> PolyContext<'s, F> {
+    #[deprecated(note = "use `PolyContext::try_new`, it reports broken preconditions as errors")]
     pub fn new(
         S: &'s PlonkStructure<F>,
         traces: &[(impl Sync + GetChallenges<F> + GetWitness<F>)],
     ) -> Self {
-        let count_of_evaluation = get_count_of_valuation_with_padding(S).unwrap().get();
+        Self::try_new(S, traces).unwrap()
+    }
+
+    /// Checks that `traces` with the accumulator can be folded with `S`
+    ///
+    /// - `S` must have gates, otherwise `F(X)` has no evaluations
+    /// - domains of `F(X)` & `G(X)` must fit into the two-adicity of `F`
+    /// - if the lagrange domain is padded, gates must vanish on the zero trace
+    pub fn try_new(
+        S: &'s PlonkStructure<F>,
+        traces: &[(impl Sync + GetChallenges<F> + GetWitness<F>)],
+    ) -> Result<Self, PolyContextError> {
+        Self::from_structure(S, traces.len() + 1)
+    }
 
-        let instances_to_fold = traces.len() + 1;
-        assert!(instances_to_fold.is_power_of_two());
+    /// Same as [`PolyContext::try_new`], but without traces, e.g. to size buffers before the
+    /// witnesses are produced
+    ///
+    /// `instances_to_fold` is the count of incoming traces plus one (accumulator), it's padded to
+    /// the next power of two
+    pub fn from_structure(
+        S: &'s PlonkStructure<F>,
+        instances_to_fold: usize,
+    ) -> Result<Self, PolyContextError> {
+        let count_of_evaluation = get_count_of_valuation_with_padding(S)
+            .ok_or(PolyContextError::EmptyGates)?
+            .get();
+
+        if !instances_to_fold.is_power_of_two() {
+            if let Some((gate, row)) = S
+                .zero_trace_violation()
+                .map_err(PolyContextError::ZeroTraceEval)?
+            {
+                return Err(PolyContextError::ZeroTraceNotSatisfying { gate, row });
+            }
+        }
+        let instances_to_fold = instances_to_fold.next_power_of_two();
 
-        let fft_points_count_G = get_points_count(S, traces.len());
+        let fft_points_count_G = get_points_count(S, instances_to_fold - 1);
 
-        Self {
+        let ctx = Self {
             S,
             instances_to_fold,
             fft_points_count_G,
             count_of_evaluation_with_padding: count_of_evaluation,
+            fft: None,
+            parallelism: None,
+            #[cfg(feature = "memory-observer")]
+            memory: None,
+            progress: None,
+        };
+
+        let log_domain = ctx
+            .fft_log_domain_size_G()
+            .max(ctx.fft_points_count_F().ilog2());
+        if log_domain > F::S {
+            return Err(PolyContextError::FftDomainTooLarge {
+                log_domain,
+                two_adicity: F::S,
+            });
+        }
+
+        Ok(ctx)
+    }
+
+    /// Caps the count of parallel tasks of [`compute_F`] & [`compute_G`], e.g. for benchmarks
+    ///
+    /// `0` removes the cap, then [`current_num_threads`] is used
+    pub fn set_parallelism(&mut self, n: usize) {
+        self.parallelism = NonZeroUsize::new(n);
+    }
+
+    /// Count of chunks for [`par_tree_reduce`] over `count` leaves
+    ///
+    /// Power of two, so chunks are subtrees of the binary tree. Without the `parallel` feature
+    /// it's always one chunk, so single-threaded builds reduce the tree as before
+    fn chunks_count(&self, count: usize) -> usize {
+        if cfg!(not(feature = "parallel")) {
+            return 1;
+        }
+
+        let parallelism = self
+            .parallelism
+            .map_or_else(current_num_threads, NonZeroUsize::get);
+
+        (1 << parallelism.max(1).ilog2()).min(count.max(1))
+    }
+
+    /// Upper bound of the nodes of [`par_tree_reduce`] over `count` leaves alive at once
+    ///
+    /// Each chunk of `2^m` leaves is reduced depth-first, so it keeps at most `m + 1` nodes: a
+    /// pending one per height below `m` & a pair of leaves. The root of a finished chunk replaces them
+    /// until the final reduction
+    fn live_nodes_count(&self, count: usize) -> usize {
+        let chunks_count = self.chunks_count(count);
+        chunks_count * ((count / chunks_count).max(1).ilog2() as usize + 1)
+    }
+
+    /// Route FFTs of [`compute_F`], [`compute_G`] & [`compute_K`] through `backend`
+    pub fn with_fft_backend(mut self, backend: Option<&'s dyn FftBackend<F>>) -> Self {
+        self.fft = backend;
+        self
+    }
+
+    fn ifft(&self, a: &mut [F]) {
+        match self.fft {
+            Some(backend) => backend.ifft(a),
+            None => fft::ifft(a),
+        }
+    }
+
+    /// Report phases & big allocations of [`compute_F`], [`compute_G`] & [`compute_K`] into
+    /// `memory`
+    #[cfg(feature = "memory-observer")]
+    pub fn with_memory_accounting(mut self, memory: Option<&'s MemoryAccounting>) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    fn observe_phase(&self, phase: Phase) -> Option<PhaseGuard<'s>> {
+        #[cfg(feature = "memory-observer")]
+        let guard = self.memory.map(|memory| memory.phase(phase));
+        #[cfg(not(feature = "memory-observer"))]
+        let guard = {
+            let _ = phase;
+            None
+        };
+
+        guard
+    }
+
+    /// `bytes` is called only if accounting is enabled
+    fn report_buffer(&self, phase: Phase, buffer: Buffer, bytes: impl FnOnce() -> usize) {
+        #[cfg(feature = "memory-observer")]
+        if let Some(memory) = self.memory {
+            memory.buffer(phase, buffer, bytes());
+        }
+        #[cfg(not(feature = "memory-observer"))]
+        let _ = (phase, buffer, bytes);
+    }
+
+    /// Report progress of [`compute_F`], [`compute_G`] & [`compute_K`] into `progress`, see
+    /// [`ProgressEvent`]
+    pub fn with_progress(mut self, progress: Option<&'s (dyn Fn(ProgressEvent) + Sync)>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// `None` if there is no progress callback, so nothing is allocated on the hot path
+    fn track_progress(&self, phase: Phase, leaves: usize) -> Option<ProgressTracker<'s>> {
+        self.progress
+            .map(|progress| ProgressTracker::new(progress, phase, leaves))
+    }
+
+    fn report_done(&self, phase: Phase) {
+        if let Some(progress) = self.progress {
+            progress(ProgressEvent::Done { phase });
+        }
+    }
+
+    fn coset_fft(&self, a: &mut [F])
+    where
+        F: WithSmallOrderMulGroup<3>,
+    {
+        fft::distribute_powers_zeta(a, F::ZETA, F::ZETA.square(), true);
+        match self.fft {
+            Some(backend) => backend.fft(a),
+            None => fft::fft(a),
+        }
+    }
+
+    fn coset_ifft(&self, a: &mut [F])
+    where
+        F: WithSmallOrderMulGroup<3>,
+    {
+        match self.fft {
+            Some(backend) => backend.coset_ifft(a),
+            None => fft::DefaultFftBackend.coset_ifft(a),
         }
     }
 
+    /// Size of the lagrange domain, padded to the power of two
+    pub fn instances_to_fold(&self) -> usize {
+        self.instances_to_fold
+    }
+
+    /// Count of gate evaluations of all rows, padded with zeros to the power of two
+    pub fn count_of_evaluation_with_padding(&self) -> usize {
+        self.count_of_evaluation_with_padding
+    }
+
     pub fn betas_count(&self) -> usize {
         self.count_of_evaluation_with_padding.ilog2() as usize
     }
 
+    /// Size of the cyclic subgroup where `F(X)` is evaluated before ifft
+    ///
+    /// `deg(F) = betas_count`, so at least `betas_count + 1` points are needed; if it's already a
+    /// power of two, the subgroup is exactly of this size
     pub fn fft_points_count_F(&self) -> usize {
         (self.betas_count() + 1).next_power_of_two()
     }
@@ -226,14 +485,23 @@ impl<'s, F: PrimeField> PolyContext<'s, F> {
         self.fft_points_count_G.ilog2()
     }
 
+    /// Points of cyclic subgroup, where `G(X)` evaluated in [`compute_G`] before ifft
+    pub fn points_for_fft_G(&self) -> Box<[F]> {
+        lagrange::iter_cyclic_subgroup(self.fft_log_domain_size_G())
+            .take(self.fft_points_count_G)
+            .collect()
+    }
+
     pub fn lagrange_domain(&self) -> u32 {
         self.instances_to_fold.ilog2()
     }
 
+    /// Log of the lagrange domain for `TRACES_LEN` incoming traces & the accumulator
+    ///
+    /// If `TRACES_LEN + 1` is not a power of two, the domain is padded with zero traces, so
+    /// [`PolyContext::try_new`] checks that they satisfy the gates
     pub fn get_lagrange_domain<const TRACES_LEN: usize>() -> u32 {
-        let instances_to_fold = TRACES_LEN + 1;
-        assert!(instances_to_fold.is_power_of_two());
-        instances_to_fold.ilog2()
+        (TRACES_LEN + 1).next_power_of_two().ilog2()
     }
 
     pub fn fft_log_domain_size_K(&self) -> u32 {
@@ -242,6 +510,14 @@ impl<'s, F: PrimeField> PolyContext<'s, F> {
             .saturating_sub(self.instances_to_fold)
             .next_power_of_two() as u32
     }
+
+    /// Is the domain for `K(X)` degenerate
+    ///
+    /// `deg(G) = (n - 1) * max_gate_degree` & `deg(Z) = n`, so if `deg(G) <= n` then
+    /// `K(X) = (G(X) - F(alpha) * L0(X)) / Z(X)` is a constant, fully defined by one point
+    pub fn is_k_domain_trivial(&self) -> bool {
+        (self.instances_to_fold - 1) * self.S.max_gate_degree() <= self.instances_to_fold
+    }
 }
 
 /// This function calculates G(X), which mathematically looks like this:
@@ -255,7 +531,7 @@ impl<'s, F: PrimeField> PolyContext<'s, F> {
 ///
 /// # Algorithm
 ///
-/// We use [`Itertools::tree_reduce`] & store in each node `X` points, for each X challenge
+/// We use [`par_tree_reduce`] & store in each node `X` points, for each X challenge
 ///
 /// I.e. item `i` from this iterator is a collection of [pow_i(X0), pow_i(X1), ...]
 ///
@@ -280,7 +556,14 @@ impl<'s, F: PrimeField> PolyContext<'s, F> {
 ///
 /// Unlike [`compute_F`] where `X` challenge affects the edges of the tree, here the set of values
 /// is in the nodes
-#[instrument(skip_all)]
+#[instrument(
+    skip_all,
+    fields(
+        count_of_evaluation = ctx.count_of_evaluation_with_padding,
+        points_count = ctx.fft_points_count_G,
+        traces = traces.len()
+    )
+)]
 pub(crate) fn compute_G<F: PrimeField>(
     ctx: &PolyContext<F>,
     betas_stroke: impl Iterator<Item = F>,
@@ -291,12 +574,66 @@ pub(crate) fn compute_G<F: PrimeField>(
         return Err(Error::EmptyTracesNotAllowed);
     }
 
+    let _phase = ctx.observe_phase(Phase::ComputeG);
+    let points_for_fft = ctx.points_for_fft_G();
+
+    evaluate_G(
+        ctx,
+        betas_stroke,
+        &FoldedWitness::new(&points_for_fft, ctx.lagrange_domain(), accumulator, traces),
+    )
+}
+
+/// Same as [`compute_G`], but contribution of `traces[i]` into folded witness is scaled by
+/// `weights[i]`, see [`FoldedWitness::new_weighted`]
+///
+/// With all weights equal to one it matches [`compute_G`]
+#[instrument(skip_all, fields(traces = traces.len()))]
+pub(crate) fn compute_G_weighted<F: PrimeField>(
+    ctx: &PolyContext<F>,
+    betas_stroke: impl Iterator<Item = F>,
+    accumulator: &(impl Sync + GetChallenges<F> + GetWitness<F>),
+    traces: &[(impl Sync + GetChallenges<F> + GetWitness<F>)],
+    weights: &[F],
+) -> Result<UnivariatePoly<F>, Error> {
+    if traces.is_empty() {
+        return Err(Error::EmptyTracesNotAllowed);
+    }
+
+    let _phase = ctx.observe_phase(Phase::ComputeG);
+    let points_for_fft = ctx.points_for_fft_G();
+
+    evaluate_G(
+        ctx,
+        betas_stroke,
+        &FoldedWitness::new_weighted(
+            &points_for_fft,
+            ctx.lagrange_domain(),
+            accumulator,
+            traces,
+            weights,
+        ),
+    )
+}
+
+/// Evaluates `G(X)` in points of [`PolyContext::points_for_fft_G`] with already folded witnesses
+/// & interpolates it
+fn evaluate_G<F: PrimeField>(
+    ctx: &PolyContext<F>,
+    betas_stroke: impl Iterator<Item = F>,
+    folded_witnesses: &[FoldedWitness<F>],
+) -> Result<UnivariatePoly<F>, Error> {
     let betas_stroke = betas_stroke.take(ctx.betas_count()).collect::<Box<[_]>>();
     assert_eq!(ctx.betas_count(), betas_stroke.len());
 
-    let points_for_fft = lagrange::iter_cyclic_subgroup(ctx.fft_log_domain_size_G())
-        .take(ctx.fft_points_count_G)
-        .collect::<Box<[_]>>();
+    ctx.report_buffer(Phase::ComputeG, Buffer::FoldedWitness, || {
+        folded_witnesses.iter().map(FoldedWitness::bytes).sum()
+    });
+    ctx.report_buffer(Phase::ComputeG, Buffer::NodePoints, || {
+        ctx.live_nodes_count(ctx.count_of_evaluation_with_padding)
+            * folded_witnesses.len()
+            * mem::size_of::<F>()
+    });
 
     /// Auxiliary wrapper for using the tree to evaluate polynomials
     #[derive(Debug)]
@@ -305,16 +642,34 @@ pub(crate) fn compute_G<F: PrimeField>(
         height: usize,
     }
 
-    let evaluated =
-        FoldedWitness::new(&points_for_fft, ctx.lagrange_domain(), accumulator, traces)
-        .iter() // folded witness iter per each X
-        .map(|folded_trace| plonk::iter_evaluate_witness::<F>(ctx.S, folded_trace)
-            .chain(iter::repeat(Ok(F::ZERO)))
-            .take(ctx.count_of_evaluation_with_padding)
-        )
-        .try_multi_product()
-        .map(|points| points.map(|points| Node { values: points, height: 0 }))
-        .tree_reduce(|left, right| {
+    let progress = ctx.track_progress(Phase::ComputeG, ctx.count_of_evaluation_with_padding);
+    let progress = progress.as_ref();
+
+    let evaluated = par_tree_reduce(
+        ctx.chunks_count(ctx.count_of_evaluation_with_padding),
+        ctx.count_of_evaluation_with_padding,
+        |range| {
+            let len = range.len();
+            folded_witnesses
+                .iter() // folded witness iter per each X
+                .map(|folded_trace| {
+                    plonk::iter_evaluate_witness_range::<F>(ctx.S, folded_trace, range.clone())
+                        .chain(iter::repeat(Ok(F::ZERO)))
+                        .take(len)
+                })
+                .try_multi_product()
+                .enumerate()
+                .map(move |(index, points)| {
+                    if let Some(progress) = progress {
+                        progress.on_leaf(index, len);
+                    }
+                    points.map(|points| Node {
+                        values: points,
+                        height: 0,
+                    })
+                })
+        },
+        |left: Node<F>, right: Node<F>| {
             let (
                 Node {
                     values: mut left,
@@ -324,27 +679,32 @@ pub(crate) fn compute_G<F: PrimeField>(
                     values: right,
                     height: r_height,
                 },
-            ) = (left?, right?);
+            ) = (left, right);
 
-            if l_height.eq(&r_height) {
-                left.iter_mut().zip(right.iter()).for_each(|(left, right)| {
-                    *left += *right * betas_stroke[l_height];
-                });
+            // Different heights should not be here because the tree is binary
+            debug_assert_eq!(l_height, r_height);
 
-                Ok(Node {
-                    values: left,
-                    height: l_height.saturating_add(1),
-                })
-            } else {
-                unreachable!("different heights should not be here because the tree is binary: {l_height} != {r_height}")
+            left.iter_mut().zip(right.iter()).for_each(|(left, right)| {
+                *left += *right * betas_stroke[l_height];
+            });
+
+            if let Some(progress) = progress {
+                progress.on_merge(l_height + 1);
             }
-        });
+
+            Ok(Node {
+                values: left,
+                height: l_height.saturating_add(1),
+            })
+        },
+    );
 
     match evaluated {
         Some(Ok(Node {
             values: mut points, ..
         })) => {
-            fft::ifft(&mut points);
+            debug_span!("ifft", len = points.len()).in_scope(|| ctx.ifft(&mut points));
+            ctx.report_done(Phase::ComputeG);
             Ok(UnivariatePoly(points))
         }
         Some(Err(err)) => Err(err.into()),
@@ -352,7 +712,35 @@ pub(crate) fn compute_G<F: PrimeField>(
     }
 }
 
-#[derive(Clone)]
+/// Parallel version of [`TryTreeReduce::try_tree_reduce`] over `count` leaves, `count` is a
+/// power of two
+///
+/// Leaves are split into `chunks_count` chunks at power-of-two boundaries, `leaves(range)`
+/// of each chunk are reduced in a separate task & then the chunk roots are reduced. So the tree
+/// is the same as the single-threaded one & `merge` always gets nodes of equal height.
+fn par_tree_reduce<N, I, E>(
+    chunks_count: usize,
+    count: usize,
+    leaves: impl Sync + Fn(Range<usize>) -> I,
+    merge: impl Sync + Fn(N, N) -> Result<N, E>,
+) -> Option<Result<N, E>>
+where
+    N: Send,
+    E: Send,
+    I: Iterator<Item = Result<N, E>>,
+{
+    let chunk_len = count / chunks_count;
+
+    (0..chunks_count)
+        .into_par_iter()
+        .map(|chunk| leaves(chunk * chunk_len..(chunk + 1) * chunk_len).try_tree_reduce(&merge))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .flatten()
+        .try_tree_reduce(&merge)
+}
+
+#[derive(Clone, Debug)]
 pub(crate) struct PolyChallenges<F> {
     pub(crate) betas: Box<[F]>,
     pub(crate) alpha: F,
@@ -362,14 +750,22 @@ pub(crate) struct PolyChallenges<F> {
 #[derive(Clone)]
 pub(crate) struct BetaStrokeIter<F> {
     cha: PolyChallenges<F>,
+    /// Index of the next beta from the front, `cha.delta` is `delta^{2^beta_index}`
     beta_index: usize,
+    /// Index after the next beta from the back
+    end: usize,
+    /// `delta^{2^i}` for `i` from the first index to `end`, at the first
+    /// [`DoubleEndedIterator::next_back`] call
+    back_deltas: Option<(usize, Vec<F>)>,
 }
 
 impl<F> PolyChallenges<F> {
     pub(crate) fn iter_beta_stroke(self) -> BetaStrokeIter<F> {
         BetaStrokeIter {
+            end: self.betas.len(),
             cha: self,
             beta_index: 0,
+            back_deltas: None,
         }
     }
 }
@@ -379,7 +775,11 @@ impl<F: Clone + Mul<Output = F> + Add<Output = F>> Iterator for BetaStrokeIter<F
 
     /// `next = beta[i] + (alpha * delta^{2^i})`
     fn next(&mut self) -> Option<Self::Item> {
-        let next = self.cha.betas.get(self.beta_index).cloned()?
+        if self.beta_index >= self.end {
+            return None;
+        }
+
+        let next = self.cha.betas[self.beta_index].clone()
             + (self.cha.alpha.clone() * self.cha.delta.clone());
 
         self.beta_index += 1;
@@ -387,8 +787,45 @@ impl<F: Clone + Mul<Output = F> + Add<Output = F>> Iterator for BetaStrokeIter<F
 
         Some(next)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end.saturating_sub(self.beta_index);
+        (len, Some(len))
+    }
+}
+
+impl<F: Clone + Mul<Output = F> + Add<Output = F>> ExactSizeIterator for BetaStrokeIter<F> {}
+
+impl<F: Clone + Mul<Output = F> + Add<Output = F>> DoubleEndedIterator for BetaStrokeIter<F> {
+    /// `next_back = beta[i] + (alpha * delta^{2^i})` for the last `i`
+    ///
+    /// Powers of delta are computed by squaring from the front, so all of them up to `end` are
+    /// computed once, at the first call
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.beta_index >= self.end {
+            return None;
+        }
+
+        let (first, deltas) = self.back_deltas.get_or_insert_with(|| {
+            let deltas = iter::successors(Some(self.cha.delta.clone()), |delta| {
+                Some(delta.clone().mul(delta.clone()))
+            })
+            .take(self.end - self.beta_index)
+            .collect();
+
+            (self.beta_index, deltas)
+        });
+
+        self.end -= 1;
+
+        Some(
+            self.cha.betas[self.end].clone()
+                + (self.cha.alpha.clone() * deltas[self.end - *first].clone()),
+        )
+    }
 }
 
+#[instrument(skip_all, fields(log_domain = ctx.fft_log_domain_size_K()))]
 pub(crate) fn compute_K<F: WithSmallOrderMulGroup<3>>(
     ctx: &PolyContext<F>,
     poly_F_in_alpha: F,
@@ -396,8 +833,11 @@ pub(crate) fn compute_K<F: WithSmallOrderMulGroup<3>>(
     accumulator: &(impl Sync + GetChallenges<F> + GetWitness<F>),
     traces: &[(impl Sync + GetChallenges<F> + GetWitness<F>)],
 ) -> Result<UnivariatePoly<F>, Error> {
+    let _phase = ctx.observe_phase(Phase::ComputeK);
     let poly_G = compute_G(ctx, betas_stroke, accumulator, traces)?;
-    Ok(compute_K_from_G(ctx, poly_G, poly_F_in_alpha))
+    let poly_K = compute_K_from_G(ctx, poly_G, poly_F_in_alpha);
+    ctx.report_done(Phase::ComputeK);
+    Ok(poly_K)
 }
 
 fn compute_K_from_G<F: WithSmallOrderMulGroup<3>>(
@@ -405,35 +845,90 @@ fn compute_K_from_G<F: WithSmallOrderMulGroup<3>>(
     poly_G: UnivariatePoly<F>,
     poly_F_in_alpha: F,
 ) -> UnivariatePoly<F> {
-    UnivariatePoly::coset_ifft(
-        lagrange::iter_cyclic_subgroup::<F>(ctx.fft_log_domain_size_K())
+    let eval_K_in_X = |X: F, poly_G_in_X: F| {
+        let poly_L0_in_X =
+            lagrange::iter_eval_lagrange_poly_for_cyclic_group(X, ctx.lagrange_domain())
+                .next()
+                .unwrap();
+
+        // Z(X) == 0, for X in coset_cyclic_subgroup
+        let poly_Z_in_X = lagrange::eval_vanish_polynomial(ctx.instances_to_fold, X);
+
+        let poly_K_in_X = (poly_G_in_X - (poly_F_in_alpha * poly_L0_in_X))
+            * poly_Z_in_X.invert().expect("Z(X) must be not equal to 0");
+
+        // Costs an extra mul & add per point, so release proving skips it
+        debug_assert_eq!(
+            (poly_F_in_alpha * poly_L0_in_X) + (poly_Z_in_X * poly_K_in_X),
+            poly_G_in_X
+        );
+
+        poly_K_in_X
+    };
+
+    let poly_K = if ctx.is_k_domain_trivial() {
+        // `K(X)` is a constant, so one point outside of the cyclic subgroup is enough
+        UnivariatePoly(Box::new([eval_K_in_X(F::ZETA, poly_G.eval(F::ZETA))]))
+    } else {
+        let log_domain = ctx.fft_log_domain_size_K();
+
+        // `G(X)` on the whole coset at once, instead of evaluation in each point
+        let mut poly_G_in_coset = poly_G
+            .clone()
+            .pad_with_zeroes(1 << log_domain)
+            .expect("deg(G) is less than the domain of K(X)")
+            .0;
+        ctx.coset_fft(&mut poly_G_in_coset);
+
+        let mut points = lagrange::iter_cyclic_subgroup::<F>(log_domain)
             .map(|X| F::ZETA * X)
-            // TODO #293
-            //.zip(poly_G.coset_fft())
-            //.map(|(X, poly_G_in_X)| {
-            .map(|X| {
-                let poly_G_in_X = poly_G.eval(X);
+            .zip_eq(poly_G_in_coset.iter().copied())
+            .map(|(X, poly_G_in_X)| eval_K_in_X(X, poly_G_in_X))
+            .collect::<Box<[_]>>();
+        ctx.coset_ifft(&mut points);
+        UnivariatePoly(points)
+    };
 
-                let poly_L0_in_X =
-                    lagrange::iter_eval_lagrange_poly_for_cyclic_group(X, ctx.lagrange_domain())
-                        .next()
-                        .unwrap();
+    #[cfg(debug_assertions)]
+    match compute_K_from_G_by_division(ctx, &poly_G, poly_F_in_alpha) {
+        Ok(poly_K_by_division) => assert_eq!(
+            poly_K_by_division.pad_with_zeroes(poly_K.len()).as_ref(),
+            Ok(&poly_K),
+            "K(X) from coset path doesn't match the exact division"
+        ),
+        Err(err) => warn!("G(X) - F(alpha) * L0(X) is not divisible by Z(X): {err}"),
+    }
 
-                // Z(X) == 0, for X in coset_cyclic_subgroup
-                let poly_Z_in_X = lagrange::eval_vanish_polynomial(ctx.instances_to_fold, X);
+    poly_K
+}
 
-                let poly_K_in_X = (poly_G_in_X - (poly_F_in_alpha * poly_L0_in_X))
-                    * poly_Z_in_X.invert().expect("Z(X) must be not equal to 0");
+/// Calculates `K(X) = (G(X) - F(alpha) * L0(X)) / Z(X)` in coefficient form
+///
+/// Unlike [`compute_K_from_G`] does not use FFT, because `L0(X) = (1 + X + ... + X^{n-1}) / n`
+/// and division by `Z(X) = X^n - 1` is synthetic. Fails if `G(X) - F(alpha) * L0(X)` is not
+/// divisible by `Z(X)`, i.e. the folded instances don't satisfy the relation.
+fn compute_K_from_G_by_division<F: PrimeField>(
+    ctx: &PolyContext<F>,
+    poly_G: &UnivariatePoly<F>,
+    poly_F_in_alpha: F,
+) -> Result<UnivariatePoly<F>, DivisionError> {
+    let n = ctx.instances_to_fold;
 
-                assert_eq!(
-                    (poly_F_in_alpha * poly_L0_in_X) + (poly_Z_in_X * poly_K_in_X),
-                    poly_G_in_X
-                );
+    let poly_L0_coeff = poly_F_in_alpha
+        * F::from(n as u64)
+            .invert()
+            .expect("count of instances must be not equal to 0");
 
-                poly_K_in_X
-            })
-            .collect::<Box<[_]>>(),
-    )
+    let mut numerator = poly_G.0.to_vec();
+    if numerator.len() < n {
+        numerator.resize(n, F::ZERO);
+    }
+    numerator
+        .iter_mut()
+        .take(n)
+        .for_each(|coeff| *coeff -= poly_L0_coeff);
+
+    UnivariatePoly(numerator.into_boxed_slice()).divide_exact(n)
 }
 
 pub fn get_count_of_valuation<F: PrimeField>(S: &PlonkStructure<F>) -> Option<NonZeroUsize> {
@@ -450,33 +945,32 @@ fn get_count_of_valuation_with_padding<F: PrimeField>(
 }
 
 fn get_points_count<F: PrimeField>(S: &PlonkStructure<F>, traces_len: usize) -> usize {
-    let ctx = QueryIndexContext::from(S);
-    let max_degree = S
-        .gates
-        .iter()
-        .map(|poly| poly.degree(&ctx))
-        .max()
-        .unwrap_or_default();
-
-    (traces_len * max_degree + 1).next_power_of_two()
+    (traces_len * S.max_gate_degree() + 1).next_power_of_two()
 }
 
 #[cfg(test)]
 mod test {
-    use std::iter;
+    use std::{array, iter};
 
     use bitter::{BitReader, LittleEndianReader};
-    use halo2_proofs::{halo2curves::ff::PrimeField, plonk::Circuit};
+    use halo2_proofs::{
+        halo2curves::ff::{PrimeField, WithSmallOrderMulGroup},
+        plonk::Circuit,
+    };
     use tracing::*;
     use tracing_test::traced_test;
 
-    use super::{folded_witness::FoldedWitness, PolyContext};
+    use super::{folded_witness::FoldedWitness, PolyChallenges, PolyContext, PolyContextError};
     use crate::{
         commitment::CommitmentKey,
         ff::Field as _Field,
         halo2curves::{bn256, CurveAffine},
-        plonk::{self, test_eval_witness::poseidon_circuit, PlonkStructure, PlonkTrace},
-        polynomial::{lagrange, univariate::UnivariatePoly},
+        nifs::tests::fibo_circuit::{get_fibo_seq, FiboCircuit},
+        plonk::{
+            self, test_eval_witness::poseidon_circuit, GetChallenges, GetWitness, PlonkInstance,
+            PlonkStructure, PlonkTrace, PlonkWitness,
+        },
+        polynomial::{lagrange, univariate::UnivariatePoly, Expression},
         poseidon::{
             random_oracle::{self, ROTrait},
             PoseidonRO, Spec,
@@ -553,55 +1047,90 @@ mod test {
             .unwrap()
     }
 
-    #[traced_test]
-    #[test]
-    fn cmp_with_direct_eval_of_F() {
-        let (S, mut trace) = poseidon_trace();
+    /// Only the count of traces matters for [`PolyContext::try_new`]
+    struct EmptyTrace;
 
-        let mut rnd = rand::thread_rng();
-        let mut gen = iter::repeat_with(|| Field::random(&mut rnd));
+    impl<F: PrimeField> GetChallenges<F> for EmptyTrace {
+        fn get_challenges(&self) -> &[F] {
+            &[]
+        }
+    }
 
-        trace.w.W.iter_mut().for_each(|row| {
-            row.iter_mut()
-                .for_each(|v| *v = gen.by_ref().next().unwrap())
-        });
+    impl<F: PrimeField> GetWitness<F> for EmptyTrace {
+        fn get_witness(&self) -> &[Vec<F>] {
+            &[]
+        }
+    }
 
-        let traces = [trace];
-        let ctx = PolyContext::new(&S, &traces);
+    /// Two rows with one gate of degree 2
+    fn square_gate_structure<F: PrimeField>() -> PlonkStructure<F> {
+        let challenge = || Box::new(Expression::Challenge(0));
 
-        let delta = gen.by_ref().next().unwrap();
-        let betas = gen.by_ref().take(ctx.betas_count()).collect::<Box<[_]>>();
+        PlonkStructure {
+            k: 1,
+            num_challenges: 1,
+            gates: vec![Expression::Product(challenge(), challenge())],
+            ..Default::default()
+        }
+    }
 
-        let evaluated_poly_F =
-            super::compute_F(&ctx, betas.iter().copied(), delta, &traces[0]).unwrap();
-
-        lagrange::iter_cyclic_subgroup::<Field>(ctx.fft_points_count_F().ilog2())
-            .chain(gen.take(10))
-            .for_each(|X| {
-                let challenge_vector = betas
-                    .iter()
-                    .zip(iter::successors(Some(delta), |d| Some(d.pow([2]))))
-                    .take(ctx.count_of_evaluation_with_padding)
-                    .map(|(beta, delta)| beta + (X * delta))
-                    .collect::<Box<[_]>>();
-
-                let result_with_direct_algo = plonk::iter_evaluate_witness::<Field>(&S, &traces[0])
-                    .enumerate()
-                    .map(|(index, f_i)| {
-                        pow_i(
-                            index,
-                            ctx.count_of_evaluation_with_padding,
-                            challenge_vector.iter(),
-                        ) * f_i.unwrap()
-                    })
-                    .sum();
+    #[test]
+    fn try_new_preconditions() {
+        let S = square_gate_structure::<Field>();
+        assert!(PolyContext::try_new(&S, &[EmptyTrace, EmptyTrace, EmptyTrace]).is_ok());
+
+        // Three instances are padded to the domain of four
+        let ctx = PolyContext::try_new(&S, &[EmptyTrace, EmptyTrace]).unwrap();
+        assert_eq!(ctx.lagrange_domain(), 2);
+        assert_eq!(ctx.fft_points_count_G, 8);
+
+        let S = PlonkStructure::<Field> {
+            k: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            PolyContext::try_new(&S, &[EmptyTrace]).err(),
+            Some(PolyContextError::EmptyGates)
+        );
 
-                assert_eq!(
-                    evaluated_poly_F.eval(X),
-                    result_with_direct_algo,
-                    "not match for {X:?}"
-                );
+        // Two-adicity of the bn256 base field is 1, but `G(X)` of a degree 2 gate needs 4 points
+        let S = square_gate_structure::<bn256::Fq>();
+        assert_eq!(
+            PolyContext::try_new(&S, &[EmptyTrace]).err(),
+            Some(PolyContextError::FftDomainTooLarge {
+                log_domain: 2,
+                two_adicity: 1
             })
+        );
+    }
+
+    #[test]
+    fn from_structure_matches_try_new() {
+        let (S, _trace) = poseidon_trace();
+
+        let sizes = |ctx: PolyContext<Field>| {
+            (
+                ctx.instances_to_fold(),
+                ctx.count_of_evaluation_with_padding(),
+                ctx.betas_count(),
+                ctx.fft_points_count_F(),
+                ctx.fft_points_count_G,
+                ctx.fft_log_domain_size_G(),
+                ctx.lagrange_domain(),
+            )
+        };
+
+        for traces_count in 1..=4 {
+            let traces = iter::repeat_with(|| EmptyTrace)
+                .take(traces_count)
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                PolyContext::from_structure(&S, traces_count + 1).map(sizes),
+                PolyContext::try_new(&S, &traces).map(sizes),
+                "not match for {traces_count} traces"
+            );
+        }
     }
 
     #[traced_test]
@@ -623,7 +1152,7 @@ mod test {
         .take(3)
         .collect::<Box<[_]>>();
 
-        let ctx = PolyContext::new(&S, &traces);
+        let ctx = PolyContext::try_new(&S, &traces).unwrap();
 
         let beta_stroke = gen.by_ref().take(ctx.betas_count()).collect::<Box<[_]>>();
 
@@ -667,90 +1196,575 @@ mod test {
         });
     }
 
-    pub fn vanish_poly<F: PrimeField>(degree: usize) -> UnivariatePoly<F> {
-        let mut coeff = vec![F::ZERO; degree].into_boxed_slice();
-        coeff[0] = -F::ONE;
-        coeff[degree - 1] = F::ONE;
-        UnivariatePoly(coeff)
+    #[traced_test]
+    #[test]
+    fn points_count_of_F() {
+        let mut rnd = rand::thread_rng();
+
+        // `betas_count + 1` is a power of two for `k = 3` & isn't for `k = 4`
+        for (k, fft_points_count_F) in [(3, 4), (4, 8)] {
+            let S = PlonkStructure::<Field> {
+                k,
+                gates: vec![Expression::Constant(Field::ONE)],
+                ..Default::default()
+            };
+            let trace = PlonkTrace::<Curve>::new(plonk::PlonkTraceArgs::from(&S));
+
+            let traces = [trace.clone()];
+            let ctx = PolyContext::try_new(&S, &traces).unwrap();
+            assert_eq!(ctx.betas_count(), k);
+            assert_eq!(ctx.fft_points_count_F(), fft_points_count_F);
+
+            let delta = Field::random(&mut rnd);
+            let betas = iter::repeat_with(|| Field::random(&mut rnd))
+                .take(ctx.betas_count())
+                .collect::<Box<[_]>>();
+
+            let poly_F = super::compute_F(&ctx, betas.iter().copied(), delta, &trace).unwrap();
+            assert_eq!(poly_F.len(), fft_points_count_F);
+
+            // Each gate is equal to one, so `F(X) = sum(pow_i(betas + X * deltas))`
+            let X = Field::random(&mut rnd);
+            let challenge_vector = betas
+                .iter()
+                .zip(iter::successors(Some(delta), |d| Some(d.pow([2]))))
+                .map(|(beta, delta)| beta + (X * delta))
+                .collect::<Box<[_]>>();
+            let expected = (0..1 << k)
+                .map(|index| pow_i(index, 1 << k, challenge_vector.iter()))
+                .sum::<Field>();
+
+            assert_eq!(poly_F.eval(X), expected);
+        }
     }
 
     #[traced_test]
     #[test]
-    fn zero_f() {
-        debug!("start");
-        let (S, trace) = poseidon_trace();
-        debug!("trace ready");
+    fn compute_F_stops_on_error() {
+        let (S, mut trace) = poseidon_trace();
         let mut rnd = rand::thread_rng();
 
-        let delta = Field::random(&mut rnd);
+        // Each gate evaluation with advice query will fail
+        trace.w.W.clear();
 
-        let traces = [trace];
+        let traces = [trace.clone()];
+        let ctx = PolyContext::try_new(&S, &traces).unwrap();
 
-        debug!("start compute F");
-        assert!(super::compute_F(
-            &super::PolyContext::new(&S, &traces),
-            iter::repeat_with(move || Field::random(&mut rnd)),
-            delta,
-            &traces[0],
-        )
-        .unwrap()
-        .iter()
-        .all(|f| f.is_zero().into()));
+        assert!(matches!(
+            super::compute_F(
+                &ctx,
+                iter::repeat_with(|| Field::random(&mut rnd)),
+                Field::random(&mut rnd),
+                &trace,
+            ),
+            Err(super::Error::Eval(_))
+        ));
     }
 
     #[traced_test]
     #[test]
-    fn non_zero_f() {
+    fn compute_G_stops_on_error() {
         let (S, mut trace) = poseidon_trace();
         let mut rnd = rand::thread_rng();
-        trace
-            .w
-            .W
-            .iter_mut()
-            .for_each(|row| row.iter_mut().for_each(|el| *el = Field::random(&mut rnd)));
 
-        let delta = Field::random(&mut rnd);
+        // Folded witnesses are empty too, so each gate evaluation with advice query will fail
+        trace.w.W.clear();
 
-        let traces = [trace];
+        let traces = [trace.clone()];
+        let ctx = PolyContext::try_new(&S, &traces).unwrap();
 
-        assert_ne!(
-            super::compute_F(
-                &super::PolyContext::new(&S, &traces),
+        assert!(matches!(
+            super::compute_G(
+                &ctx,
                 iter::repeat_with(|| Field::random(&mut rnd)),
-                delta,
-                &traces[0],
+                &trace,
+                &traces,
             ),
-            Ok(UnivariatePoly::from_iter(
-                iter::repeat(Field::ZERO).take(16)
-            ))
-        );
+            Err(super::Error::Eval(_))
+        ));
     }
 
     #[traced_test]
     #[test]
-    fn zero_g() {
+    fn parallel_is_bit_identical_to_serial() {
         let (S, trace) = poseidon_trace();
         let mut rnd = rand::thread_rng();
 
+        let mut accumulator = trace.clone();
+        accumulator
+            .w
+            .W
+            .iter_mut()
+            .flatten()
+            .for_each(|el| *el = Field::random(&mut rnd));
         let traces = [trace];
-        assert!(super::compute_G(
-            &super::PolyContext::new(&S, &traces),
-            iter::repeat_with(|| Field::random(&mut rnd)),
-            &traces[0].clone(),
-            &traces
-        )
-        .unwrap()
-        .iter()
-        .all(|f| f.is_zero().into()));
+
+        let delta = Field::random(&mut rnd);
+        let betas = iter::repeat_with(|| Field::random(&mut rnd))
+            .take(PolyContext::try_new(&S, &traces).unwrap().betas_count())
+            .collect::<Box<[_]>>();
+
+        let [(single_F, single_G), (multi_F, multi_G)] = [1, 8].map(|parallelism| {
+            let mut ctx = PolyContext::try_new(&S, &traces).unwrap();
+            ctx.set_parallelism(parallelism);
+            #[cfg(feature = "parallel")]
+            assert_eq!(
+                ctx.chunks_count(ctx.count_of_evaluation_with_padding),
+                parallelism
+            );
+
+            (
+                super::compute_F(&ctx, betas.iter().copied(), delta, &accumulator).unwrap(),
+                super::compute_G(&ctx, betas.iter().copied(), &accumulator, &traces).unwrap(),
+            )
+        });
+
+        assert_eq!(single_F, multi_F);
+        assert_eq!(single_G, multi_G);
     }
 
+    /// Cross-check of the two tree algorithms
+    ///
+    /// With `alpha = 0` the betas are not moved, `β* = β + 0·δ = β`. When the only incoming trace
+    /// is the accumulator itself, every folded witness is the accumulator (Lagrange polynomials
+    /// sum up to one), so `G(X)` is the constant `Σ pow_i(β) f_i(acc) = F(0)`
     #[traced_test]
     #[test]
-    fn non_zero_g() {
-        let (S, mut trace) = poseidon_trace();
+    fn cmp_F_and_G_with_zero_alpha() {
+        let (S, mut accumulator) = poseidon_trace();
         let mut rnd = rand::thread_rng();
-        trace
-            .w
+        let mut gen = iter::repeat_with(|| Field::random(&mut rnd));
+
+        accumulator
+            .w
+            .W
+            .iter_mut()
+            .flatten()
+            .for_each(|el| *el = gen.by_ref().next().unwrap());
+        let traces = [accumulator.clone()];
+
+        let ctx = PolyContext::try_new(&S, &traces).unwrap();
+
+        let delta = gen.by_ref().next().unwrap();
+        let betas = gen.by_ref().take(ctx.betas_count()).collect::<Box<[_]>>();
+
+        let betas_stroke = PolyChallenges {
+            betas: betas.clone(),
+            alpha: Field::ZERO,
+            delta,
+        }
+        .iter_beta_stroke()
+        .collect::<Box<[_]>>();
+        assert_eq!(betas_stroke, betas);
+
+        let poly_F = super::compute_F(&ctx, betas.iter().copied(), delta, &accumulator).unwrap();
+        let poly_G =
+            super::compute_G(&ctx, betas_stroke.iter().copied(), &accumulator, &traces).unwrap();
+
+        let F_at_zero = poly_F.eval(Field::ZERO);
+        assert_ne!(
+            F_at_zero,
+            Field::ZERO,
+            "random witness must not satisfy gates"
+        );
+
+        assert_eq!(poly_G.eval(Field::ZERO), F_at_zero);
+        gen.take(10)
+            .for_each(|X| assert_eq!(poly_G.eval(X), F_at_zero, "not match for {X:?}"));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn folded_witness_par_iter() {
+        use crate::util::parallel::ParallelIterator;
+
+        let (_S, trace) = poseidon_trace();
+        let mut rnd = rand::thread_rng();
+
+        let [accumulator, traces @ ..] = array::from_fn::<_, 5, _>(|_| {
+            let mut trace = trace.clone();
+            trace
+                .w
+                .W
+                .iter_mut()
+                .flatten()
+                .for_each(|el| *el = Field::random(&mut rnd));
+            trace
+        });
+
+        // Four incoming traces & the accumulator are padded to the domain of eight
+        let lagrange_domain = 3;
+        let points = lagrange::iter_cyclic_subgroup::<Field>(4).collect::<Box<[_]>>();
+
+        let folded = |folded: &FoldedWitness<Field>| {
+            (
+                folded.get_witness().to_vec(),
+                folded.get_challenges().to_vec(),
+            )
+        };
+
+        let sequential = FoldedWitness::iter(&points, lagrange_domain, &accumulator, &traces)
+            .map(|witness| folded(&witness))
+            .collect::<Vec<_>>();
+        let parallel = FoldedWitness::par_iter(&points, lagrange_domain, &accumulator, &traces)
+            .map(|witness| folded(&witness))
+            .collect::<Vec<_>>();
+        let eager = FoldedWitness::new(&points, lagrange_domain, &accumulator, &traces)
+            .iter()
+            .map(folded)
+            .collect::<Vec<_>>();
+
+        assert_eq!(parallel, sequential);
+        assert_eq!(sequential, eager);
+    }
+
+    /// Padding of the lagrange domain in [`PolyContext::try_new`] is the same as explicit zero
+    /// traces
+    /// Gates of fibonacci circuit are homogeneous, so they vanish on the zero trace
+    fn fibo_trace() -> (PlonkStructure<Field>, PlonkTrace<Curve>) {
+        const SIZE: usize = 16;
+        let seq = get_fibo_seq(1, 1, SIZE);
+
+        get_trace(
+            10,
+            FiboCircuit {
+                a: Field::from(seq[0]),
+                b: Field::from(seq[1]),
+                num: SIZE,
+            },
+            vec![vec![Field::from(seq[SIZE - 1])]],
+        )
+    }
+
+    #[test]
+    fn padding_requires_vanishing_gates() {
+        // `1 = 0` at every row
+        let S = PlonkStructure::<Field> {
+            k: 1,
+            gates: vec![Expression::Constant(Field::ONE)],
+            ..Default::default()
+        };
+        assert_eq!(
+            PolyContext::try_new(&S, &[EmptyTrace, EmptyTrace]).err(),
+            Some(PolyContextError::ZeroTraceNotSatisfying { gate: 0, row: 0 })
+        );
+        // No padding, so zero trace isn't used
+        assert!(PolyContext::try_new(&S, &[EmptyTrace]).is_ok());
+        assert!(PolyContext::try_new(&S, &[EmptyTrace, EmptyTrace, EmptyTrace]).is_ok());
+
+        // `c * c + 1 = 0`
+        let challenge = || Box::new(Expression::Challenge(0));
+        let S = PlonkStructure::<Field> {
+            k: 1,
+            num_challenges: 1,
+            gates: vec![
+                Expression::Product(challenge(), challenge()),
+                Expression::Sum(
+                    Box::new(Expression::Product(challenge(), challenge())),
+                    Box::new(Expression::Constant(Field::ONE)),
+                ),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(S.zero_trace_violation(), Ok(Some((1, 0))));
+        assert_eq!(
+            PolyContext::from_structure(&S, 3).err(),
+            Some(PolyContextError::ZeroTraceNotSatisfying { gate: 1, row: 0 })
+        );
+
+        // Round constants of poseidon are fixed columns, so its gates don't vanish
+        let (S, trace) = poseidon_trace();
+        assert!(matches!(
+            PolyContext::try_new(&S, &[trace.clone(), trace]),
+            Err(PolyContextError::ZeroTraceNotSatisfying { .. })
+        ));
+
+        let (S, trace) = fibo_trace();
+        assert_eq!(S.zero_trace_violation(), Ok(None));
+        assert!(PolyContext::try_new(&S, &[trace.clone(), trace]).is_ok());
+    }
+
+    #[traced_test]
+    #[test]
+    fn implicit_padding_matches_zero_trace() {
+        let (S, trace) = fibo_trace();
+        let mut rnd = rand::thread_rng();
+
+        let [accumulator, traces @ ..] = array::from_fn::<_, 3, _>(|_| {
+            let mut trace = trace.clone();
+            trace
+                .w
+                .W
+                .iter_mut()
+                .flatten()
+                .for_each(|el| *el = Field::random(&mut rnd));
+            trace
+        });
+
+        let mut zero = trace.clone();
+        zero.w
+            .W
+            .iter_mut()
+            .flatten()
+            .for_each(|el| *el = Field::ZERO);
+        zero.u
+            .challenges
+            .iter_mut()
+            .for_each(|cha| *cha = Field::ZERO);
+        let padded = [traces[0].clone(), traces[1].clone(), zero];
+
+        // Accumulator & two traces are padded to the domain of four
+        let implicit = PolyContext::try_new(&S, &traces).unwrap();
+        let explicit = PolyContext::try_new(&S, &padded).unwrap();
+        assert_eq!(implicit.lagrange_domain(), explicit.lagrange_domain());
+
+        let betas = iter::repeat_with(|| Field::random(&mut rnd))
+            .take(implicit.betas_count())
+            .collect::<Box<[_]>>();
+        let poly_F_in_alpha = Field::random(&mut rnd);
+
+        assert_eq!(
+            super::compute_G(&implicit, betas.iter().copied(), &accumulator, &traces).unwrap(),
+            super::compute_G(&explicit, betas.iter().copied(), &accumulator, &padded).unwrap(),
+        );
+        assert_eq!(
+            super::compute_K(
+                &implicit,
+                poly_F_in_alpha,
+                betas.iter().copied(),
+                &accumulator,
+                &traces
+            )
+            .unwrap(),
+            super::compute_K(
+                &explicit,
+                poly_F_in_alpha,
+                betas.iter().copied(),
+                &accumulator,
+                &padded
+            )
+            .unwrap(),
+        );
+    }
+
+    #[traced_test]
+    #[test]
+    fn progress_reports_all_leaves() {
+        use std::sync::Mutex;
+
+        use crate::nifs::protogalaxy::{memory::Phase, progress::ProgressEvent};
+
+        let (S, trace) = poseidon_trace();
+        let traces = [trace.clone(), trace.clone(), trace.clone()];
+        let mut rnd = rand::thread_rng();
+
+        let events = Mutex::new(Vec::new());
+        let record = |event: ProgressEvent| events.lock().unwrap().push(event);
+
+        let mut ctx = PolyContext::try_new(&S, &traces)
+            .unwrap()
+            .with_progress(Some(&record));
+        // Several tasks report leaves & merges concurrently
+        ctx.set_parallelism(4);
+
+        let betas = iter::repeat_with(|| Field::random(&mut rnd))
+            .take(ctx.betas_count())
+            .collect::<Box<[_]>>();
+
+        super::compute_F(&ctx, betas.iter().copied(), Field::random(&mut rnd), &trace).unwrap();
+        super::compute_G(&ctx, betas.iter().copied(), &trace, &traces).unwrap();
+
+        let total = ctx.count_of_evaluation_with_padding();
+        drop(ctx);
+        let events = events.into_inner().unwrap();
+
+        for phase in [Phase::ComputeF, Phase::ComputeG] {
+            let (leaves, completed) = events
+                .iter()
+                .filter_map(|event| match event {
+                    ProgressEvent::Leaves {
+                        phase: event_phase,
+                        count,
+                        completed,
+                        total: event_total,
+                    } if *event_phase == phase => {
+                        assert_eq!(*event_total, total);
+                        Some((*count, *completed))
+                    }
+                    _ => None,
+                })
+                .fold((0, 0), |(leaves, max), (count, completed)| {
+                    (leaves + count, max.max(completed))
+                });
+            assert_eq!(leaves, total, "{phase:?}");
+            assert_eq!(completed, total, "{phase:?}");
+
+            let mut heights = events
+                .iter()
+                .filter_map(|event| match event {
+                    ProgressEvent::Level {
+                        phase: event_phase,
+                        height,
+                        ..
+                    } if *event_phase == phase => Some(*height),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            heights.sort();
+            assert_eq!(
+                heights,
+                (1..=total.ilog2() as usize).collect::<Vec<_>>(),
+                "{phase:?}"
+            );
+
+            assert!(events.contains(&ProgressEvent::Done { phase }));
+        }
+    }
+
+    #[test]
+    fn beta_stroke_iter_len_and_rev() {
+        let mut rnd = rand::thread_rng();
+        let cha = PolyChallenges {
+            betas: iter::repeat_with(|| Field::random(&mut rnd))
+                .take(6)
+                .collect(),
+            alpha: Field::random(&mut rnd),
+            delta: Field::random(&mut rnd),
+        };
+
+        let forward = cha.clone().iter_beta_stroke().collect::<Vec<Field>>();
+
+        let mut betas_stroke = cha.clone().iter_beta_stroke();
+        for len in (0..=forward.len()).rev() {
+            assert_eq!(betas_stroke.len(), len);
+            betas_stroke.next();
+        }
+
+        assert_eq!(
+            cha.clone().iter_beta_stroke().rev().collect::<Vec<_>>(),
+            forward.iter().rev().copied().collect::<Vec<_>>()
+        );
+
+        // Both ends meet in the middle
+        let mut betas_stroke = cha.iter_beta_stroke();
+        assert_eq!(betas_stroke.next(), Some(forward[0]));
+        assert_eq!(betas_stroke.next_back(), Some(forward[5]));
+        assert_eq!(betas_stroke.next(), Some(forward[1]));
+        assert_eq!(betas_stroke.len(), 3);
+        assert_eq!(
+            betas_stroke.rev().collect::<Vec<_>>(),
+            [forward[4], forward[3], forward[2]]
+        );
+    }
+
+    #[test]
+    fn weighted_folded_witness() {
+        let mut rnd = rand::thread_rng();
+        let mut gen = iter::repeat_with(|| Field::random(&mut rnd));
+
+        let [accumulator, traces @ ..] = array::from_fn::<_, 4, _>(|_| PlonkTrace::<Curve> {
+            u: PlonkInstance {
+                W_commitments: vec![],
+                instances: vec![],
+                challenges: gen.by_ref().take(2).collect(),
+            },
+            w: PlonkWitness {
+                W: vec![
+                    gen.by_ref().take(4).collect(),
+                    gen.by_ref().take(4).collect(),
+                ],
+            },
+        });
+        let weights = [2, 3, 5].map(Field::from);
+
+        let lagrange_domain = 2;
+        let points = gen.take(5).collect::<Box<[_]>>();
+
+        let weighted =
+            FoldedWitness::new_weighted(&points, lagrange_domain, &accumulator, &traces, &weights);
+
+        for (X, folded) in points.iter().zip(weighted.iter()) {
+            let coeffs = lagrange::iter_eval_lagrange_poly_for_cyclic_group(*X, lagrange_domain)
+                .zip(iter::once(Field::ONE).chain(weights))
+                .map(|(poly_L_in_X, weight)| poly_L_in_X * weight)
+                .collect::<Box<[_]>>();
+            let all_traces = || {
+                iter::once(&accumulator)
+                    .chain(traces.iter())
+                    .zip(coeffs.iter())
+            };
+
+            let expected_W = (0..2)
+                .map(|col| {
+                    (0..4)
+                        .map(|row| all_traces().map(|(tr, c)| tr.w.W[col][row] * c).sum())
+                        .collect::<Vec<Field>>()
+                })
+                .collect::<Vec<_>>();
+            let expected_challenges = (0..2)
+                .map(|i| all_traces().map(|(tr, c)| tr.u.challenges[i] * c).sum())
+                .collect::<Vec<Field>>();
+
+            assert_eq!(folded.get_witness(), expected_W);
+            assert_eq!(folded.get_challenges(), expected_challenges);
+        }
+
+        let unweighted = FoldedWitness::new(&points, lagrange_domain, &accumulator, &traces);
+        let with_ones = FoldedWitness::new_weighted(
+            &points,
+            lagrange_domain,
+            &accumulator,
+            &traces,
+            &[Field::ONE; 3],
+        );
+        for (unweighted, with_ones) in unweighted.iter().zip(with_ones.iter()) {
+            assert_eq!(unweighted.get_witness(), with_ones.get_witness());
+            assert_eq!(unweighted.get_challenges(), with_ones.get_challenges());
+        }
+    }
+
+    pub fn vanish_poly<F: PrimeField>(degree: usize) -> UnivariatePoly<F> {
+        let mut coeff = vec![F::ZERO; degree].into_boxed_slice();
+        coeff[0] = -F::ONE;
+        coeff[degree - 1] = F::ONE;
+        UnivariatePoly(coeff)
+    }
+
+    #[traced_test]
+    #[test]
+    fn non_zero_f() {
+        let (S, mut trace) = poseidon_trace();
+        let mut rnd = rand::thread_rng();
+        trace
+            .w
+            .W
+            .iter_mut()
+            .for_each(|row| row.iter_mut().for_each(|el| *el = Field::random(&mut rnd)));
+
+        let delta = Field::random(&mut rnd);
+
+        let traces = [trace];
+
+        assert_ne!(
+            super::compute_F(
+                &super::PolyContext::try_new(&S, &traces).unwrap(),
+                iter::repeat_with(|| Field::random(&mut rnd)),
+                delta,
+                &traces[0],
+            ),
+            Ok(UnivariatePoly::from_iter(
+                iter::repeat(Field::ZERO).take(16)
+            ))
+        );
+    }
+
+    #[traced_test]
+    #[test]
+    fn non_zero_g() {
+        let (S, mut trace) = poseidon_trace();
+        let mut rnd = rand::thread_rng();
+        trace
+            .w
             .W
             .iter_mut()
             .for_each(|row| row.iter_mut().for_each(|el| *el = Field::random(&mut rnd)));
@@ -758,7 +1772,7 @@ mod test {
         let traces = [trace];
         assert_ne!(
             super::compute_G(
-                &super::PolyContext::new(&S, &traces),
+                &super::PolyContext::try_new(&S, &traces).unwrap(),
                 iter::repeat_with(|| Field::random(&mut rnd)),
                 &traces[0].clone(),
                 &traces
@@ -768,4 +1782,395 @@ mod test {
             ))
         );
     }
+
+    #[traced_test]
+    #[test]
+    fn cmp_K_coset_with_division() {
+        let (S, trace) = poseidon_trace();
+        let mut rnd = rand::thread_rng();
+
+        let mut accumulator = trace.clone();
+        accumulator
+            .w
+            .W
+            .iter_mut()
+            .for_each(|row| row.iter_mut().for_each(|el| *el = Field::random(&mut rnd)));
+
+        let traces = [trace];
+        let ctx = PolyContext::try_new(&S, &traces).unwrap();
+
+        let poly_G = super::compute_G(
+            &ctx,
+            iter::repeat_with(|| Field::random(&mut rnd)),
+            &accumulator,
+            &traces,
+        )
+        .unwrap();
+
+        // G(1) = F(alpha), since L0(1) = 1 & Z(1) = 0
+        let poly_F_in_alpha = poly_G.eval(Field::ONE);
+
+        let poly_K = super::compute_K_from_G(&ctx, poly_G.clone(), poly_F_in_alpha);
+        let poly_K_by_division =
+            super::compute_K_from_G_by_division(&ctx, &poly_G, poly_F_in_alpha).unwrap();
+
+        assert_eq!(poly_K_by_division.pad_with_zeroes(poly_K.len()), Ok(poly_K));
+    }
+
+    /// The coset FFT path must give the same `K(X)` as the evaluation of `G(X)` in each point
+    #[traced_test]
+    #[test]
+    fn cmp_K_coset_with_eval() {
+        let (S, trace) = poseidon_trace();
+        let mut rnd = rand::thread_rng();
+
+        let traces = [trace.clone(), trace.clone(), trace];
+        let ctx = PolyContext::try_new(&S, &traces).unwrap();
+        assert!(!ctx.is_k_domain_trivial());
+
+        let poly_G = UnivariatePoly::from_iter(
+            iter::repeat_with(|| Field::random(&mut rnd)).take(ctx.fft_points_count_G),
+        );
+        let poly_F_in_alpha = Field::random(&mut rnd);
+
+        assert_eq!(
+            super::compute_K_from_G(&ctx, poly_G.clone(), poly_F_in_alpha),
+            compute_K_by_eval(&ctx, &poly_G, poly_F_in_alpha)
+        );
+    }
+
+    /// Same as [`cmp_K_coset_with_eval`], but with `G(X)` of the folded traces
+    #[traced_test]
+    #[test]
+    fn cmp_K_coset_with_eval_of_traces() {
+        let (S, trace) = poseidon_trace();
+        let mut rnd = rand::thread_rng();
+
+        let mut accumulator = trace.clone();
+        accumulator
+            .w
+            .W
+            .iter_mut()
+            .for_each(|row| row.iter_mut().for_each(|el| *el = Field::random(&mut rnd)));
+
+        let traces = [trace.clone(), trace.clone(), trace];
+        let ctx = PolyContext::try_new(&S, &traces).unwrap();
+        assert!(!ctx.is_k_domain_trivial());
+
+        let betas = iter::repeat_with(|| Field::random(&mut rnd))
+            .take(ctx.betas_count())
+            .collect::<Box<[_]>>();
+        let poly_G = super::compute_G(&ctx, betas.iter().copied(), &accumulator, &traces).unwrap();
+        let poly_F_in_alpha = Field::random(&mut rnd);
+
+        assert_eq!(
+            super::compute_K_from_G(&ctx, poly_G.clone(), poly_F_in_alpha),
+            compute_K_by_eval(&ctx, &poly_G, poly_F_in_alpha)
+        );
+    }
+
+    /// Reference `K(X)`: evaluation of `G(X)` in each point of the coset, one by one
+    fn compute_K_by_eval(
+        ctx: &PolyContext<Field>,
+        poly_G: &UnivariatePoly<Field>,
+        poly_F_in_alpha: Field,
+    ) -> UnivariatePoly<Field> {
+        let mut points = lagrange::iter_cyclic_subgroup::<Field>(ctx.fft_log_domain_size_K())
+            .map(|X| Field::ZETA * X)
+            .map(|X| {
+                let poly_L0_in_X =
+                    lagrange::iter_eval_lagrange_poly_for_cyclic_group(X, ctx.lagrange_domain())
+                        .next()
+                        .unwrap();
+                let poly_Z_in_X = lagrange::eval_vanish_polynomial(ctx.instances_to_fold, X);
+
+                (poly_G.eval(X) - poly_F_in_alpha * poly_L0_in_X) * poly_Z_in_X.invert().unwrap()
+            })
+            .collect::<Box<[_]>>();
+        ctx.coset_ifft(&mut points);
+
+        UnivariatePoly(points)
+    }
+
+    /// The per-point identity holds for any `G(X)` by construction of `K(X)`, so a tampered
+    /// `G(X)` is caught only by the debug cross-check with the exact division
+    #[cfg(debug_assertions)]
+    #[traced_test]
+    #[test]
+    fn tampered_G() {
+        let (S, trace) = poseidon_trace();
+        let mut rnd = rand::thread_rng();
+
+        let traces = [trace.clone()];
+        let ctx = PolyContext::try_new(&S, &traces).unwrap();
+
+        let mut poly_G = super::compute_G(
+            &ctx,
+            iter::repeat_with(|| Field::random(&mut rnd)),
+            &trace,
+            &traces,
+        )
+        .unwrap();
+        let poly_F_in_alpha = poly_G.eval(Field::ONE);
+
+        poly_G.0[0] += Field::ONE;
+
+        assert!(super::compute_K_from_G_by_division(&ctx, &poly_G, poly_F_in_alpha).is_err());
+
+        let poly_K = super::compute_K_from_G(&ctx, poly_G.clone(), poly_F_in_alpha);
+        assert!(logs_contain("is not divisible by Z(X)"));
+
+        let X = Field::random(&mut rnd);
+        let poly_L0_in_X =
+            lagrange::iter_eval_lagrange_poly_for_cyclic_group(X, ctx.lagrange_domain())
+                .next()
+                .unwrap();
+        let poly_Z_in_X = lagrange::eval_vanish_polynomial(ctx.instances_to_fold, X);
+
+        assert_ne!(
+            poly_F_in_alpha * poly_L0_in_X + poly_Z_in_X * poly_K.eval(X),
+            poly_G.eval(X)
+        );
+    }
+
+    #[traced_test]
+    #[test]
+    fn trivial_K_domain() {
+        let (S, trace) = fibo_trace();
+        let mut rnd = rand::thread_rng();
+
+        // Gates of fibonacci circuit are at most quadratic, so with one incoming trace
+        // `deg(G) <= 2 = n`
+        let ctx = PolyContext::try_new(&S, &[trace]).unwrap();
+        assert_eq!(ctx.instances_to_fold(), 2);
+        assert!(ctx.is_k_domain_trivial());
+
+        let poly_F_in_alpha = Field::random(&mut rnd);
+        let poly_K_const = Field::random(&mut rnd);
+
+        // G(X) = F(alpha) * L0(X) + Z(X) * K, where L0(X) = (1 + X) / 2, Z(X) = X^2 - 1
+        let half = Field::from(2).invert().unwrap();
+        let poly_G = UnivariatePoly(Box::new([
+            poly_F_in_alpha * half - poly_K_const,
+            poly_F_in_alpha * half,
+            poly_K_const,
+        ]));
+
+        assert_eq!(
+            super::compute_K_from_G(&ctx, poly_G, poly_F_in_alpha),
+            UnivariatePoly(Box::new([poly_K_const]))
+        );
+    }
+
+    #[traced_test]
+    #[test]
+    fn custom_fft_backend() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use crate::fft::{DefaultFftBackend, FftBackend};
+
+        /// Delegates to [`DefaultFftBackend`] & counts calls
+        #[derive(Default)]
+        struct Counting(AtomicUsize);
+
+        impl FftBackend<Field> for Counting {
+            fn fft(&self, a: &mut [Field]) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                DefaultFftBackend.fft(a)
+            }
+
+            fn ifft(&self, a: &mut [Field]) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                DefaultFftBackend.ifft(a)
+            }
+
+            fn coset_ifft(&self, a: &mut [Field]) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                DefaultFftBackend.coset_ifft(a)
+            }
+        }
+
+        let (S, trace) = poseidon_trace();
+        let mut rnd = rand::thread_rng();
+        let mut gen = iter::repeat_with(|| Field::random(&mut rnd));
+
+        let mut incoming = trace.clone();
+        incoming
+            .w
+            .W
+            .iter_mut()
+            .for_each(|row| row.iter_mut().zip(gen.by_ref()).for_each(|(v, r)| *v = r));
+        let traces = [incoming];
+        let accumulator = trace;
+
+        let backend = Counting::default();
+        let default_ctx = PolyContext::try_new(&S, &traces).unwrap();
+        let custom_ctx = PolyContext::try_new(&S, &traces)
+            .unwrap()
+            .with_fft_backend(Some(&backend));
+
+        let delta = gen.by_ref().next().unwrap();
+        let alpha = gen.by_ref().next().unwrap();
+        let betas = gen
+            .by_ref()
+            .take(default_ctx.betas_count())
+            .collect::<Box<[_]>>();
+
+        let compute_F = |ctx: &PolyContext<Field>| {
+            super::compute_F(ctx, betas.iter().copied(), delta, &accumulator).unwrap()
+        };
+        let compute_K = |ctx: &PolyContext<Field>| {
+            super::compute_K(ctx, alpha, betas.iter().copied(), &accumulator, &traces).unwrap()
+        };
+
+        assert_eq!(compute_F(&default_ctx), compute_F(&custom_ctx));
+        assert_eq!(compute_K(&default_ctx), compute_K(&custom_ctx));
+        assert!(backend.0.load(Ordering::Relaxed) >= 3);
+    }
+
+    /// Checks over any curve, instantiated for each of them by [`crate::curve_tests`]
+    ///
+    /// Domains of FFTs depend only on [`PrimeField::S`] of the scalar field, which is 28 for
+    /// bn256 & 32 for pasta (as for bls12-381), so nothing here is specific to bn256
+    mod generic_curve {
+        use std::iter;
+
+        use halo2_proofs::halo2curves::ff::{FromUniformBytes, PrimeFieldBits};
+
+        use super::{poseidon_circuit, pow_i, R_F1, R_P1};
+        use crate::{
+            commitment::CommitmentKey,
+            ff::Field,
+            halo2curves::CurveAffine,
+            nifs::protogalaxy::poly::{compute_F, compute_G, PolyContext},
+            plonk::{self, PlonkStructure, PlonkTrace},
+            polynomial::lagrange,
+            poseidon::{PoseidonHash, ROTrait, Spec},
+            table::CircuitRunner,
+        };
+
+        fn poseidon_trace<C>() -> (PlonkStructure<C::ScalarExt>, PlonkTrace<C>)
+        where
+            C: CurveAffine,
+            C::ScalarExt: PrimeFieldBits + FromUniformBytes<64>,
+            C::Base: PrimeFieldBits + FromUniformBytes<64>,
+        {
+            let runner = CircuitRunner::<C::ScalarExt, _>::new(
+                13,
+                poseidon_circuit::TestPoseidonCircuit::<_>::default(),
+                vec![],
+            );
+
+            let S = runner.try_collect_plonk_structure().unwrap();
+            let witness = runner.try_collect_witness().unwrap();
+
+            let trace = S
+                .run_sps_protocol(
+                    &CommitmentKey::<C>::setup(18, b""),
+                    &[vec![C::ScalarExt::from(4097)]],
+                    &witness,
+                    &mut PoseidonHash::<C::Base, 3, 2>::new(Spec::new(R_F1, R_P1)),
+                )
+                .unwrap();
+
+            (S, trace)
+        }
+
+        fn zero_f<C>()
+        where
+            C: CurveAffine,
+            C::ScalarExt: PrimeFieldBits + FromUniformBytes<64>,
+            C::Base: PrimeFieldBits + FromUniformBytes<64>,
+        {
+            let (S, trace) = poseidon_trace::<C>();
+            let mut rnd = rand::thread_rng();
+
+            let traces = [trace];
+            let delta = C::ScalarExt::random(&mut rnd);
+
+            assert!(compute_F(
+                &PolyContext::try_new(&S, &traces).unwrap(),
+                iter::repeat_with(|| C::ScalarExt::random(&mut rnd)),
+                delta,
+                &traces[0],
+            )
+            .unwrap()
+            .iter()
+            .all(|f| f.is_zero().into()));
+        }
+
+        fn zero_g<C>()
+        where
+            C: CurveAffine,
+            C::ScalarExt: PrimeFieldBits + FromUniformBytes<64>,
+            C::Base: PrimeFieldBits + FromUniformBytes<64>,
+        {
+            let (S, trace) = poseidon_trace::<C>();
+            let mut rnd = rand::thread_rng();
+
+            let traces = [trace];
+            assert!(compute_G(
+                &PolyContext::try_new(&S, &traces).unwrap(),
+                iter::repeat_with(|| C::ScalarExt::random(&mut rnd)),
+                &traces[0].clone(),
+                &traces
+            )
+            .unwrap()
+            .iter()
+            .all(|f| f.is_zero().into()));
+        }
+
+        fn cmp_with_direct_eval_of_F<C>()
+        where
+            C: CurveAffine,
+            C::ScalarExt: PrimeFieldBits + FromUniformBytes<64>,
+            C::Base: PrimeFieldBits + FromUniformBytes<64>,
+        {
+            let (S, mut trace) = poseidon_trace::<C>();
+            let mut rnd = rand::thread_rng();
+            let mut gen = iter::repeat_with(|| C::ScalarExt::random(&mut rnd));
+
+            trace.w.W.iter_mut().for_each(|row| {
+                row.iter_mut()
+                    .for_each(|v| *v = gen.by_ref().next().unwrap())
+            });
+
+            let traces = [trace];
+            let ctx = PolyContext::try_new(&S, &traces).unwrap();
+
+            let delta = gen.by_ref().next().unwrap();
+            let betas = gen.by_ref().take(ctx.betas_count()).collect::<Box<[_]>>();
+
+            let poly_F = compute_F(&ctx, betas.iter().copied(), delta, &traces[0]).unwrap();
+
+            lagrange::iter_cyclic_subgroup::<C::ScalarExt>(ctx.fft_points_count_F().ilog2())
+                .chain(gen.take(10))
+                .for_each(|X| {
+                    let challenge_vector = betas
+                        .iter()
+                        .zip(iter::successors(Some(delta), |d| Some(d.square())))
+                        .map(|(beta, delta)| *beta + (X * delta))
+                        .collect::<Box<[_]>>();
+
+                    let direct = plonk::iter_evaluate_witness::<C::ScalarExt>(&S, &traces[0])
+                        .enumerate()
+                        .map(|(index, f_i)| {
+                            pow_i(
+                                index,
+                                ctx.count_of_evaluation_with_padding,
+                                challenge_vector.iter(),
+                            ) * f_i.unwrap()
+                        })
+                        .sum::<C::ScalarExt>();
+
+                    assert_eq!(poly_F.eval(X), direct, "not match for {X:?}");
+                })
+        }
+
+        crate::curve_tests!(
+            [zero_f, zero_g, cmp_with_direct_eval_of_F];
+            bn256 => crate::halo2curves::bn256::G1Affine,
+            pallas => crate::halo2curves::pasta::EpAffine,
+        );
+    }
 }
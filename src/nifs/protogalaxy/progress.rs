@@ -0,0 +1,99 @@
+//! Progress reporting of the folding hot paths
+//!
+//! Evaluation of `F(X)` & `G(X)` is a reduction over a binary tree of
+//! [`super::poly::PolyContext::count_of_evaluation_with_padding`] leaves. The callback installed
+//! with [`super::poly::PolyContext::with_progress`] gets a [`ProgressEvent`] every
+//! [`LEAVES_STEP`] evaluated leaves, after all nodes of each tree level are merged & at the end of
+//! each [`Phase`].
+//!
+//! Events are emitted from the threads doing the evaluation, so the callback must be [`Sync`].
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::memory::Phase;
+
+/// Count of leaves evaluated by one task between two [`ProgressEvent::Leaves`]
+pub const LEAVES_STEP: usize = 1 << 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressEvent {
+    /// `count` more leaves are evaluated, `completed` of `total` in all tasks
+    Leaves {
+        phase: Phase,
+        count: usize,
+        completed: usize,
+        total: usize,
+    },
+    /// All nodes of `height` are merged, the root is at `height == levels`
+    Level {
+        phase: Phase,
+        height: usize,
+        levels: usize,
+    },
+    /// Phase is over
+    ///
+    /// [`Phase::ComputeK`] evaluates `G(X)` inside, so its leaves & levels are reported as
+    /// [`Phase::ComputeG`] before this event
+    Done { phase: Phase },
+}
+
+/// Progress of one tree of `total` leaves
+pub(crate) struct ProgressTracker<'p> {
+    callback: &'p (dyn Fn(ProgressEvent) + Sync),
+    phase: Phase,
+    total: usize,
+    completed: AtomicUsize,
+    /// Count of merged nodes at each height, starting from `1`
+    merged: Box<[AtomicUsize]>,
+}
+
+impl<'p> ProgressTracker<'p> {
+    /// `total` is a power of two
+    pub(crate) fn new(
+        callback: &'p (dyn Fn(ProgressEvent) + Sync),
+        phase: Phase,
+        total: usize,
+    ) -> Self {
+        Self {
+            callback,
+            phase,
+            total,
+            completed: AtomicUsize::new(0),
+            merged: (0..total.ilog2()).map(|_| AtomicUsize::new(0)).collect(),
+        }
+    }
+
+    /// Called after leaf `index` of the chunk of `len` leaves is evaluated
+    pub(crate) fn on_leaf(&self, index: usize, len: usize) {
+        let evaluated = index + 1;
+        if evaluated % LEAVES_STEP != 0 && evaluated != len {
+            return;
+        }
+
+        let count = match evaluated % LEAVES_STEP {
+            0 => LEAVES_STEP,
+            rest => rest,
+        };
+        let completed = self.completed.fetch_add(count, Ordering::Relaxed) + count;
+
+        (self.callback)(ProgressEvent::Leaves {
+            phase: self.phase,
+            count,
+            completed,
+            total: self.total,
+        });
+    }
+
+    /// Called after the node of `height` is produced by the merge of two nodes of `height - 1`
+    pub(crate) fn on_merge(&self, height: usize) {
+        let merged = self.merged[height - 1].fetch_add(1, Ordering::Relaxed) + 1;
+
+        if merged == self.total >> height {
+            (self.callback)(ProgressEvent::Level {
+                phase: self.phase,
+                height,
+                levels: self.merged.len(),
+            });
+        }
+    }
+}
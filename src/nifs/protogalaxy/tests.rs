@@ -2,11 +2,26 @@ use halo2_proofs::{
     dev::MockProver,
     halo2curves::{
         ff::{FromUniformBytes, PrimeFieldBits},
-        group::prime::PrimeCurveAffine,
+        group::{prime::PrimeCurveAffine, GroupEncoding},
     },
     plonk::Circuit,
 };
-use tracing::info_span;
+use std::{
+    array,
+    sync::{Arc, Mutex},
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+use tracing::{
+    info_span,
+    span::{Attributes, Id},
+    Subscriber,
+};
+use tracing_subscriber::{
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    Layer,
+};
 use tracing_test::traced_test;
 
 use super::*;
@@ -23,6 +38,7 @@ use crate::{
     },
     poseidon::{PoseidonHash, Spec},
     table::{CircuitRunner, Witness},
+    testing::synthetic::{SyntheticCircuit, SyntheticSpec},
 };
 
 const T: usize = 3;
@@ -133,13 +149,13 @@ impl<C: Circuit<Scalar>> Mock<C> {
     pub fn new_accumulator(&self) -> Accumulator {
         let acc = ProtoGalaxy::new_accumulator(AccumulatorArgs::from(&self.S), &self.pp, &mut ro());
 
-        ProtoGalaxy::is_sat_accumulation(&self.S, &acc)
+        ProtoGalaxy::is_sat_accumulation(&self.ck, &self.S, &acc)
             .expect("The newly created accumulator is not satisfactory");
 
         acc
     }
 
-    pub fn run(mut self) {
+    pub fn run(mut self) -> (Accumulator, nifs::protogalaxy::Proof<Scalar>) {
         let incoming = self.generate_plonk_traces();
 
         let init_accumulator = self.new_accumulator();
@@ -172,12 +188,25 @@ impl<C: Circuit<Scalar>> Mock<C> {
         )
         .unwrap();
 
-        let accumulator_inst_from_prove = AccumulatorInstance::from(accumulator_from_prove);
+        let accumulator_inst_from_prove = AccumulatorInstance::from(accumulator_from_prove.clone());
 
         assert_eq!(accumulator_inst_from_prove, accumulator_from_verify,);
+
+        (accumulator_from_prove, proof)
     }
 }
 
+/// [`Mock`] of `L` equal circuits of random linear combination
+fn rlc_mock() -> Mock<RandomLinearCombinationCircuit<Scalar>> {
+    let circuit = || {
+        RandomLinearCombinationCircuit::new((1..10).map(Scalar::from).collect(), Scalar::from(2))
+    };
+    Mock::new(
+        10,
+        array::from_fn(|_| (circuit(), vec![Scalar::from(4097)])),
+    )
+}
+
 #[traced_test]
 #[test]
 fn random_linear_combination() {
@@ -210,6 +239,344 @@ fn random_linear_combination() {
     .run();
 }
 
+#[test]
+fn byte_size() {
+    type Repr = [u8; 32];
+
+    #[derive(serde::Serialize)]
+    struct InstanceLayout {
+        W_commitments: Vec<Repr>,
+        instances: Vec<Vec<Repr>>,
+        challenges: Vec<Repr>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct AccumulatorLayout {
+        ins: InstanceLayout,
+        W: Vec<Vec<Repr>>,
+        betas: Vec<Repr>,
+        e: Repr,
+    }
+
+    #[derive(serde::Serialize)]
+    struct ProofLayout {
+        poly_F: Vec<Repr>,
+        poly_K: Vec<Repr>,
+    }
+
+    let field = |value: &Scalar| -> Repr { value.to_repr() };
+    let fields = |values: &[Scalar]| values.iter().map(field).collect::<Vec<_>>();
+    let point = |value: &Affine| -> Repr { value.to_bytes().as_ref().try_into().unwrap() };
+
+    let mock = rlc_mock();
+    let S = mock.S.clone();
+    let (acc, proof) = mock.run();
+
+    let acc_layout = AccumulatorLayout {
+        ins: InstanceLayout {
+            W_commitments: acc.trace.u.W_commitments.iter().map(point).collect(),
+            instances: acc
+                .trace
+                .u
+                .instances
+                .iter()
+                .map(|col| fields(col))
+                .collect(),
+            challenges: fields(&acc.trace.u.challenges),
+        },
+        W: acc.trace.w.W.iter().map(|col| fields(col)).collect(),
+        betas: fields(&acc.betas),
+        e: field(&acc.e),
+    };
+    assert_eq!(
+        bincode::serialize(&acc_layout).unwrap().len(),
+        Accumulator::byte_size(&S)
+    );
+
+    let proof_layout = ProofLayout {
+        poly_F: fields(proof.poly_F.coeffs()),
+        poly_K: fields(proof.poly_K.coeffs()),
+    };
+    assert_eq!(
+        bincode::serialize(&proof_layout).unwrap().len(),
+        proof.byte_size()
+    );
+}
+
+#[traced_test]
+#[test]
+fn accumulator_serde_round_trip() {
+    let mut mock = rlc_mock();
+    let incoming = mock.generate_plonk_traces();
+
+    let (acc, _proof) = ProtoGalaxy::prove(
+        &mock.ck,
+        &mock.pp,
+        &mut ro(),
+        mock.new_accumulator(),
+        &incoming,
+    )
+    .unwrap();
+
+    let bytes = bincode::serialize(&acc).unwrap();
+    assert_eq!(bytes.len(), Accumulator::byte_size(&mock.S));
+
+    let restored: Accumulator = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(restored, acc);
+
+    let json = serde_json::to_string(&acc).unwrap();
+    assert_eq!(serde_json::from_str::<Accumulator>(&json).unwrap(), acc);
+
+    let acc_instance = AccumulatorInstance::from(restored.clone());
+    let restored_instance: AccumulatorInstance<Affine> =
+        bincode::deserialize(&bincode::serialize(&acc_instance).unwrap()).unwrap();
+    assert_eq!(restored_instance, acc_instance);
+
+    // Continue folding from the restored accumulator
+    let (next_acc, proof) =
+        ProtoGalaxy::prove(&mock.ck, &mock.pp, &mut ro(), restored, &incoming).unwrap();
+
+    let next_acc_from_verify = ProtoGalaxy::verify(
+        &mock.vp,
+        &mut ro(),
+        &mut ro(),
+        &restored_instance,
+        &incoming.map(|tr| tr.u),
+        &proof,
+    )
+    .unwrap();
+
+    assert_eq!(AccumulatorInstance::from(next_acc), next_acc_from_verify);
+}
+
+#[test]
+fn pp_digest() {
+    let mock = rlc_mock();
+
+    let digest = compute_pp_digest(&mock.S, &mock.ck).unwrap();
+    assert_eq!(
+        compute_pp_digest(&mock.S.clone(), &mock.ck.clone()).unwrap(),
+        digest
+    );
+    assert_ne!(digest, Affine::identity());
+
+    let changed = PlonkStructure {
+        num_advice_columns: mock.S.num_advice_columns + 1,
+        ..mock.S.clone()
+    };
+    assert_ne!(compute_pp_digest(&changed, &mock.ck).unwrap(), digest);
+}
+
+/// `poly_F` & `poly_K` of [`ProtoGalaxy::prove`] survive serialization & the verifier accepts
+/// the deserialized proof
+#[traced_test]
+#[test]
+fn proof_serde_round_trip() {
+    let mut mock = rlc_mock();
+    let incoming = mock.generate_plonk_traces();
+    let acc = mock.new_accumulator();
+
+    let (folded, proof) =
+        ProtoGalaxy::prove(&mock.ck, &mock.pp, &mut ro(), acc.clone(), &incoming).unwrap();
+
+    let from_bincode: nifs::protogalaxy::Proof<Scalar> =
+        bincode::deserialize(&bincode::serialize(&proof).unwrap()).unwrap();
+    let from_json: nifs::protogalaxy::Proof<Scalar> =
+        serde_json::from_str(&serde_json::to_string(&proof).unwrap()).unwrap();
+    assert_eq!(from_bincode, proof);
+    assert_eq!(from_json, proof);
+
+    let folded_from_verify = ProtoGalaxy::verify(
+        &mock.vp,
+        &mut ro(),
+        &mut ro(),
+        &acc.into(),
+        &incoming.map(|tr| tr.u),
+        &from_bincode,
+    )
+    .unwrap();
+    assert_eq!(AccumulatorInstance::from(folded), folded_from_verify);
+}
+
+/// Proof computed before folding & applied separately gives the same fold as the combined one
+#[traced_test]
+#[test]
+fn prove_fold_then_apply() {
+    let mut mock = rlc_mock();
+    let incoming = mock.generate_plonk_traces();
+    let acc = mock.new_accumulator();
+
+    let proof = acc
+        .prove_fold(&mock.ck, &mock.pp, &incoming, &mut ro())
+        .unwrap();
+    let applied = acc
+        .clone()
+        .apply_fold(&mock.pp, &incoming, &proof, &mut ro());
+
+    let (expected_acc, expected_proof) =
+        acc.fold(&mock.ck, &mock.pp, &incoming, &mut ro()).unwrap();
+    assert_eq!(proof, expected_proof);
+    assert_eq!(applied, expected_acc);
+}
+
+/// Checkpoint of a long-running prover: the accumulator restored after two folds must give the
+/// same third fold as the one kept in memory
+#[traced_test]
+#[test]
+fn accumulator_checkpoint() {
+    let mut mock = rlc_mock();
+    let incoming = mock.generate_plonk_traces();
+    let fold = |acc| ProtoGalaxy::prove(&mock.ck, &mock.pp, &mut ro(), acc, &incoming).unwrap();
+
+    let (acc, _proof) = fold(mock.new_accumulator());
+    let (acc, _proof) = fold(acc);
+
+    let restored: Accumulator = bincode::deserialize(&bincode::serialize(&acc).unwrap()).unwrap();
+    assert_eq!(restored, acc);
+
+    let (expected_acc, expected_proof) = fold(acc);
+    let (acc, proof) = fold(restored);
+    assert_eq!(acc, expected_acc);
+    assert_eq!(proof, expected_proof);
+}
+
+/// Independent folds verified in parallel give the same accumulators as the sequential `verify`
+#[traced_test]
+#[test]
+fn par_verify() {
+    const FOLDS: usize = 16;
+
+    let mut mock = rlc_mock();
+    let incoming = mock.generate_plonk_traces();
+    let incoming_instances = incoming
+        .iter()
+        .map(|trace| trace.u.clone())
+        .collect::<Vec<_>>();
+
+    let mut acc = mock.new_accumulator();
+    let folds = (0..FOLDS)
+        .map(|_| {
+            let (new_acc, proof) =
+                ProtoGalaxy::prove(&mock.ck, &mock.pp, &mut ro(), acc.clone(), &incoming).unwrap();
+            let acc_instance = AccumulatorInstance::from(std::mem::replace(&mut acc, new_acc));
+
+            (acc_instance, incoming_instances.as_slice(), proof)
+        })
+        .collect::<Vec<_>>();
+
+    let sequential = folds
+        .iter()
+        .map(|(acc, incoming, proof)| {
+            ProtoGalaxy::verify(
+                &mock.vp,
+                &mut ro(),
+                &mut ro(),
+                acc,
+                (*incoming).try_into().unwrap(),
+                proof,
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        ProtoGalaxy::par_verify(&mock.vp, ro::<Base>, ro::<Base>, &folds).unwrap(),
+        sequential
+    );
+
+    // One bad proof rejects the whole set
+    let mut with_bad_proof = folds.clone();
+    let max = with_bad_proof[5].0.betas.len();
+    with_bad_proof[5].2.poly_F = UnivariatePoly::from_iter(
+        iter::repeat(Scalar::ZERO)
+            .take(max + 1)
+            .chain(iter::once(Scalar::ONE)),
+    );
+    assert!(matches!(
+        ProtoGalaxy::par_verify(&mock.vp, ro::<Base>, ro::<Base>, &with_bad_proof),
+        Err(crate::Error::ProtoGalaxy(Error::PolyFDegree { degree, max: m })) if degree == max + 1 && m == max
+    ));
+
+    let mut folds = folds;
+    folds[3].1 = &incoming_instances[1..];
+    assert!(matches!(
+        ProtoGalaxy::par_verify(&mock.vp, ro::<Base>, ro::<Base>, &folds),
+        Err(crate::Error::ProtoGalaxy(Error::FoldIncomingCount {
+            index: 3,
+            expected: L,
+            actual: 2
+        }))
+    ));
+}
+
+/// Without gates there are no betas & `F(X)` is zero, so `e` is folded from `K(X)` only
+#[traced_test]
+#[test]
+fn verify_empty_betas() {
+    let mut rnd = rand::thread_rng();
+    let instance = || PlonkInstance::<Affine> {
+        W_commitments: vec![Affine::generator()],
+        instances: vec![vec![Scalar::random(&mut rnd)]],
+        challenges: vec![],
+    };
+
+    let vp = VerifierParam {
+        pp_digest: Affine::generator(),
+    };
+    let accumulator = AccumulatorInstance {
+        ins: instance(),
+        betas: Box::new([]),
+        e: Scalar::ZERO,
+    };
+    let incoming: [_; L] = array::from_fn(|_| instance());
+    let poly_K = UnivariatePoly(Box::new([Scalar::from(2), Scalar::from(3)]));
+
+    let proof = nifs::protogalaxy::Proof {
+        poly_F: UnivariatePoly::new_zeroed(0),
+        poly_K: poly_K.clone(),
+    };
+    let folded =
+        ProtoGalaxy::verify(&vp, &mut ro(), &mut ro(), &accumulator, &incoming, &proof).unwrap();
+
+    let Challenges { gamma, .. } =
+        Challenges::generate::<_, Affine>(&vp, &mut ro(), &accumulator, incoming.iter(), &proof);
+    assert!(folded.betas.is_empty());
+    assert_eq!(
+        folded.e,
+        lagrange::eval_vanish_polynomial(L + 1, gamma) * poly_K.eval(gamma)
+    );
+}
+
+#[traced_test]
+#[test]
+fn single_threaded_prove() {
+    let mut mock = rlc_mock();
+    let incoming = mock.generate_plonk_traces();
+    let acc = mock.new_accumulator();
+
+    let parallel =
+        ProtoGalaxy::prove(&mock.ck, &mock.pp, &mut ro(), acc.clone(), &incoming).unwrap();
+
+    // Progress is reported from the threads of the prove, so they see the size of their pool
+    let pool_sizes = Arc::new(Mutex::new(Vec::new()));
+    let pp = mock.pp.with_parallelism(1).with_progress({
+        let pool_sizes = pool_sizes.clone();
+        Arc::new(move |_event| {
+            pool_sizes
+                .lock()
+                .unwrap()
+                .push(crate::util::parallel::current_num_threads())
+        })
+    });
+    let single_threaded = ProtoGalaxy::prove(&mock.ck, &pp, &mut ro(), acc, &incoming).unwrap();
+
+    assert_eq!(parallel, single_threaded);
+
+    let pool_sizes = pool_sizes.lock().unwrap();
+    assert!(!pool_sizes.is_empty());
+    assert!(pool_sizes.iter().all(|size| *size == 1));
+}
+
 #[traced_test]
 #[test]
 fn fibo() {
@@ -256,10 +623,111 @@ fn fibo() {
 
 #[traced_test]
 #[test]
-fn fibo_lookup() {
-    let _s = info_span!("fibo_lookup").entered();
+fn accumulator_is_sat() {
+    const SIZE: usize = 16;
+
+    let circuit = |a, b| {
+        let seq = get_fibo_seq(a, b, SIZE);
+        (
+            FiboCircuit {
+                a: Scalar::from(seq[0]),
+                b: Scalar::from(seq[1]),
+                num: SIZE,
+            },
+            vec![Scalar::from(seq[SIZE - 1])],
+        )
+    };
+    let mut mock = Mock::new(10, [circuit(1, 1), circuit(2, 3), circuit(3, 5)]);
+    let incoming = mock.generate_plonk_traces();
+    let acc = mock.new_accumulator();
 
-    const K: u32 = 5;
+    let (mut folded, _proof) =
+        ProtoGalaxy::prove(&mock.ck, &mock.pp, &mut ro(), acc.clone(), &incoming).unwrap();
+    ProtoGalaxy::is_sat(&mock.ck, &mock.S, &folded, &[]).unwrap();
+    assert!(!folded.e.is_zero_vartime());
+
+    // Gates of the folded trace don't vanish, the corrupted cell is found by the commitment
+    folded.trace.w.W[0][3] += Scalar::ONE;
+    let errors = ProtoGalaxy::is_sat(&mock.ck, &mock.S, &folded, &[]).unwrap_err();
+
+    assert!(errors.iter().any(|err| matches!(
+        err,
+        VerifyError::MismatchE {
+            first_offending: Some(GateRow { gate: 0, row: 3 }),
+            ..
+        }
+    )));
+
+    // Not folded trace satisfies all gates, so the offending one is found
+    let mut unfolded = Accumulator {
+        trace: incoming[0].clone(),
+        betas: acc.betas.clone(),
+        e: Scalar::ZERO,
+    };
+    ProtoGalaxy::is_sat(&mock.ck, &mock.S, &unfolded, &[]).unwrap();
+
+    // `a` of the row 3 is the current `a` of the first gate at this row
+    unfolded.trace.w.W[0][3] += Scalar::ONE;
+    let errors = ProtoGalaxy::is_sat(&mock.ck, &mock.S, &unfolded, &[]).unwrap_err();
+
+    assert!(errors.iter().any(|err| matches!(
+        err,
+        VerifyError::MismatchE {
+            first_offending: Some(GateRow { gate: 0, row: 3 }),
+            ..
+        }
+    )));
+    assert!(errors.iter().any(
+        |err| matches!(err, VerifyError::WitnessCommitmentMismatch(rounds) if rounds[..] == [0])
+    ));
+}
+
+/// Two incoming traces give `L + 1 = 3` points, so the lagrange domain is padded with a zero trace
+#[traced_test]
+#[test]
+fn fibo_padded_lagrange_domain() {
+    const SIZE: usize = 16;
+
+    let circuit = |a, b| {
+        let seq = get_fibo_seq(a, b, SIZE);
+        (
+            FiboCircuit {
+                a: Scalar::from(seq[0]),
+                b: Scalar::from(seq[1]),
+                num: SIZE,
+            },
+            vec![Scalar::from(seq[SIZE - 1])],
+        )
+    };
+    let mut mock = Mock::new(10, [circuit(1, 1), circuit(2, 3), circuit(3, 5)]);
+    let [t0, t1, _] = mock.generate_plonk_traces();
+    let acc = mock.new_accumulator();
+
+    type PaddedProtoGalaxy = crate::nifs::protogalaxy::ProtoGalaxy<Affine, 2>;
+
+    let (folded, proof) = PaddedProtoGalaxy::prove(
+        &mock.ck,
+        &mock.pp,
+        &mut ro(),
+        acc.clone(),
+        &[t0.clone(), t1.clone()],
+    )
+    .unwrap();
+    PaddedProtoGalaxy::is_sat_accumulation(&mock.ck, &mock.S, &folded).unwrap();
+
+    let folded_from_verify = PaddedProtoGalaxy::verify(
+        &mock.vp,
+        &mut ro(),
+        &mut ro(),
+        &acc.into(),
+        &[t0.u, t1.u],
+        &proof,
+    )
+    .unwrap();
+    assert_eq!(AccumulatorInstance::from(folded), folded_from_verify);
+}
+
+fn fibo_lookup_mock() -> Mock<FiboCircuitWithLookup<Scalar>> {
     const SIZE: usize = 7;
 
     // circuit 1
@@ -299,5 +767,322 @@ fn fibo_lookup() {
             ),
         ],
     )
-    .run();
+}
+
+#[traced_test]
+#[test]
+fn fibo_lookup() {
+    let _s = info_span!("fibo_lookup").entered();
+
+    fibo_lookup_mock().run();
+}
+
+/// Each kind of wrong input of `verify` is reported by its own variant
+#[traced_test]
+#[test]
+fn verify_errors() {
+    let mut mock = fibo_lookup_mock();
+    let incoming = mock.generate_plonk_traces();
+    let accumulator = mock.new_accumulator();
+
+    let (folded, proof) = ProtoGalaxy::prove(
+        &mock.ck,
+        &mock.pp,
+        &mut ro(),
+        accumulator.clone(),
+        &incoming,
+    )
+    .unwrap();
+
+    let accumulator = AccumulatorInstance::from(accumulator);
+    let incoming = incoming.map(|trace| trace.u);
+    let verify = |incoming: &[PlonkInstance<Affine>; L],
+                  proof: &nifs::protogalaxy::Proof<Scalar>| {
+        ProtoGalaxy::verify(
+            &mock.vp,
+            &mut ro(),
+            &mut ro(),
+            &accumulator,
+            incoming,
+            proof,
+        )
+    };
+
+    assert!(!incoming[0].challenges.is_empty());
+    assert_eq!(
+        verify(&incoming, &proof).unwrap(),
+        AccumulatorInstance::from(folded)
+    );
+
+    let mut wrong_shape = incoming.clone();
+    wrong_shape[1].instances[0].push(Scalar::ONE);
+    assert!(matches!(
+        verify(&wrong_shape, &proof),
+        Err(crate::Error::ProtoGalaxy(Error::IncomingShape { index: 1 }))
+    ));
+
+    let mut wrong_challenge = incoming.clone();
+    wrong_challenge[2].challenges[0] += Scalar::ONE;
+    match verify(&wrong_challenge, &proof) {
+        Err(crate::Error::ProtoGalaxy(Error::VerifySps(errors))) => {
+            assert_eq!(errors.len(), 1);
+            assert!(matches!(
+                errors[0],
+                (2, sps::Error::ChallengeNotMatch { challenge_index: 0 })
+            ));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    let max = accumulator.betas.len();
+    let mut wrong_degree = proof.clone();
+    wrong_degree.poly_F = UnivariatePoly::from_iter(
+        proof
+            .poly_F
+            .iter()
+            .copied()
+            .chain(iter::repeat(Scalar::ZERO))
+            .take(max + 2)
+            .enumerate()
+            .map(|(i, coeff)| if i == max + 1 { Scalar::ONE } else { coeff }),
+    );
+    assert!(matches!(
+        verify(&incoming, &wrong_degree),
+        Err(crate::Error::ProtoGalaxy(Error::PolyFDegree { degree, max: m })) if degree == max + 1 && m == max
+    ));
+}
+
+#[traced_test]
+#[test]
+fn synthetic() {
+    fn run<const GATES: usize, const DEGREE: usize, const LOOKUPS: usize>(k: u32) {
+        let circuits = array::from_fn(|seed| {
+            let circuit = SyntheticCircuit::<Scalar, GATES, DEGREE, LOOKUPS>::new(SyntheticSpec {
+                k,
+                rows: 1 << (k - 1),
+                instance_width: 2,
+                seed: seed as u64,
+            });
+            let instance = circuit.instances().remove(0);
+            (circuit, instance)
+        });
+
+        Mock::new(k, circuits).run();
+    }
+
+    run::<2, 3, 0>(5);
+    run::<1, 2, 1>(6);
+}
+
+#[traced_test]
+#[test]
+fn deterministic_with_seeded_rng() {
+    let run = || {
+        let mut rng = StdRng::seed_from_u64(42);
+        let circuits = array::from_fn(|_| {
+            let circuit = SyntheticCircuit::<Scalar, 2, 3, 1>::from_rng(
+                SyntheticSpec {
+                    k: 6,
+                    rows: 32,
+                    instance_width: 2,
+                    seed: 0,
+                },
+                &mut rng,
+            );
+            let instance = circuit.instances().remove(0);
+            (circuit, instance)
+        });
+
+        Mock::new(6, circuits).run()
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[traced_test]
+#[test]
+fn debug_dump_verify() {
+    let circuit = || {
+        let circuit = SyntheticCircuit::<Scalar, 1, 2, 0>::new(SyntheticSpec {
+            k: 5,
+            rows: 16,
+            instance_width: 1,
+            seed: 0,
+        });
+        let instance = circuit.instances().remove(0);
+        (circuit, instance)
+    };
+    let mut mock = Mock::new(5, [circuit(), circuit(), circuit()]);
+
+    let incoming = mock.generate_plonk_traces();
+    let init_accumulator = mock.new_accumulator();
+
+    let (accumulator_from_prove, proof) = ProtoGalaxy::prove(
+        &mock.ck,
+        &mock.pp,
+        &mut ro(),
+        init_accumulator.clone(),
+        &incoming,
+    )
+    .unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("fold.json");
+    debug_dump::dump_fold_inputs(
+        &path,
+        &mock.vp,
+        &AccumulatorInstance::from(init_accumulator),
+        &incoming.map(|tr| tr.u),
+        &proof,
+    )
+    .unwrap();
+
+    let loaded = debug_dump::load_fold_inputs::<Affine, L>(&path).unwrap();
+    assert_eq!(
+        debug_dump::verify_dump(&loaded, &mut ro(), &mut ro()).unwrap(),
+        AccumulatorInstance::from(accumulator_from_prove)
+    );
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_of_fold() {
+    use metrics_util::debugging::DebuggingRecorder;
+
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+
+    ::metrics::with_local_recorder(&recorder, || {
+        let circuit = || {
+            let circuit = SyntheticCircuit::<Scalar, 1, 2, 0>::new(SyntheticSpec {
+                k: 5,
+                rows: 16,
+                instance_width: 1,
+                seed: 0,
+            });
+            let instance = circuit.instances().remove(0);
+            (circuit, instance)
+        };
+
+        Mock::new(5, [circuit(), circuit(), circuit()]).run();
+    });
+
+    let names = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, ..)| key.key().name().to_owned())
+        .collect::<Vec<_>>();
+
+    for expected in [
+        crate::metrics::FOLD_TOTAL,
+        crate::metrics::FOLD_DURATION_SECONDS,
+        crate::metrics::FOLD_PHASE_DURATION_SECONDS,
+        crate::metrics::ACCUMULATOR_WITNESS_BYTES,
+        crate::metrics::COMMIT_TOTAL,
+        crate::metrics::MSM_SIZE,
+        crate::metrics::SPS_ROUNDS_TOTAL,
+        crate::metrics::VERIFY_TOTAL,
+        crate::metrics::VERIFY_DURATION_SECONDS,
+    ] {
+        assert!(
+            names.iter().any(|name| name == expected),
+            "{expected} not emitted"
+        );
+    }
+}
+
+#[test]
+fn error_conversions() {
+    use crate::{ivc::protogalaxy::VerifyChipError, sps};
+
+    let err = Error::from(poly::Error::EmptyTracesNotAllowed);
+    assert!(matches!(
+        err,
+        Error::Poly(poly::Error::EmptyTracesNotAllowed)
+    ));
+    assert_eq!(err.to_string(), "You can't fold 0 traces");
+
+    let err = Error::from(sps::Error::ChallengeNotMatch { challenge_index: 1 });
+    assert!(matches!(
+        err,
+        Error::Sps(sps::Error::ChallengeNotMatch { challenge_index: 1 })
+    ));
+    assert_eq!(
+        err.to_string(),
+        "Sps verification fail challenge not match at index 1"
+    );
+
+    let err = Error::from(VerifyChipError::Squeeze {
+        err: halo2_proofs::plonk::Error::Synthesis,
+    });
+    assert!(matches!(err, Error::Chip(VerifyChipError::Squeeze { .. })));
+    assert_eq!(err.to_string(), "Error while squeeze challenges: Synthesis");
+
+    assert!(matches!(
+        crate::Error::from(VerifyChipError::Fold {
+            err: halo2_proofs::plonk::Error::Synthesis,
+        }),
+        crate::Error::ProtoGalaxy(Error::Chip(VerifyChipError::Fold { .. }))
+    ));
+}
+
+/// Collects `(name, parent name)` of every created span
+#[derive(Clone, Default)]
+struct SpanTreeCollector(Arc<Mutex<Vec<(&'static str, Option<&'static str>)>>>);
+
+impl<S: Subscriber + for<'l> LookupSpan<'l>> Layer<S> for SpanTreeCollector {
+    fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let span = ctx.span(id).expect("span just created");
+        self.0
+            .lock()
+            .unwrap()
+            .push((span.name(), span.parent().map(|parent| parent.name())));
+    }
+}
+
+#[test]
+fn span_hierarchy() {
+    const SIZE: usize = 16;
+
+    let seq = get_fibo_seq(1, 1, SIZE);
+    let circuit = || {
+        (
+            FiboCircuit {
+                a: Scalar::from(seq[0]),
+                b: Scalar::from(seq[1]),
+                num: SIZE,
+            },
+            vec![Scalar::from(seq[SIZE - 1])],
+        )
+    };
+
+    let collector = SpanTreeCollector::default();
+    tracing::subscriber::with_default(
+        tracing_subscriber::registry().with(collector.clone()),
+        || Mock::new(10, [circuit(), circuit(), circuit()]).run(),
+    );
+
+    let spans = collector.0.lock().unwrap();
+    [
+        ("sps", None),
+        ("commit", Some("witness_commit")),
+        ("compute_F", Some("prove")),
+        ("ifft", Some("compute_F")),
+        ("compute_K", Some("prove")),
+        ("compute_G", Some("compute_K")),
+        ("ifft", Some("compute_G")),
+        ("fold_instance", Some("prove")),
+        ("fold_witness", Some("prove")),
+        ("verify_sps", Some("verify")),
+        ("off_circuit_generate", Some("verify")),
+        ("fold_instance", Some("verify")),
+    ]
+    .into_iter()
+    .for_each(|expected| {
+        assert!(
+            spans.contains(&expected),
+            "span {expected:?} not found in {spans:?}"
+        )
+    });
 }
@@ -0,0 +1,179 @@
+//! Transcript of the challenges of [`super::ProtoGalaxy`] for binding into external protocols
+//!
+//! E.g. a SNARK proving that the folding verifier ran has to absorb the same elements in the same
+//! order to derive the same `delta`, `alpha` & `gamma`. The elements are recorded from the
+//! [`AbsorbInRO`] implementations used by the verifier, so they can't diverge from it.
+
+use std::num::NonZeroUsize;
+
+use super::{AccumulatorInstance, Challenges, Proof, VerifierParam};
+use crate::{
+    ff::Field,
+    halo2curves::CurveAffine,
+    plonk::PlonkInstance,
+    poseidon::{random_oracle::ROConstantsTrait, ROTrait},
+};
+
+/// One step of the transcript of [`super::ProtoGalaxy::verify`] challenges
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptElement<C: CurveAffine> {
+    Point(C),
+    Field(C::Base),
+    /// Challenge squeezed from everything absorbed before it
+    Squeeze {
+        num_bits: NonZeroUsize,
+    },
+}
+
+impl<C: CurveAffine> TranscriptElement<C> {
+    /// Replay this element into `ro`, returns the challenge for [`TranscriptElement::Squeeze`]
+    pub fn replay(&self, ro: &mut impl ROTrait<C::Base>) -> Option<C::ScalarExt> {
+        match self {
+            Self::Point(point) => {
+                ro.absorb_point(point);
+                None
+            }
+            Self::Field(value) => {
+                ro.absorb_field(*value);
+                None
+            }
+            Self::Squeeze { num_bits } => Some(ro.squeeze::<C>(*num_bits)),
+        }
+    }
+}
+
+struct NoConstants;
+
+impl ROConstantsTrait for NoConstants {
+    fn new(_r_f: usize, _r_p: usize) -> Self {
+        Self
+    }
+}
+
+/// [`ROTrait`] that only records the calls
+///
+/// Panics on absorbing a point that isn't on `C`, the verifier only absorbs points of `C`
+struct Recorder<C: CurveAffine> {
+    elements: Vec<TranscriptElement<C>>,
+}
+
+impl<C: CurveAffine> ROTrait<C::Base> for Recorder<C> {
+    type Constants = NoConstants;
+
+    fn new(_constants: Self::Constants) -> Self {
+        Self {
+            elements: Vec::new(),
+        }
+    }
+
+    fn absorb_field(&mut self, base: C::Base) -> &mut Self {
+        self.elements.push(TranscriptElement::Field(base));
+        self
+    }
+
+    fn absorb_point<P: CurveAffine<Base = C::Base>>(&mut self, p: &P) -> &mut Self {
+        // The identity has no coordinates
+        let coordinates: Option<_> = p.coordinates().into();
+        let point = match coordinates {
+            Some(coordinates) => Option::<C>::from(C::from_xy(*coordinates.x(), *coordinates.y()))
+                .expect("absorbed point isn't on the curve of the transcript"),
+            None => C::identity(),
+        };
+
+        self.elements.push(TranscriptElement::Point(point));
+        self
+    }
+
+    fn inspect(&mut self, scan: impl FnOnce(&[C::Base])) -> &mut Self {
+        // Nothing is hashed, so there is no buffer to show
+        scan(&[]);
+        self
+    }
+
+    fn squeeze<P: CurveAffine<Base = C::Base>>(&mut self, num_bits: NonZeroUsize) -> P::Scalar {
+        self.elements.push(TranscriptElement::Squeeze { num_bits });
+        P::Scalar::ZERO
+    }
+}
+
+/// Everything absorbed & squeezed while [`super::ProtoGalaxy::verify`] generates `delta`,
+/// `alpha` & `gamma`, in order
+///
+/// Replaying the elements into a fresh random oracle with [`TranscriptElement::replay`] gives
+/// these challenges at the three [`TranscriptElement::Squeeze`] steps.
+pub fn transcript_elements<C: CurveAffine>(
+    vp: &VerifierParam<C>,
+    accumulator: &AccumulatorInstance<C>,
+    incoming: &[PlonkInstance<C>],
+    proof: &Proof<C::ScalarExt>,
+) -> Vec<TranscriptElement<C>> {
+    let mut recorder = Recorder::new(NoConstants);
+    Challenges::generate::<_, C>(vp, &mut recorder, accumulator, incoming.iter(), proof);
+    recorder.elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        group::{prime::PrimeCurveAffine, Curve},
+        halo2curves::bn256::G1Affine as Affine,
+        polynomial::univariate::UnivariatePoly,
+        poseidon::{PoseidonHash, Spec},
+    };
+
+    type Scalar = <Affine as CurveAffine>::ScalarExt;
+    type Base = <Affine as CurveAffine>::Base;
+    type RO = PoseidonHash<Base, 3, 2>;
+
+    fn ro() -> RO {
+        RO::new(Spec::new(4, 3))
+    }
+
+    #[test]
+    fn replay_gives_same_challenges() {
+        let field = || Scalar::random(rand::thread_rng());
+        let instance = || PlonkInstance::<Affine> {
+            W_commitments: vec![
+                (Affine::generator() * field()).to_affine(),
+                Affine::identity(),
+            ],
+            instances: vec![vec![field(), field()]],
+            challenges: vec![field()],
+        };
+
+        let vp = VerifierParam {
+            pp_digest: instance().W_commitments[0],
+        };
+        let accumulator = AccumulatorInstance {
+            ins: instance(),
+            betas: Box::new([field(), field()]),
+            e: field(),
+        };
+        let incoming = [instance(), instance(), instance()];
+        let proof = Proof {
+            poly_F: UnivariatePoly(Box::new([field(), field()])),
+            poly_K: UnivariatePoly(Box::new([field()])),
+        };
+
+        let Challenges {
+            delta,
+            alpha,
+            gamma,
+        } = Challenges::generate::<_, Affine>(
+            &vp,
+            &mut ro(),
+            &accumulator,
+            incoming.iter(),
+            &proof,
+        );
+
+        let mut ro = ro();
+        let replayed = transcript_elements(&vp, &accumulator, &incoming, &proof)
+            .iter()
+            .filter_map(|element| element.replay(&mut ro))
+            .collect::<Vec<_>>();
+
+        assert_eq!(replayed, [delta, alpha, gamma]);
+    }
+}
@@ -8,7 +8,6 @@ use halo2_proofs::{
     halo2curves::ff::{FromUniformBytes, PrimeFieldBits},
 };
 use itertools::Itertools;
-use rayon::prelude::*;
 use tracing::{debug, instrument, warn};
 
 use super::{GetConsistencyMarkers, GetStepCircuitInstances};
@@ -20,7 +19,7 @@ use crate::{
         self, GetChallenges, GetWitness, PlonkInstance, PlonkStructure, PlonkTrace, PlonkWitness,
     },
     poseidon::{AbsorbInRO, ROTrait},
-    util::ScalarToBase,
+    util::{parallel::*, ScalarToBase},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
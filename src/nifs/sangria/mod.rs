@@ -2,7 +2,6 @@ use std::{iter, marker::PhantomData, num::NonZeroUsize};
 
 use count_to_non_zero::CountToNonZeroExt;
 use itertools::Itertools;
-use rayon::prelude::*;
 use some_to_err::ErrOr;
 use tracing::*;
 
@@ -30,6 +29,7 @@ use crate::{
     },
     poseidon::ROTrait,
     sps::{Error as SpsError, SpecialSoundnessVerifier},
+    util::parallel::*,
 };
 
 pub mod accumulator;
@@ -28,6 +28,8 @@ enum Error<C: CurveAffine> {
     Nifs(#[from] nifs::sangria::Error),
     #[error(transparent)]
     Plonk(#[from] plonk::Error),
+    #[error(transparent)]
+    Sirius(#[from] crate::Error),
     #[error("while verify: {errors:?}")]
     Verify {
         errors: Vec<(&'static str, nifs::sangria::VerifyError)>,
@@ -42,7 +42,6 @@ use std::{
 };
 
 use halo2_proofs::{plonk::ConstraintSystem, poly::Rotation};
-use rayon::prelude::*;
 use serde::Serialize;
 use tracing::*;
 
@@ -54,6 +53,7 @@ use crate::{
         PlonkStructure,
     },
     polynomial::{graph_evaluator::GraphEvaluator, Expression, Query},
+    util::parallel::*,
 };
 
 /// Lookup Argument
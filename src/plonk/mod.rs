@@ -9,15 +9,14 @@
 //!
 //! Additionally, it defines a method is_sat on PlonkStructure to determine if
 //! a given Plonk instance and witness satisfy the circuit constraints.
-use std::{iter, num::NonZeroUsize, time::Instant};
+use std::{iter, num::NonZeroUsize, ops::Range, sync::OnceLock};
 
 use count_to_non_zero::*;
 use halo2_proofs::arithmetic::CurveAffine;
 use itertools::Itertools;
-use rayon::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use some_to_err::*;
-use tracing::{debug, error, info, info_span, instrument, warn};
+use tracing::{debug, error, info_span, instrument, warn};
 
 use self::permutation::PermutationData;
 use crate::{
@@ -25,6 +24,7 @@ use crate::{
     concat_vec,
     constants::NUM_CHALLENGE_BITS,
     ff::{Field, PrimeField},
+    metrics,
     plonk::{
         self,
         eval::{Error as EvalError, GetDataForEval, PlonkEvalDomain},
@@ -38,7 +38,7 @@ use crate::{
     },
     poseidon::{AbsorbInRO, ROTrait},
     sps::{Error as SpsError, SpecialSoundnessVerifier},
-    util::{concatenate_with_padding, ScalarToBase},
+    util::{concatenate_with_padding, parallel::*, serde_repr, ScalarToBase},
 };
 
 pub mod eval;
@@ -81,27 +81,16 @@ pub(crate) struct CompressedGates<F: PrimeField> {
 impl<F: PrimeField> CompressedGates<F> {
     #[instrument(name = "compressed_gates", skip_all)]
     pub fn new(original_expressions: &[Expression<F>], ctx: &mut QueryIndexContext) -> Self {
-        let timer = Instant::now();
         debug!("input num_challenges: {}", ctx.num_challenges);
-        let compressed = plonk::util::compress_expression(original_expressions, ctx.num_challenges);
-        info!(
-            "custom gates compressed in {} ns",
-            timer.elapsed().as_nanos()
-        );
+        let compressed = info_span!("compress").in_scope(|| {
+            plonk::util::compress_expression(original_expressions, ctx.num_challenges)
+        });
         ctx.num_challenges = compressed.num_challenges();
 
-        let homogeneous = compressed.homogeneous(ctx);
-        info!(
-            "compressed made homogeneous in {} ns",
-            timer.elapsed().as_nanos()
-        );
+        let homogeneous = info_span!("homogeneous").in_scope(|| compressed.homogeneous(ctx));
         ctx.num_challenges = homogeneous.num_challenges();
 
-        let grouped = GroupedPoly::new(&homogeneous, ctx);
-        info!(
-            "homogeneous made grouped in {} ns",
-            timer.elapsed().as_nanos()
-        );
+        let grouped = info_span!("grouped").in_scope(|| GroupedPoly::new(&homogeneous, ctx));
 
         Self {
             compressed,
@@ -156,12 +145,36 @@ pub struct PlonkStructure<F: PrimeField> {
 
     pub(crate) permutation_data: PermutationData,
     pub(crate) lookup_arguments: Option<lookup::Arguments<F>>,
+
+    /// Cache of [`PlonkStructure::max_gate_degree`] & [`PlonkStructure::zero_trace_violation`]
+    ///
+    /// Must be reset, if `gates` are replaced in a copy of the structure
+    #[serde(skip)]
+    pub(crate) gates_cache: GatesCache,
+}
+
+/// Lazily computed properties of [`PlonkStructure::gates`]
+///
+/// They're derived from the gates, so they're ignored when structures are compared
+#[derive(Clone, Debug, Default)]
+pub(crate) struct GatesCache {
+    max_degree: OnceLock<usize>,
+    zero_trace_violation: OnceLock<Option<(usize, usize)>>,
+}
+
+impl PartialEq for GatesCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct PlonkInstance<C: CurveAffine> {
     /// `W_commitments = round_sizes.len()`, see [`PlonkStructure::round_sizes`]
+    #[serde(with = "serde_repr::points")]
     pub(crate) W_commitments: Vec<C>,
+    #[serde(with = "serde_repr::nested_fields")]
     pub(crate) instances: Vec<Vec<C::ScalarExt>>,
     /// challenges generated in special soundness protocol
     /// we will have 0 ~ 3 challenges depending on different cases:
@@ -170,12 +183,15 @@ pub struct PlonkInstance<C: CurveAffine> {
     /// r2: challenge to calculate h and g in log-derivative relation
     /// r3: combine all custom gates (P_i) and lookup relations (L_i), e.g.:
     /// (P_1, P_2, L_1, L_2) -> P_1 + r3*P_2 + r3^2*L_1 + r3^3*L_2
+    #[serde(with = "serde_repr::fields")]
     pub(crate) challenges: Vec<C::ScalarExt>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct PlonkWitness<F: PrimeField> {
     /// length of W equals number of prover rounds, see [`PlonkStructure`]
+    #[serde(with = "serde_repr::nested_fields")]
     pub(crate) W: Vec<Vec<F>>,
 }
 
@@ -188,7 +204,8 @@ impl<F: PrimeField> PlonkWitness<F> {
 }
 
 // TODO #31 docs
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct PlonkTrace<C: CurveAffine> {
     pub u: PlonkInstance<C>,
     pub w: PlonkWitness<C::Scalar>,
@@ -243,6 +260,24 @@ impl<C: CurveAffine> GetWitness<C::ScalarExt> for PlonkTrace<C> {
     }
 }
 
+/// All-zero witness & challenges in the shape of a [`PlonkStructure`]
+struct ZeroTrace<F: PrimeField> {
+    challenges: Vec<F>,
+    witness: PlonkWitness<F>,
+}
+
+impl<F: PrimeField> GetChallenges<F> for ZeroTrace<F> {
+    fn get_challenges(&self) -> &[F] {
+        &self.challenges
+    }
+}
+
+impl<F: PrimeField> GetWitness<F> for ZeroTrace<F> {
+    fn get_witness(&self) -> &[Vec<F>] {
+        self.witness.get_witness()
+    }
+}
+
 /// Generalized trait to get challenges
 ///
 /// Used to generalize:
@@ -299,6 +334,62 @@ impl<F: PrimeField> PlonkStructure<F> {
         }
     }
 
+    /// Degree of each of [`PlonkStructure::gates`] in ascending order
+    pub fn gate_degrees(&self) -> Vec<usize> {
+        let ctx = QueryIndexContext::from(self);
+
+        self.gates
+            .iter()
+            .map(|gate| gate.degree(&ctx))
+            .sorted_unstable()
+            .collect()
+    }
+
+    /// Maximum degree of [`PlonkStructure::gates`], zero if there are no gates
+    ///
+    /// Computed once per structure
+    pub fn max_gate_degree(&self) -> usize {
+        *self.gates_cache.max_degree.get_or_init(|| {
+            let ctx = QueryIndexContext::from(self);
+
+            self.gates
+                .iter()
+                .map(|gate| gate.degree(&ctx))
+                .max()
+                .unwrap_or_default()
+        })
+    }
+
+    /// First `(gate, row)` at which the gates don't vanish on the all-zero witness & challenges
+    ///
+    /// Folding pads the lagrange domain with zero traces, that is sound only if there is no such
+    /// row. Computed once per structure
+    pub fn zero_trace_violation(&self) -> Result<Option<(usize, usize)>, EvalError> {
+        if let Some(violation) = self.gates_cache.zero_trace_violation.get() {
+            return Ok(*violation);
+        }
+
+        let zero_trace = ZeroTrace {
+            challenges: vec![F::ZERO; self.num_challenges],
+            witness: PlonkWitness::new(&self.round_sizes),
+        };
+        let row_size = 1 << self.k;
+
+        let violation = iter_evaluate_witness(self, &zero_trace)
+            .enumerate()
+            .find_map(|(index, evaluated)| match evaluated {
+                Ok(value) if value.is_zero_vartime() => None,
+                Ok(_) => Some(Ok((index / row_size, index % row_size))),
+                Err(err) => Some(Err(err)),
+            })
+            .transpose()?;
+
+        Ok(*self
+            .gates_cache
+            .zero_trace_violation
+            .get_or_init(|| violation))
+    }
+
     /// indicates whether the original constrain system contains vector lookup
     pub fn has_vector_lookup(&self) -> bool {
         self.lookup_arguments
@@ -423,6 +514,8 @@ impl<F: PrimeField> PlonkStructure<F> {
         advice: &[Vec<F>],
         ro_nark: &mut RO,
     ) -> Result<PlonkTrace<C>, SpsError> {
+        metrics::increment_counter(metrics::SPS_ROUNDS_TOTAL, self.round_sizes.len() as u64);
+
         match self.num_challenges {
             0 => self.run_sps_protocol_0(instances, advice, ck),
             1 => self.run_sps_protocol_1(instances, advice, ck, ro_nark),
@@ -698,26 +791,85 @@ impl<C: CurveAffine> PlonkInstance<C> {
 /// fails.
 ///
 /// In other words iterator: `[gate1(row0), ..., gate1(rowN), gate2(0), ...]`
+///
+/// If the gate is enabled by a selector (see [`enabling_selector`]), rows with disabled selector
+/// yield [`Field::ZERO`] without evaluation of the gate expression
 pub(crate) fn iter_evaluate_witness<'link, F: PrimeField>(
     S: &'link PlonkStructure<F>,
     trace: &'link (impl Sync + GetChallenges<F> + GetWitness<F>),
 ) -> impl 'link + Send + Iterator<Item = Result<F, eval::Error>> {
-    S.gates.iter().flat_map(|gate| {
-        let eval_domain = PlonkEvalDomain {
-            num_advice: S.num_advice_columns,
-            num_lookup: S.num_lookups(),
-            selectors: &S.selectors,
-            fixed: &S.fixed_columns,
-            challenges: trace.get_challenges(),
-            W1s: trace.get_witness(),
-            W2s: &[],
-        };
+    iter_evaluate_witness_range(S, trace, 0..usize::MAX)
+}
+
+/// Same as [`iter_evaluate_witness`], but yields only items with indexes from `range`
+///
+/// Items out of `range` are not evaluated, so the whole sequence can be split into chunks &
+/// evaluated in parallel
+pub(crate) fn iter_evaluate_witness_range<'link, F: PrimeField>(
+    S: &'link PlonkStructure<F>,
+    trace: &'link (impl Sync + GetChallenges<F> + GetWitness<F>),
+    range: Range<usize>,
+) -> impl 'link + Send + Iterator<Item = Result<F, eval::Error>> {
+    iter_evaluate_witness_with(S, trace, range, true)
+}
+
+/// Index of a selector, that is a factor of the `gate` (queried without rotation)
+///
+/// If such selector is disabled at some row, the `gate` is zero at this row
+fn enabling_selector<F: PrimeField>(gate: &Expression<F>, num_selectors: usize) -> Option<usize> {
+    match gate {
+        Expression::Polynomial(query) if query.index < num_selectors && query.rotation.0 == 0 => {
+            Some(query.index)
+        }
+        Expression::Product(lhs, rhs) => {
+            enabling_selector(lhs, num_selectors).or_else(|| enabling_selector(rhs, num_selectors))
+        }
+        Expression::Negated(expr) | Expression::Scaled(expr, _) => {
+            enabling_selector(expr, num_selectors)
+        }
+        _ => None,
+    }
+}
+
+fn iter_evaluate_witness_with<'link, F: PrimeField>(
+    S: &'link PlonkStructure<F>,
+    trace: &'link (impl Sync + GetChallenges<F> + GetWitness<F>),
+    range: Range<usize>,
+    skip_disabled: bool,
+) -> impl 'link + Send + Iterator<Item = Result<F, eval::Error>> {
+    let row_size = 2usize.pow(S.k as u32);
 
-        let evaluator = GraphEvaluator::new(gate);
+    S.gates
+        .iter()
+        .enumerate()
+        .map(move |(gate_index, gate)| {
+            let gate_start = gate_index * row_size;
+            let to_row = |index: usize| index.clamp(gate_start, gate_start + row_size) - gate_start;
 
-        (0..eval_domain.row_size())
-            .map(move |row_index| evaluator.evaluate(&eval_domain, row_index))
-    })
+            (gate, to_row(range.start)..to_row(range.end))
+        })
+        .filter(|(_gate, rows)| !rows.is_empty())
+        .flat_map(move |(gate, rows)| {
+            let eval_domain = PlonkEvalDomain {
+                num_advice: S.num_advice_columns,
+                num_lookup: S.num_lookups(),
+                selectors: &S.selectors,
+                fixed: &S.fixed_columns,
+                challenges: trace.get_challenges(),
+                W1s: trace.get_witness(),
+                W2s: &[],
+            };
+
+            let evaluator = GraphEvaluator::new(gate);
+            let selector = enabling_selector(gate, S.selectors.len())
+                .filter(|_| skip_disabled)
+                .map(|index| &S.selectors[index]);
+
+            rows.map(move |row_index| match selector {
+                Some(selector) if !selector[row_index] => Ok(F::ZERO),
+                _ => evaluator.evaluate(&eval_domain, row_index),
+            })
+        })
 }
 
 #[cfg(test)]
@@ -853,11 +1005,88 @@ pub(crate) mod test_eval_witness {
             )
             .unwrap();
 
-        use rayon::prelude::*;
+        use crate::util::parallel::*;
         super::iter_evaluate_witness::<Field>(&S, &PlonkTrace { u, w })
             .par_bridge()
             .for_each(|v| {
                 assert_eq!(v, Ok(Field::ZERO));
             });
     }
+
+    #[test]
+    fn skip_disabled_selectors() {
+        let runner = CircuitRunner::<Field, _>::new(
+            12,
+            poseidon_circuit::TestPoseidonCircuit::<_, 5>::default(),
+            vec![],
+        );
+
+        let S = runner.try_collect_plonk_structure().unwrap();
+        let witness = runner.try_collect_witness().unwrap();
+
+        let mut trace = S
+            .run_sps_protocol(
+                &CommitmentKey::<Curve>::setup(15, b"k"),
+                &[],
+                &witness,
+                &mut RO::new(PoseidonSpec::new(R_F1, R_P1)),
+            )
+            .unwrap();
+
+        // With random witness gates are non-zero on enabled rows
+        let mut rnd = rand::thread_rng();
+        trace
+            .w
+            .W
+            .iter_mut()
+            .flatten()
+            .for_each(|value| *value = Field::random(&mut rnd));
+
+        assert!(S
+            .gates
+            .iter()
+            .any(|gate| super::enabling_selector(gate, S.selectors.len()).is_some()));
+        assert!(S
+            .selectors
+            .iter()
+            .any(|selector| selector.iter().any(|enabled| !enabled)));
+
+        assert!(super::iter_evaluate_witness::<Field>(&S, &trace).eq(
+            super::iter_evaluate_witness_with::<Field>(&S, &trace, 0..usize::MAX, false)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::halo2curves::bn256::Fr;
+
+    #[test]
+    fn gate_degrees() {
+        let challenge = || Box::new(Expression::Challenge(0));
+
+        let S = PlonkStructure::<Fr> {
+            k: 1,
+            num_challenges: 1,
+            gates: vec![
+                Expression::Product(challenge(), challenge()),
+                Expression::Constant(Fr::ONE),
+                Expression::Product(
+                    challenge(),
+                    Box::new(Expression::Product(challenge(), challenge())),
+                ),
+                *challenge(),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(S.gate_degrees(), [0, 1, 2, 3]);
+        assert_eq!(S.max_gate_degree(), 3);
+        // Cached value
+        assert_eq!(S.clone().max_gate_degree(), 3);
+
+        assert_eq!(PlonkStructure::<Fr>::default().gate_degrees(), []);
+        assert_eq!(PlonkStructure::<Fr>::default().max_gate_degree(), 0);
+    }
 }
@@ -2,7 +2,6 @@ use std::{
     cmp::Ordering,
     fmt,
     ops::{Add, Mul, Neg, Sub},
-    time::Instant,
 };
 
 use itertools::*;
@@ -88,8 +87,6 @@ impl<F: PrimeField> GroupedPoly<F> {
     pub fn new(expr: &Expression<F>, ctx: &QueryIndexContext) -> Self {
         use Expression::*;
 
-        let timer = Instant::now();
-
         trace!("start grouped {expr}");
         let res = match expr {
             Constant(constant) => GroupedPoly {
@@ -132,7 +129,7 @@ impl<F: PrimeField> GroupedPoly<F> {
             Scaled(a, k) => GroupedPoly::new(a, ctx) * k,
         };
 
-        trace!("grouped {expr} in {} ns", timer.elapsed().as_nanos());
+        trace!("grouped {expr}");
 
         res
     }
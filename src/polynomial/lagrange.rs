@@ -47,6 +47,9 @@ pub fn iter_cyclic_subgroup<F: PrimeField>(log_n: u32) -> impl Iterator<Item = F
 /// ```
 /// where {1, \omega, \omega^2, ..., \omega^n} - cyclic group, check [`iter_cyclic_subgroup`] for
 /// more details
+///
+/// If `X` is an element of the cyclic group (`X^n - 1 = 0`), the formula degenerates into `0/0`,
+/// so Kronecker-delta values are returned instead: `L_i(\omega^j) = [i == j]`
 pub fn iter_eval_lagrange_poly_for_cyclic_group<F: PrimeField>(
     X: F,
     lagrange_domain: u32,
@@ -57,18 +60,21 @@ pub fn iter_eval_lagrange_poly_for_cyclic_group<F: PrimeField>(
         .invert()
         .expect("safe because it's `2^log_n`");
 
+    let X_pow_n_sub_1 = X.pow([points_count as u64]) - F::ONE;
+    let is_X_in_domain = X_pow_n_sub_1.is_zero_vartime();
+
     iter_cyclic_subgroup::<F>(lagrange_domain)
         .map(move |value| {
-            let X_sub_value_inverted = X.sub(value).invert();
-            let X_pow_n_sub_1 = X.pow([points_count as u64]) - F::ONE;
-
-            // During the calculation, this part of the expression should be reduced to 1, but we
-            // get 0/0 here, so we insert an explicit `if`.
-            if X_pow_n_sub_1.is_zero_vartime() && X_sub_value_inverted.is_none().into() {
-                F::ONE
-            } else {
-                value * inverted_n * (X_pow_n_sub_1 * X_sub_value_inverted.unwrap())
+            if is_X_in_domain {
+                return if X == value { F::ONE } else { F::ZERO };
             }
+
+            let X_sub_value_inverted = X
+                .sub(value)
+                .invert()
+                .expect("safe because `X` is not an element of the cyclic group");
+
+            value * inverted_n * X_pow_n_sub_1 * X_sub_value_inverted
         })
         .take(points_count)
 }
@@ -126,4 +132,35 @@ mod tests {
             .map(|f| Fr::from_str_vartime(f).unwrap())
         );
     }
+
+    #[test]
+    fn kronecker_delta_in_domain() {
+        let mut rnd = rand::thread_rng();
+
+        for log_n in 1..=3 {
+            let domain = iter_cyclic_subgroup::<Fr>(log_n).collect::<Vec<_>>();
+
+            domain.iter().enumerate().for_each(|(j, w_j)| {
+                iter_eval_lagrange_poly_for_cyclic_group(*w_j, log_n)
+                    .enumerate()
+                    .for_each(|(i, L_i)| {
+                        assert_eq!(L_i, if i == j { Fr::ONE } else { Fr::ZERO });
+                    })
+            });
+
+            // For point outside of domain: sum(L_i(X)) = 1 & sum(L_i(X) * w_i) = X
+            let X = Fr::random(&mut rnd);
+            let L = iter_eval_lagrange_poly_for_cyclic_group(X, log_n).collect::<Vec<_>>();
+
+            assert_eq!(L.len(), domain.len());
+            assert_eq!(L.iter().sum::<Fr>(), Fr::ONE);
+            assert_eq!(
+                L.iter()
+                    .zip(domain.iter())
+                    .map(|(L_i, w_i)| L_i * w_i)
+                    .sum::<Fr>(),
+                X
+            );
+        }
+    }
 }
@@ -1,13 +1,37 @@
 use std::{
     cmp::Ordering,
-    iter,
-    ops::{Add, Mul},
+    fmt, iter,
+    marker::PhantomData,
+    mem,
+    ops::{Add, Mul, Neg, Sub},
 };
 
 use halo2_proofs::halo2curves::ff::{PrimeField, WithSmallOrderMulGroup};
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 use tracing::*;
 
-use crate::{ff::Field, fft, util};
+use crate::{
+    ff::Field,
+    fft,
+    util::{self, serde_repr},
+};
+
+/// Max degree of the deserialized [`UnivariatePoly`]
+///
+/// Longer payloads are rejected before the coefficients are allocated, so a peer can't make the
+/// verifier allocate an arbitrary amount of memory
+pub const MAX_POLY_DEGREE: usize = 1 << 20;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
+pub enum DivisionError {
+    #[error("Vanishing polynomial of zero degree is zero, division is undefined")]
+    ZeroDegreeVanishing,
+    #[error("Division by vanishing polynomial of degree {degree} has non-zero remainder")]
+    NonZeroRemainder { degree: usize },
+}
 
 /// Represents a univariate polynomial
 ///
@@ -30,6 +54,11 @@ impl<F: Field> UnivariatePoly<F> {
 }
 
 impl<F> UnivariatePoly<F> {
+    /// Coefficients in ascending-degree order: `coeffs()[i]` is the coefficient of `X^i`
+    pub fn coeffs(&self) -> &[F] {
+        &self.0
+    }
+    /// Iterates over [`UnivariatePoly::coeffs`], from the constant term to the leading one
     pub fn iter(&self) -> impl Iterator<Item = &F> {
         self.0.iter()
     }
@@ -91,59 +120,364 @@ impl<F: Field> UnivariatePoly<F> {
         let scaled_coeffs: Vec<F> = self.iter().map(|&coeff| coeff * factor).collect();
         UnivariatePoly(scaled_coeffs.into_boxed_slice())
     }
+
+    /// Divides the polynomial by the vanishing polynomial `Z(X) = X^n - 1`
+    ///
+    /// Returns `(quotient, remainder)`, such that `self = quotient * Z(X) + remainder`.
+    /// The remainder always has exactly `n` coefficients.
+    ///
+    /// Synthetic division is used: going from the highest coefficient, each `a_i * X^i` is
+    /// replaced by `a_i * X^{i-n} * Z(X) + a_i * X^{i-n}`, so no FFT is required.
+    pub fn divide_by_vanishing(&self, n: usize) -> Result<(Self, Self), DivisionError> {
+        if n == 0 {
+            return Err(DivisionError::ZeroDegreeVanishing);
+        }
+
+        let mut remainder = self.0.to_vec();
+        let mut quotient = vec![F::ZERO; self.len().saturating_sub(n)];
+
+        for i in (n..remainder.len()).rev() {
+            let coeff = mem::replace(&mut remainder[i], F::ZERO);
+            quotient[i - n] = coeff;
+            remainder[i - n] += coeff;
+        }
+
+        remainder.resize(n, F::ZERO);
+
+        Ok((
+            UnivariatePoly(quotient.into_boxed_slice()),
+            UnivariatePoly(remainder.into_boxed_slice()),
+        ))
+    }
+
+    /// Divides the polynomial by the vanishing polynomial `Z(X) = X^n - 1`
+    ///
+    /// Unlike [`UnivariatePoly::divide_by_vanishing`] returns only the quotient and fails if the
+    /// remainder is not zero
+    pub fn divide_exact(&self, n: usize) -> Result<Self, DivisionError> {
+        let (quotient, remainder) = self.divide_by_vanishing(n)?;
+
+        if remainder.iter().any(|coeff| F::ZERO.ne(coeff)) {
+            return Err(DivisionError::NonZeroRemainder { degree: n });
+        }
+
+        Ok(quotient)
+    }
 }
 
-impl<F: Field> Mul<&UnivariatePoly<F>> for UnivariatePoly<F> {
-    type Output = UnivariatePoly<F>;
+/// Below this length of the shorter operand [`Mul`] uses the schoolbook multiplication
+///
+/// Karatsuba replaces one of four half-size products by a few additions of halves, which doesn't
+/// pay for itself (nor for its allocations) on tiny operands. Checked by `benches/poly_mul`
+const KARATSUBA_THRESHOLD: usize = 32;
 
-    fn mul(self, rhs: &UnivariatePoly<F>) -> UnivariatePoly<F> {
-        let new_len = self.len() + rhs.len() - 1;
-        let mut result = vec![F::ZERO; new_len];
+/// Above this length of the longer operand [`Mul`] uses FFT
+///
+/// FFT is `O(n log n)`, but it works over the power-of-two domain of the whole product and needs
+/// three transforms with their twiddles, so up to a few hundreds of coefficients Karatsuba's
+/// `O(n^1.58)` is cheaper. Checked by `benches/poly_mul`
+const FFT_THRESHOLD: usize = 256;
 
-        for (i, &a) in self.iter().enumerate() {
-            for (j, &b) in rhs.iter().enumerate() {
-                result[i + j] += a * b;
-            }
+/// Product of `lhs` & `rhs` with `lhs.len() + rhs.len() - 1` coefficients
+fn mul_schoolbook<F: Field>(lhs: &[F], rhs: &[F]) -> Vec<F> {
+    if lhs.is_empty() || rhs.is_empty() {
+        return vec![];
+    }
+
+    let mut result = vec![F::ZERO; lhs.len() + rhs.len() - 1];
+
+    for (i, &a) in lhs.iter().enumerate() {
+        for (j, &b) in rhs.iter().enumerate() {
+            result[i + j] += a * b;
         }
+    }
+
+    result
+}
+
+/// Sum of `lhs` & `rhs` with `max(lhs.len(), rhs.len())` coefficients
+fn add_coeffs<F: Field>(lhs: &[F], rhs: &[F]) -> Vec<F> {
+    let (longer, shorter) = if lhs.len() >= rhs.len() {
+        (lhs, rhs)
+    } else {
+        (rhs, lhs)
+    };
+
+    let mut result = longer.to_vec();
+    result
+        .iter_mut()
+        .zip(shorter)
+        .for_each(|(result, coeff)| *result += coeff);
+    result
+}
+
+/// Same as [`mul_schoolbook`], but splits operands in halves by `X^half`:
+///
+/// `(a_lo + a_hi X^half)(b_lo + b_hi X^half) = z0 + (z1 - z0 - z2) X^half + z2 X^{2 half}`,
+/// where `z0 = a_lo b_lo`, `z2 = a_hi b_hi` & `z1 = (a_lo + a_hi)(b_lo + b_hi)`
+fn mul_karatsuba<F: Field>(lhs: &[F], rhs: &[F]) -> Vec<F> {
+    if lhs.len().min(rhs.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(lhs, rhs);
+    }
+
+    let half = lhs.len().max(rhs.len()) / 2;
+    let (lhs_lo, lhs_hi) = lhs.split_at(half.min(lhs.len()));
+    let (rhs_lo, rhs_hi) = rhs.split_at(half.min(rhs.len()));
+
+    let z0 = mul_karatsuba(lhs_lo, rhs_lo);
+    let z2 = mul_karatsuba(lhs_hi, rhs_hi);
+    let z1 = mul_karatsuba(&add_coeffs(lhs_lo, lhs_hi), &add_coeffs(rhs_lo, rhs_hi));
+
+    let mut result = vec![F::ZERO; lhs.len() + rhs.len() - 1];
+
+    // Coefficients of `z1` beyond the product degree are zero, so `zip` loses nothing
+    let middle = &mut result[half..];
+    middle.iter_mut().zip(&z1).for_each(|(r, c)| *r += c);
+    middle.iter_mut().zip(&z0).for_each(|(r, c)| *r -= c);
+    middle.iter_mut().zip(&z2).for_each(|(r, c)| *r -= c);
+
+    result.iter_mut().zip(&z0).for_each(|(r, c)| *r += c);
+    result[2 * half..]
+        .iter_mut()
+        .zip(&z2)
+        .for_each(|(r, c)| *r += c);
 
-        // Skip trailing zeros using itertools
+    result
+}
+
+/// Same as [`mul_schoolbook`], but by pointwise multiplication of the operands' FFTs
+fn mul_fft<F: PrimeField>(lhs: &[F], rhs: &[F]) -> Vec<F> {
+    if lhs.is_empty() || rhs.is_empty() {
+        return vec![];
+    }
+
+    let len = lhs.len() + rhs.len() - 1;
+    let domain = len.next_power_of_two();
+
+    let evaluate = |coeffs: &[F]| {
+        let mut points = coeffs.to_vec();
+        points.resize(domain, F::ZERO);
+        fft::fft(&mut points);
+        points
+    };
+
+    let mut points = evaluate(lhs);
+    points
+        .iter_mut()
+        .zip(evaluate(rhs))
+        .for_each(|(lhs, rhs)| *lhs *= rhs);
+
+    fft::ifft(&mut points);
+    points.truncate(len);
+    points
+}
+
+impl<F: Field> UnivariatePoly<F> {
+    /// Polynomial of `coeffs` without trailing zeros
+    fn from_trimmed(mut coeffs: Vec<F>) -> Self {
         // Efficiently remove trailing zeros
-        let last_non_zero = result
+        let last_non_zero = coeffs
             .iter()
             .rposition(|&x| x != F::ZERO)
             .map_or(0, |pos| pos + 1);
 
-        result.truncate(last_non_zero);
+        coeffs.truncate(last_non_zero);
+
+        UnivariatePoly(coeffs.into_boxed_slice())
+    }
+}
+
+impl<F: Field> UnivariatePoly<F> {
+    /// Product by schoolbook multiplication, regardless of the size of operands
+    ///
+    /// [`Mul`] uses it only below [`KARATSUBA_THRESHOLD`], this one is for comparison of the
+    /// algorithms, e.g. in `benches/poly_mul`
+    pub fn mul_schoolbook(&self, rhs: &Self) -> Self {
+        Self::from_trimmed(mul_schoolbook(&self.0, &rhs.0))
+    }
 
-        UnivariatePoly(result.into_boxed_slice())
+    /// Product by Karatsuba multiplication, with schoolbook below [`KARATSUBA_THRESHOLD`] only
+    ///
+    /// [`Mul`] uses it only up to [`FFT_THRESHOLD`], this one is for comparison of the
+    /// algorithms, e.g. in `benches/poly_mul`
+    pub fn mul_karatsuba(&self, rhs: &Self) -> Self {
+        Self::from_trimmed(mul_karatsuba(&self.0, &rhs.0))
+    }
+}
+
+impl<F: PrimeField> UnivariatePoly<F> {
+    /// Product by pointwise multiplication of FFTs, regardless of the size of operands
+    ///
+    /// [`Mul`] switches to it only above [`FFT_THRESHOLD`], this one is for callers that know
+    /// their operands are large
+    pub fn mul_fft(&self, rhs: &Self) -> Self {
+        Self::from_trimmed(mul_fft(&self.0, &rhs.0))
     }
 }
 
-impl<F: Field> Add for UnivariatePoly<F> {
+/// Chooses the algorithm by the size of operands: schoolbook for tiny ones (see
+/// [`KARATSUBA_THRESHOLD`]), Karatsuba for medium ones & FFT for large ones (see
+/// [`FFT_THRESHOLD`]). All of them give the same product.
+impl<F: PrimeField> Mul<&UnivariatePoly<F>> for &UnivariatePoly<F> {
     type Output = UnivariatePoly<F>;
 
-    fn add(self, rhs: UnivariatePoly<F>) -> UnivariatePoly<F> {
-        let (longer, shorter) = if self.len() >= rhs.len() {
-            (self, rhs)
+    fn mul(self, rhs: &UnivariatePoly<F>) -> UnivariatePoly<F> {
+        let result = if self.len().min(rhs.len()) < KARATSUBA_THRESHOLD {
+            mul_schoolbook(&self.0, &rhs.0)
+        } else if self.len().max(rhs.len()) <= FFT_THRESHOLD {
+            mul_karatsuba(&self.0, &rhs.0)
         } else {
-            (rhs, self)
+            mul_fft(&self.0, &rhs.0)
         };
 
-        let mut result = longer.0.into_vec();
+        UnivariatePoly::from_trimmed(result)
+    }
+}
 
-        for (res_coeff, &short_coeff) in result.iter_mut().zip(shorter.iter()) {
-            *res_coeff += short_coeff;
+impl<F: PrimeField> Mul<&UnivariatePoly<F>> for UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn mul(self, rhs: &UnivariatePoly<F>) -> UnivariatePoly<F> {
+        &self * rhs
+    }
+}
+
+impl<F: PrimeField> Mul for UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn mul(self, rhs: UnivariatePoly<F>) -> UnivariatePoly<F> {
+        &self * &rhs
+    }
+}
+
+impl<F: Field> UnivariatePoly<F> {
+    /// Applies `op` to coefficients of `self` & `rhs` of the same degree, reusing the buffer of
+    /// `self` extended with zeros up to `rhs.len()`
+    fn zip_coeffs_with(self, rhs: &Self, op: impl Fn(&mut F, F)) -> Self {
+        let mut result = self.0.into_vec();
+        if result.len() < rhs.len() {
+            result.resize(rhs.len(), F::ZERO);
         }
 
-        // Efficiently remove trailing zeros
-        let last_non_zero = result
-            .iter()
-            .rposition(|&x| x != F::ZERO)
-            .map_or(0, |pos| pos + 1);
+        for (res_coeff, &rhs_coeff) in result.iter_mut().zip(rhs.iter()) {
+            op(res_coeff, rhs_coeff);
+        }
 
-        result.truncate(last_non_zero);
+        UnivariatePoly::from_trimmed(result)
+    }
+}
+
+impl<F: Field> Add<&UnivariatePoly<F>> for UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn add(self, rhs: &UnivariatePoly<F>) -> UnivariatePoly<F> {
+        self.zip_coeffs_with(rhs, |res, rhs| *res += rhs)
+    }
+}
+
+impl<F: Field> Sub<&UnivariatePoly<F>> for UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn sub(self, rhs: &UnivariatePoly<F>) -> UnivariatePoly<F> {
+        self.zip_coeffs_with(rhs, |res, rhs| *res -= rhs)
+    }
+}
+
+/// Implements `owned op owned` & `&lhs op &rhs` through `owned op &rhs`
+macro_rules! forward_binop {
+    ($trait:ident, $method:ident) => {
+        impl<F: Field> $trait for UnivariatePoly<F> {
+            type Output = UnivariatePoly<F>;
 
-        UnivariatePoly(result.into_boxed_slice())
+            fn $method(self, rhs: UnivariatePoly<F>) -> UnivariatePoly<F> {
+                <Self as $trait<&UnivariatePoly<F>>>::$method(self, &rhs)
+            }
+        }
+
+        impl<F: Field> $trait<&UnivariatePoly<F>> for &UnivariatePoly<F> {
+            type Output = UnivariatePoly<F>;
+
+            fn $method(self, rhs: &UnivariatePoly<F>) -> UnivariatePoly<F> {
+                <UnivariatePoly<F> as $trait<&UnivariatePoly<F>>>::$method(self.clone(), rhs)
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add);
+forward_binop!(Sub, sub);
+
+impl<F: Field> Neg for UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn neg(mut self) -> UnivariatePoly<F> {
+        self.0.iter_mut().for_each(|coeff| *coeff = -*coeff);
+        self
+    }
+}
+
+impl<F: Field> Neg for &UnivariatePoly<F> {
+    type Output = UnivariatePoly<F>;
+
+    fn neg(self) -> UnivariatePoly<F> {
+        -self.clone()
+    }
+}
+
+/// Sum of polynomials of any lengths, the sum of an empty iterator is the zero polynomial
+impl<F: Field> iter::Sum for UnivariatePoly<F> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(Box::new([])), |acc, poly| acc + poly)
+    }
+}
+
+impl<'l, F: Field> iter::Sum<&'l UnivariatePoly<F>> for UnivariatePoly<F> {
+    fn sum<I: Iterator<Item = &'l Self>>(iter: I) -> Self {
+        iter.fold(Self(Box::new([])), |acc, poly| acc + poly)
+    }
+}
+
+/// Sequence of coefficients, each of them encoded as in [`serde_repr::field`]
+impl<F: PrimeField> Serialize for UnivariatePoly<F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_repr::fields::serialize(&self.0, serializer)
+    }
+}
+
+/// Rejects polynomials with degree above [`MAX_POLY_DEGREE`]
+impl<'de, F: PrimeField> Deserialize<'de> for UnivariatePoly<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CoeffsVisitor<F>(PhantomData<F>);
+
+        impl<'de, F: PrimeField> Visitor<'de> for CoeffsVisitor<F> {
+            type Value = UnivariatePoly<F>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "at most {} coefficients", MAX_POLY_DEGREE + 1)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let max_len = MAX_POLY_DEGREE + 1;
+
+                // Binary formats know the length in advance, so nothing is read at all
+                let len_hint = seq.size_hint().unwrap_or_default();
+                if len_hint > max_len {
+                    return Err(de::Error::invalid_length(len_hint, &self));
+                }
+
+                let mut coeffs = Vec::with_capacity(len_hint);
+                while let Some(serde_repr::FieldOwned(coeff)) = seq.next_element()? {
+                    if coeffs.len() == max_len {
+                        return Err(de::Error::invalid_length(max_len + 1, &self));
+                    }
+                    coeffs.push(coeff);
+                }
+
+                Ok(UnivariatePoly(coeffs.into_boxed_slice()))
+            }
+        }
+
+        deserializer.deserialize_seq(CoeffsVisitor(PhantomData))
     }
 }
 
@@ -168,9 +502,14 @@ impl<F: PrimeField> UnivariatePoly<F> {
 }
 
 impl<F: WithSmallOrderMulGroup<3>> UnivariatePoly<F> {
-    pub fn coset_fft(mut self) -> Box<[F]> {
-        fft::coset_fft(self.as_mut());
-        self.0
+    /// Evaluates the poly over the coset `ZETA * subgroup` of `2^log_domain` points
+    ///
+    /// The poly is padded with zeros to the domain, `None` if it has more coefficients than the
+    /// domain has points. [`UnivariatePoly::coset_ifft`] of the result gives the padded poly back
+    pub fn coset_fft(&self, log_domain: u32) -> Option<Box<[F]>> {
+        let mut evaluations = self.clone().pad_with_zeroes(1 << log_domain).ok()?.0;
+        fft::coset_fft(&mut evaluations);
+        Some(evaluations)
     }
 
     pub fn coset_ifft(mut input: Box<[F]>) -> Self {
@@ -183,8 +522,8 @@ impl<F: WithSmallOrderMulGroup<3>> UnivariatePoly<F> {
 mod tests {
     use std::iter;
 
-    use super::UnivariatePoly;
-    use crate::halo2curves::bn256::Fr;
+    use super::{DivisionError, UnivariatePoly};
+    use crate::{ff::Field, halo2curves::bn256::Fr};
 
     // Helper to create an `Fr` iterator from a `u64` iterator
     trait ToF<I: Into<Fr>>: Sized + IntoIterator<Item = I> {
@@ -194,6 +533,36 @@ mod tests {
     }
     impl<I: Into<Fr>, ITER: Sized + IntoIterator<Item = I>> ToF<I> for ITER {}
 
+    #[test]
+    fn coeffs_order() {
+        let x = Fr::from(3);
+
+        for degree in 0..4 {
+            let monomial =
+                UnivariatePoly::from_iter(iter::repeat(0).take(degree).chain(iter::once(1)).to_f());
+
+            assert_eq!(monomial.coeffs()[degree], 1.into());
+            assert_eq!(monomial.eval(x), x.pow([degree as u64]));
+        }
+    }
+
+    #[test]
+    fn coset_fft_round_trip() {
+        use crate::{group::ff::WithSmallOrderMulGroup, polynomial::lagrange};
+
+        const LOG_N: u32 = 4;
+
+        let poly = UnivariatePoly::from_iter((0..1 << LOG_N).to_f());
+
+        let evaluations = poly.coset_fft(LOG_N).unwrap();
+        lagrange::iter_cyclic_subgroup::<Fr>(LOG_N)
+            .zip(evaluations.iter())
+            .for_each(|(X, value)| assert_eq!(poly.eval(Fr::ZETA * X), *value));
+
+        assert_eq!(UnivariatePoly::coset_ifft(evaluations), poly);
+        assert_eq!(poly.coset_fft(LOG_N - 1), None);
+    }
+
     #[test]
     fn test_constant_polynomial() {
         assert_eq!(
@@ -298,6 +667,163 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mul_thresholds() {
+        use super::{mul_fft, mul_karatsuba, mul_schoolbook, FFT_THRESHOLD, KARATSUBA_THRESHOLD};
+
+        let mut rnd = rand::thread_rng();
+        let mut random_poly = |len: usize| {
+            UnivariatePoly::from_iter(iter::repeat_with(|| Fr::random(&mut rnd)).take(len))
+        };
+
+        for (lhs_len, rhs_len) in [
+            (KARATSUBA_THRESHOLD - 1, KARATSUBA_THRESHOLD - 1),
+            (KARATSUBA_THRESHOLD - 1, FFT_THRESHOLD + 1),
+            (KARATSUBA_THRESHOLD, KARATSUBA_THRESHOLD),
+            (KARATSUBA_THRESHOLD, FFT_THRESHOLD),
+            (KARATSUBA_THRESHOLD + 1, 2 * KARATSUBA_THRESHOLD + 3),
+            (FFT_THRESHOLD, FFT_THRESHOLD),
+            (FFT_THRESHOLD, FFT_THRESHOLD + 1),
+            (FFT_THRESHOLD + 1, KARATSUBA_THRESHOLD),
+            (FFT_THRESHOLD + 1, FFT_THRESHOLD + 1),
+        ] {
+            let lhs = random_poly(lhs_len);
+            let rhs = random_poly(rhs_len);
+
+            let expected = mul_schoolbook(&lhs.0, &rhs.0);
+            assert_eq!(expected.len(), lhs_len + rhs_len - 1);
+            assert_eq!(
+                mul_karatsuba(&lhs.0, &rhs.0),
+                expected,
+                "{lhs_len}x{rhs_len}"
+            );
+            assert_eq!(mul_fft(&lhs.0, &rhs.0), expected, "{lhs_len}x{rhs_len}");
+            assert_eq!(
+                lhs * &rhs,
+                UnivariatePoly(expected.into_boxed_slice()),
+                "{lhs_len}x{rhs_len}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sub_neg_sum_polynomials() {
+        let poly1 = UnivariatePoly::from_iter([3, 2, 1].to_f());
+        let poly2 = UnivariatePoly::from_iter([1, 2].to_f());
+
+        assert_eq!(&poly1 - &poly2, UnivariatePoly::from_iter([2, 0, 1].to_f()));
+        assert_eq!(
+            poly2.clone() - poly2.clone(),
+            UnivariatePoly::from_iter(iter::empty())
+        );
+        assert_eq!(&poly1 + &-&poly2, &poly1 - &poly2);
+
+        assert_eq!(
+            [poly1.clone(), poly2.clone(), poly1.clone()]
+                .iter()
+                .sum::<UnivariatePoly<Fr>>(),
+            UnivariatePoly::from_iter([7, 6, 2].to_f())
+        );
+        assert_eq!(
+            iter::empty::<UnivariatePoly<Fr>>().sum::<UnivariatePoly<Fr>>(),
+            UnivariatePoly::from_iter(iter::empty())
+        );
+        assert_eq!(poly1.mul_fft(&poly2), &poly1 * &poly2);
+    }
+
+    #[cfg(feature = "proptest")]
+    mod arithmetic {
+        use proptest::{collection::vec, prelude::*};
+
+        use super::*;
+        use crate::{
+            polynomial::univariate::{FFT_THRESHOLD, KARATSUBA_THRESHOLD},
+            testing::strategies::{field, univariate_poly},
+        };
+
+        /// Polynomial with length in the range of schoolbook, Karatsuba or FFT product
+        fn poly_of_any_mul_range() -> impl Strategy<Value = UnivariatePoly<Fr>> {
+            prop_oneof![
+                1..=KARATSUBA_THRESHOLD - 1,
+                KARATSUBA_THRESHOLD..=FFT_THRESHOLD,
+                FFT_THRESHOLD + 1..=2 * FFT_THRESHOLD,
+            ]
+            .prop_flat_map(|len| vec(field::<Fr>(), len))
+            .prop_map(|coeffs| UnivariatePoly(coeffs.into_boxed_slice()))
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(32))]
+
+            #[test]
+            fn add_evaluates_to_sum(
+                a in poly_of_any_mul_range(),
+                b in poly_of_any_mul_range(),
+                x in field::<Fr>(),
+            ) {
+                prop_assert_eq!((&a + &b).eval(x), a.eval(x) + b.eval(x));
+                prop_assert_eq!((a.clone() + b.clone()).eval(x), a.eval(x) + b.eval(x));
+                prop_assert_eq!((a.clone() - &b).eval(x), a.eval(x) - b.eval(x));
+                prop_assert_eq!((&a - &b).eval(x), a.eval(x) - b.eval(x));
+            }
+
+            #[test]
+            fn mul_evaluates_to_product(
+                a in poly_of_any_mul_range(),
+                b in poly_of_any_mul_range(),
+                x in field::<Fr>(),
+            ) {
+                prop_assert_eq!((&a * &b).eval(x), a.eval(x) * b.eval(x));
+                prop_assert_eq!(a.mul_fft(&b).eval(x), a.eval(x) * b.eval(x));
+            }
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(50))]
+
+            #[test]
+            fn coset_fft_then_ifft_is_identity(
+                poly in univariate_poly::<Fr>(15),
+                log_domain in 4u32..=6,
+            ) {
+                let evaluations = poly.coset_fft(log_domain).unwrap();
+                prop_assert_eq!(evaluations.len(), 1 << log_domain);
+
+                prop_assert_eq!(
+                    UnivariatePoly::coset_ifft(evaluations),
+                    poly.pad_with_zeroes(1 << log_domain).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let poly = UnivariatePoly::from_iter([Fr::from(3), -Fr::ONE, Fr::ZERO, Fr::from(7)]);
+
+        let bytes = bincode::serialize(&poly).unwrap();
+        assert_eq!(bytes.len(), 8 + poly.len() * 32);
+        assert_eq!(
+            bincode::deserialize::<UnivariatePoly<Fr>>(&bytes).unwrap(),
+            poly
+        );
+
+        let json = serde_json::to_string(&poly).unwrap();
+        assert_eq!(
+            serde_json::from_str::<UnivariatePoly<Fr>>(&json).unwrap(),
+            poly
+        );
+    }
+
+    #[test]
+    fn serde_rejects_too_long() {
+        use super::MAX_POLY_DEGREE;
+
+        // Only the length prefix, coefficients must not be read
+        let bytes = bincode::serialize(&((MAX_POLY_DEGREE + 2) as u64)).unwrap();
+        assert!(bincode::deserialize::<UnivariatePoly<Fr>>(&bytes).is_err());
+    }
+
     #[test]
     fn test_resize_polynomial_larger() {
         let poly = UnivariatePoly::from_iter((0..3).map(Fr::from));
@@ -341,4 +867,48 @@ mod tests {
         let expected = UnivariatePoly::from_iter((0..3).map(|x| Fr::from(x) * factor)); // Polynomial: 0 + 2*x + 4*x^2
         assert_eq!(scaled, expected, "Scaling polynomial failed.");
     }
+
+    #[test]
+    fn test_divide_by_vanishing() {
+        // (X^3 + 2X^2 - X - 2) + (X + 3) = (X^2 - 1)(X + 2) + (X + 3)
+        let poly = UnivariatePoly::from_iter([1, 0, 2, 1].to_f());
+
+        assert_eq!(
+            poly.divide_by_vanishing(2),
+            Ok((
+                UnivariatePoly::from_iter([2, 1].to_f()),
+                UnivariatePoly::from_iter([3, 1].to_f()),
+            ))
+        );
+        assert_eq!(
+            poly.divide_exact(2),
+            Err(DivisionError::NonZeroRemainder { degree: 2 })
+        );
+    }
+
+    #[test]
+    fn test_divide_exact() {
+        let vanishing = UnivariatePoly::from_iter([-Fr::from(1), 0.into(), 0.into(), 1.into()]);
+        let quotient = UnivariatePoly::from_iter([7, 5, 3].to_f());
+
+        assert_eq!(
+            (quotient.clone() * &vanishing).divide_exact(3),
+            Ok(quotient)
+        );
+    }
+
+    #[test]
+    fn test_divide_by_vanishing_short_poly() {
+        assert_eq!(
+            UnivariatePoly::from_iter([5].to_f()).divide_by_vanishing(2),
+            Ok((
+                UnivariatePoly::from_iter(iter::empty()),
+                UnivariatePoly::from_iter([5, 0].to_f()),
+            ))
+        );
+        assert_eq!(
+            UnivariatePoly::from_iter([5].to_f()).divide_by_vanishing(0),
+            Err(DivisionError::ZeroDegreeVanishing)
+        );
+    }
 }
@@ -1,4 +1,7 @@
+use std::{convert::Infallible, marker::PhantomData};
+
 use halo2_proofs::arithmetic::CurveAffine;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 
 use crate::{
     commitment,
@@ -18,45 +21,401 @@ pub enum Error {
     LackOfLookupArguments,
     #[error("Lack of advices, should call `TableData::assembly` first")]
     LackOfAdvices,
+    /// Only the prover side is limited: [`crate::plonk::PlonkStructure::run_sps_protocol`] knows
+    /// rounds of protostar, while [`SpecialSoundnessVerifier`] accepts any count
     #[error("Only 0..=3 num of challenges supported: {challenges_count} not")]
     UnsupportedChallengesCount { challenges_count: usize },
+    #[error(
+        "Instance has {challenges_count} challenges, but only {commitments_count} commitments"
+    )]
+    LackOfCommitments {
+        challenges_count: usize,
+        commitments_count: usize,
+    },
+    /// Commitments after the last challenge are not absorbed, so nothing binds them
+    #[error("Instance has {challenges_count} challenges, but {commitments_count} commitments")]
+    UnboundCommitments {
+        challenges_count: usize,
+        commitments_count: usize,
+    },
     #[error("Error while commit {annotation} with err: {err:?}")]
     WrongCommitmentSize {
         annotation: &'static str,
         err: commitment::Error,
     },
+    /// Accumulator has fewer betas than `pow_i` of its structure needs
+    #[error("Accumulator has {betas_count} betas, but structure needs {expected}")]
+    LackOfBetas { betas_count: usize, expected: usize },
+    #[error("Value of {annotation} can't be represented in the base field")]
+    NotInBaseField { annotation: &'static str },
+}
+
+/// Random oracle side of the sps transcript
+///
+/// Implemented by [`OffCircuitSps`] over [`ROTrait`] & by the on-circuit verifier over
+/// [`crate::poseidon::ROCircuitTrait`], so both derive challenges with [`sps_challenges`]
+pub trait SpsAbsorber {
+    type Instance;
+    type Commitment;
+    type Challenge;
+    type Error;
+
+    fn absorb_instance(&mut self, instance: &Self::Instance);
+
+    fn absorb_commitment(&mut self, commitment: &Self::Commitment);
+
+    /// Squeezes a challenge of [`NUM_CHALLENGE_BITS`]
+    fn squeeze_challenge(&mut self) -> Result<Self::Challenge, Self::Error>;
+}
+
+/// Canonical absorption schedule of the sps transcript
+///
+/// Nothing is absorbed for an instance without challenges. Otherwise all instance columns are
+/// absorbed flattened in order, then the commitment of each round is absorbed before its
+/// challenge is squeezed. Commitments after the last challenge are not absorbed.
+pub fn sps_challenges<A: SpsAbsorber>(
+    absorber: &mut A,
+    instances: &[Vec<A::Instance>],
+    W_commitments: &[A::Commitment],
+    num_challenges: usize,
+) -> Result<Vec<A::Challenge>, A::Error> {
+    if num_challenges == 0 {
+        return Ok(vec![]);
+    }
+
+    instances
+        .iter()
+        .flatten()
+        .for_each(|instance| absorber.absorb_instance(instance));
+
+    W_commitments
+        .iter()
+        .take(num_challenges)
+        .map(|W| {
+            absorber.absorb_commitment(W);
+            absorber.squeeze_challenge()
+        })
+        .collect()
+}
+
+/// [`SpsAbsorber`] over off-circuit [`ROTrait`]
+pub struct OffCircuitSps<'r, C: CurveAffine, RO: ROTrait<C::Base>> {
+    ro_nark: &'r mut RO,
+    _p: PhantomData<C>,
+}
+
+impl<'r, C: CurveAffine, RO: ROTrait<C::Base>> OffCircuitSps<'r, C, RO> {
+    pub fn new(ro_nark: &'r mut RO) -> Self {
+        Self {
+            ro_nark,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<C: CurveAffine, RO: ROTrait<C::Base>> SpsAbsorber for OffCircuitSps<'_, C, RO> {
+    type Instance = C::ScalarExt;
+    type Commitment = C;
+    type Challenge = C::ScalarExt;
+    type Error = Infallible;
+
+    fn absorb_instance(&mut self, instance: &C::ScalarExt) {
+        self.ro_nark
+            .absorb_field(C::scalar_to_base(instance).unwrap());
+    }
+
+    fn absorb_commitment(&mut self, commitment: &C) {
+        self.ro_nark.absorb_point(commitment);
+    }
+
+    fn squeeze_challenge(&mut self) -> Result<C::ScalarExt, Infallible> {
+        Ok(self.ro_nark.squeeze::<C>(NUM_CHALLENGE_BITS))
+    }
 }
 
 /// This trait verifies whether the instance is faithly generated by a Special soundness protocol (sps)
 /// Reference: section 3.1 of [protostar](https://eprint.iacr.org/2023/620)
+///
+/// Each challenge is squeezed after absorbing the commitment of its round, so any number of
+/// rounds is supported, as long as each challenge has exactly one commitment. The same rules are
+/// checked on-circuit by `ivc::protogalaxy::verify_chip::verify_sps`
 pub trait SpecialSoundnessVerifier<C: CurveAffine, RO: ROTrait<C::Base>> {
+    /// Derives challenges of the instance from `ro_nark` without comparing them with the claimed
+    /// ones, useful to inspect a mismatch
+    ///
+    /// Stops at the last commitment if there are fewer commitments than challenges. By default
+    /// no challenges are derived & `ro_nark` is untouched, so existing implementors keep compiling
+    fn sps_derive_challenges(&self, _ro_nark: &mut RO) -> Vec<C::ScalarExt> {
+        vec![]
+    }
+
     fn sps_verify(&self, ro_nark: &mut RO) -> Result<(), Error>;
+
+    /// Same as [`SpecialSoundnessVerifier::sps_verify`], but challenges are compared with
+    /// [`ConstantTimeEq`] & all of them are checked without an early exit
+    ///
+    /// In case of mismatch, the index of the first mismatched challenge is returned. By default
+    /// falls back to [`SpecialSoundnessVerifier::sps_verify`], so it's constant-time only for
+    /// implementors that override it, as [`PlonkInstance`] does
+    fn sps_verify_ct(&self, ro_nark: &mut RO) -> Result<(), Error> {
+        self.sps_verify(ro_nark)
+    }
 }
 
 impl<C: CurveAffine, RO: ROTrait<C::Base>> SpecialSoundnessVerifier<C, RO> for PlonkInstance<C> {
+    fn sps_derive_challenges(&self, ro_nark: &mut RO) -> Vec<C::ScalarExt> {
+        let Ok(challenges) = sps_challenges(
+            &mut OffCircuitSps::<C, RO>::new(ro_nark),
+            &self.instances,
+            &self.W_commitments,
+            self.challenges.len(),
+        );
+
+        challenges
+    }
+
     fn sps_verify(&self, ro_nark: &mut RO) -> Result<(), Error> {
-        let num_challenges = self.challenges.len();
+        self.check_commitments_count()?;
+
+        match self
+            .sps_derive_challenges(ro_nark)
+            .iter()
+            .zip(self.challenges.iter())
+            .position(|(derived, claimed)| derived.ne(claimed))
+        {
+            Some(challenge_index) => Err(Error::ChallengeNotMatch { challenge_index }),
+            None => Ok(()),
+        }
+    }
+
+    fn sps_verify_ct(&self, ro_nark: &mut RO) -> Result<(), Error> {
+        self.check_commitments_count()?;
+
+        let derived = self.sps_derive_challenges(ro_nark);
+
+        let mut is_mismatch = Choice::from(0);
+        let mut challenge_index = 0u64;
+
+        for (i, (derived, challenge)) in derived.iter().zip(self.challenges.iter()).enumerate() {
+            let is_ne = !derived.ct_eq(challenge);
+
+            challenge_index =
+                u64::conditional_select(&challenge_index, &(i as u64), is_ne & !is_mismatch);
+            is_mismatch |= is_ne;
+        }
+
+        if bool::from(is_mismatch) {
+            Err(Error::ChallengeNotMatch {
+                challenge_index: challenge_index as usize,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Verifies `instances` one after another with the same `ro_nark`
+///
+/// As in [`crate::plonk::PlonkStructure::run_sps_protocol`] called for several traces with one
+/// random oracle, the transcript of each instance continues the transcript of the previous one.
+/// Verification stops at the first invalid instance, since the transcripts after it can't match.
+///
+/// In case of error, the index of the invalid instance is returned with the error
+pub fn sps_verify_batch<'l, C: CurveAffine, RO: ROTrait<C::Base>>(
+    instances: impl IntoIterator<Item = &'l PlonkInstance<C>>,
+    ro_nark: &mut RO,
+) -> Result<(), (usize, Error)> {
+    instances
+        .into_iter()
+        .enumerate()
+        .try_for_each(|(index, instance)| instance.sps_verify(ro_nark).map_err(|err| (index, err)))
+}
 
-        if num_challenges == 0 {
-            return Ok(());
+impl<C: CurveAffine> PlonkInstance<C> {
+    fn check_commitments_count(&self) -> Result<(), Error> {
+        if self.W_commitments.len() < self.challenges.len() {
+            return Err(Error::LackOfCommitments {
+                challenges_count: self.challenges.len(),
+                commitments_count: self.W_commitments.len(),
+            });
         }
 
+        if !self.challenges.is_empty() && self.W_commitments.len() > self.challenges.len() {
+            return Err(Error::UnboundCommitments {
+                challenges_count: self.challenges.len(),
+                commitments_count: self.W_commitments.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::halo2curves::{bn256::G1Affine as Affine, group::prime::PrimeCurveAffine};
+
+    use super::*;
+    use crate::{
+        ff::Field,
+        poseidon::{PoseidonHash, Spec},
+    };
+
+    type Scalar = <Affine as CurveAffine>::ScalarExt;
+    type Base = <Affine as CurveAffine>::Base;
+
+    fn ro() -> PoseidonHash<Base, 3, 2> {
+        PoseidonHash::new(Spec::new(4, 3))
+    }
+
+    fn instance() -> PlonkInstance<Affine> {
+        instance_with_challenges(3)
+    }
+
+    fn instance_with_challenges(challenges_count: u64) -> PlonkInstance<Affine> {
+        instance_in_transcript(&mut ro(), challenges_count)
+    }
+
+    /// Instance with challenges derived from `ro_nark`, which continues its transcript
+    fn instance_in_transcript(
+        ro_nark: &mut PoseidonHash<Base, 3, 2>,
+        challenges_count: u64,
+    ) -> PlonkInstance<Affine> {
+        let instances = vec![
+            vec![Scalar::from(1), Scalar::from(2)],
+            vec![Scalar::from(3)],
+        ];
+        let W_commitments = (1..=challenges_count)
+            .map(|i| (Affine::generator() * Scalar::from(i)).into())
+            .collect::<Vec<Affine>>();
+
         ro_nark.absorb_field_iter(
-            self.instances
+            instances
                 .iter()
-                .flat_map(|inst| inst.iter())
-                .map(|val| C::scalar_to_base(val).unwrap()),
+                .flatten()
+                .map(|val| Affine::scalar_to_base(val).unwrap()),
         );
+        let challenges = W_commitments
+            .iter()
+            .map(|W| {
+                ro_nark
+                    .absorb_point(W)
+                    .squeeze::<Affine>(NUM_CHALLENGE_BITS)
+            })
+            .collect();
 
-        for i in 0..num_challenges {
-            if ro_nark
-                .absorb_point(&self.W_commitments[i])
-                .squeeze::<C>(NUM_CHALLENGE_BITS)
-                .ne(&self.challenges[i])
-            {
-                return Err(Error::ChallengeNotMatch { challenge_index: i });
-            }
+        PlonkInstance {
+            W_commitments,
+            instances,
+            challenges,
         }
-        Ok(())
+    }
+
+    #[test]
+    fn derive_challenges() {
+        let valid = instance();
+        assert_eq!(valid.sps_derive_challenges(&mut ro()), valid.challenges);
+
+        let mut invalid = valid.clone();
+        invalid.challenges[1] += Scalar::ONE;
+        assert_eq!(invalid.sps_derive_challenges(&mut ro()), valid.challenges);
+    }
+
+    #[test]
+    fn constant_time_verify() {
+        let valid = instance();
+        assert_eq!(valid.sps_verify_ct(&mut ro()), Ok(()));
+
+        for tampered in [1, 2] {
+            let mut invalid = valid.clone();
+            invalid.challenges[tampered] += Scalar::ONE;
+            invalid.challenges[2] += Scalar::ONE;
+
+            let expected = Err(Error::ChallengeNotMatch {
+                challenge_index: tampered,
+            });
+            assert_eq!(invalid.sps_verify_ct(&mut ro()), expected);
+            assert_eq!(invalid.sps_verify(&mut ro()), expected);
+        }
+    }
+
+    /// Implementor with only `sps_verify`, as ones written before the other methods were added
+    struct OnlyVerify(PlonkInstance<Affine>);
+
+    impl SpecialSoundnessVerifier<Affine, PoseidonHash<Base, 3, 2>> for OnlyVerify {
+        fn sps_verify(&self, ro_nark: &mut PoseidonHash<Base, 3, 2>) -> Result<(), Error> {
+            self.0.sps_verify(ro_nark)
+        }
+    }
+
+    #[test]
+    fn default_methods() {
+        let valid = OnlyVerify(instance());
+        assert_eq!(valid.sps_derive_challenges(&mut ro()), vec![]);
+        assert_eq!(valid.sps_verify_ct(&mut ro()), Ok(()));
+
+        let mut invalid = OnlyVerify(instance());
+        invalid.0.challenges[1] += Scalar::ONE;
+        assert_eq!(
+            invalid.sps_verify_ct(&mut ro()),
+            Err(Error::ChallengeNotMatch { challenge_index: 1 })
+        );
+    }
+
+    #[test]
+    fn verify_batch() {
+        let mut ro_nark = ro();
+        let mut instances = (0..4)
+            .map(|_| instance_in_transcript(&mut ro_nark, 3))
+            .collect::<Vec<_>>();
+        assert_eq!(sps_verify_batch(&instances, &mut ro()), Ok(()));
+
+        // The same instance in another transcript position has other challenges
+        assert_ne!(instances[0].challenges, instances[1].challenges);
+
+        instances[3].challenges[2] += Scalar::ONE;
+        assert_eq!(
+            sps_verify_batch(&instances, &mut ro()),
+            Err((3, Error::ChallengeNotMatch { challenge_index: 2 }))
+        );
+    }
+
+    #[test]
+    fn four_challenges() {
+        let valid = instance_with_challenges(4);
+        assert_eq!(valid.challenges.len(), 4);
+        assert_eq!(valid.sps_derive_challenges(&mut ro()), valid.challenges);
+        assert_eq!(valid.sps_verify(&mut ro()), Ok(()));
+        assert_eq!(valid.sps_verify_ct(&mut ro()), Ok(()));
+
+        let mut invalid = valid.clone();
+        invalid.challenges[3] += Scalar::ONE;
+        let expected = Err(Error::ChallengeNotMatch { challenge_index: 3 });
+        assert_eq!(invalid.sps_verify(&mut ro()), expected);
+        assert_eq!(invalid.sps_verify_ct(&mut ro()), expected);
+
+        let mut truncated = valid.clone();
+        truncated.W_commitments.pop();
+        let expected = Err(Error::LackOfCommitments {
+            challenges_count: 4,
+            commitments_count: 3,
+        });
+        assert_eq!(truncated.sps_verify(&mut ro()), expected);
+        assert_eq!(truncated.sps_verify_ct(&mut ro()), expected);
+
+        let mut extended = valid.clone();
+        extended.W_commitments.push(Affine::generator());
+        let expected = Err(Error::UnboundCommitments {
+            challenges_count: 4,
+            commitments_count: 5,
+        });
+        assert_eq!(extended.sps_verify(&mut ro()), expected);
+        assert_eq!(extended.sps_verify_ct(&mut ro()), expected);
+
+        // Identity commitments are valid, e.g. of an all-zero round
+        let mut with_identity = valid.clone();
+        with_identity.W_commitments[0] = Affine::identity();
+        with_identity.challenges = with_identity.sps_derive_challenges(&mut ro());
+        assert_eq!(with_identity.sps_verify(&mut ro()), Ok(()));
     }
 }
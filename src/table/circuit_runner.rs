@@ -33,7 +33,7 @@ impl<F: PrimeField, CT: Circuit<F>> CircuitRunner<F, CT> {
     }
 
     #[instrument(name = "circuit_collect_plonk_struct", skip_all)]
-    pub fn try_collect_plonk_structure(&self) -> Result<PlonkStructure<F>, Error> {
+    pub fn try_collect_plonk_structure(&self) -> Result<PlonkStructure<F>, crate::Error> {
         debug!("start build metainfo");
         let ConstraintSystemMetainfo {
             num_challenges,
@@ -64,11 +64,12 @@ impl<F: PrimeField, CT: Circuit<F>> CircuitRunner<F, CT> {
             gates,
             permutation_data,
             lookup_arguments: plonk::lookup::Arguments::compress_from(&self.cs),
+            gates_cache: Default::default(),
         })
     }
 
     #[instrument(name = "circuit_collect_witness", skip_all)]
-    pub fn try_collect_witness(&self) -> Result<Witness<F>, Error> {
+    pub fn try_collect_witness(&self) -> Result<Witness<F>, crate::Error> {
         let mut witness = WitnessCollector {
             instances: self.instances.clone(),
             advice: vec![vec![F::ZERO.into(); 1 << self.k]; self.cs.num_advice_columns()],
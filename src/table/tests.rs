@@ -70,7 +70,7 @@ impl<F: PrimeField + FromUniformBytes<64>> Circuit<F> for TestCircuit<F> {
 
 #[traced_test]
 #[test]
-fn test_assembly() -> Result<(), Error> {
+fn test_assembly() -> Result<(), crate::Error> {
     use crate::halo2curves::pasta::Fp;
 
     const K: u32 = 4;
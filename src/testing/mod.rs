@@ -0,0 +1,5 @@
+//! Helpers for testing & benchmarking code built on top of sirius
+
+#[cfg(feature = "proptest")]
+pub mod strategies;
+pub mod synthetic;
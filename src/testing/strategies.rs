@@ -0,0 +1,274 @@
+//! [`proptest`] strategies for the core types
+//!
+//! All strategies are parameterized by [`Shape`], so they can be composed with
+//! [`Strategy::prop_flat_map`]:
+//!
+//! ```ignore
+//! shape().prop_flat_map(|shape| plonk_instance::<C>(&shape))
+//! ```
+//!
+//! Vector lengths are taken from the [`Shape`], so shrinking of [`shape`] reduces the lengths of
+//! all generated vectors, and shrinking of [`field`] reduces values towards zero.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::{
+    ff::PrimeField,
+    group::prime::PrimeCurveAffine,
+    halo2_proofs::arithmetic::{best_multiexp, CurveAffine},
+    nifs::protogalaxy::{poly::PolyChallenges, Accumulator, AccumulatorInstance},
+    plonk::{PlonkInstance, PlonkStructure, PlonkTrace, PlonkWitness},
+    polynomial::univariate::UnivariatePoly,
+};
+
+/// Sizes of the generated [`PlonkInstance`], [`PlonkWitness`] & [`PlonkStructure`]
+#[derive(Debug, Clone)]
+pub struct Shape {
+    /// `2^k` is the number of rows
+    pub k: usize,
+    /// Instance columns lengths
+    pub num_io: Box<[usize]>,
+    pub num_advice_columns: usize,
+    /// See [`PlonkInstance::challenges`]
+    pub num_challenges: usize,
+}
+
+impl Shape {
+    /// Number of prover rounds, one witness commitment per round
+    pub fn rounds(&self) -> usize {
+        self.num_challenges.max(1)
+    }
+
+    pub fn round_sizes(&self) -> Vec<usize> {
+        vec![self.num_advice_columns << self.k; self.rounds()]
+    }
+
+    /// Gate-less [`PlonkStructure`] of this shape
+    ///
+    /// There are no gates & lookup arguments, so only shapes from [`sps_shape`] can be used in
+    /// [`PlonkStructure::run_sps_protocol`]
+    pub fn plonk_structure<F: PrimeField>(&self) -> PlonkStructure<F> {
+        PlonkStructure {
+            k: self.k,
+            num_io: self.num_io.clone(),
+            num_advice_columns: self.num_advice_columns,
+            num_challenges: self.num_challenges,
+            round_sizes: self.round_sizes(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Small [`Shape`] with up to 3 challenges
+pub fn shape() -> impl Strategy<Value = Shape> {
+    shape_with_challenges(0..=3)
+}
+
+/// Small [`Shape`] with at most one challenge, so [`PlonkStructure::run_sps_protocol`] does not
+/// require lookup arguments
+pub fn sps_shape() -> impl Strategy<Value = Shape> {
+    shape_with_challenges(0..=1)
+}
+
+fn shape_with_challenges(
+    num_challenges: impl Strategy<Value = usize>,
+) -> impl Strategy<Value = Shape> {
+    (
+        1..=4usize,
+        vec(0..4usize, 0..=2),
+        1..=3usize,
+        num_challenges,
+    )
+        .prop_map(|(k, num_io, num_advice_columns, num_challenges)| Shape {
+            k,
+            num_io: num_io.into_boxed_slice(),
+            num_advice_columns,
+            num_challenges,
+        })
+}
+
+/// Field element, shrinks towards zero
+pub fn field<F: PrimeField>() -> impl Strategy<Value = F> {
+    any::<u128>().prop_map(F::from_u128)
+}
+
+/// Multiple of the curve generator
+pub fn point<C: CurveAffine>() -> impl Strategy<Value = C> {
+    field::<C::ScalarExt>().prop_map(|scalar| best_multiexp(&[scalar], &[C::generator()]).into())
+}
+
+/// Polynomial with `1..=max_degree + 1` coefficients
+pub fn univariate_poly<F: PrimeField>(
+    max_degree: usize,
+) -> impl Strategy<Value = UnivariatePoly<F>> {
+    vec(field::<F>(), 1..=max_degree + 1)
+        .prop_map(|coeffs| UnivariatePoly(coeffs.into_boxed_slice()))
+}
+
+pub(crate) fn poly_challenges<F: PrimeField>(
+    betas_count: usize,
+) -> impl Strategy<Value = PolyChallenges<F>> {
+    (vec(field::<F>(), betas_count), field::<F>(), field::<F>()).prop_map(
+        |(betas, alpha, delta)| PolyChallenges {
+            betas: betas.into_boxed_slice(),
+            alpha,
+            delta,
+        },
+    )
+}
+
+/// Instance columns of the `shape`
+pub fn instances<F: PrimeField>(shape: &Shape) -> impl Strategy<Value = Vec<Vec<F>>> {
+    shape
+        .num_io
+        .iter()
+        .map(|len| vec(field::<F>(), *len))
+        .collect::<Vec<_>>()
+}
+
+/// Advice columns of the `shape`, each of them with `2^k` rows
+pub fn advice<F: PrimeField>(shape: &Shape) -> impl Strategy<Value = Vec<Vec<F>>> {
+    vec(vec(field::<F>(), 1 << shape.k), shape.num_advice_columns)
+}
+
+pub fn plonk_instance<C: CurveAffine>(shape: &Shape) -> impl Strategy<Value = PlonkInstance<C>> {
+    (
+        vec(point::<C>(), shape.rounds()),
+        instances::<C::ScalarExt>(shape),
+        vec(field::<C::ScalarExt>(), shape.num_challenges),
+    )
+        .prop_map(|(W_commitments, instances, challenges)| PlonkInstance {
+            W_commitments,
+            instances,
+            challenges,
+        })
+}
+
+pub fn plonk_witness<F: PrimeField>(shape: &Shape) -> impl Strategy<Value = PlonkWitness<F>> {
+    shape
+        .round_sizes()
+        .into_iter()
+        .map(|size| vec(field::<F>(), size))
+        .collect::<Vec<_>>()
+        .prop_map(|W| PlonkWitness { W })
+}
+
+pub fn plonk_trace<C: CurveAffine>(shape: &Shape) -> impl Strategy<Value = PlonkTrace<C>> {
+    (
+        plonk_instance::<C>(shape),
+        plonk_witness::<C::ScalarExt>(shape),
+    )
+        .prop_map(|(u, w)| PlonkTrace { u, w })
+}
+
+pub fn accumulator_instance<C: CurveAffine>(
+    shape: &Shape,
+    betas_count: usize,
+) -> impl Strategy<Value = AccumulatorInstance<C>> {
+    (
+        plonk_instance::<C>(shape),
+        vec(field::<C::ScalarExt>(), betas_count),
+        field::<C::ScalarExt>(),
+    )
+        .prop_map(|(ins, betas, e)| AccumulatorInstance {
+            ins,
+            betas: betas.into_boxed_slice(),
+            e,
+        })
+}
+
+pub fn accumulator<C: CurveAffine>(
+    shape: &Shape,
+    betas_count: usize,
+) -> impl Strategy<Value = Accumulator<C>> {
+    (
+        plonk_trace::<C>(shape),
+        vec(field::<C::ScalarExt>(), betas_count),
+        field::<C::ScalarExt>(),
+    )
+        .prop_map(|(trace, betas, e)| Accumulator {
+            trace,
+            betas: betas.into_boxed_slice(),
+            e,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commitment::CommitmentKey,
+        ff::Field,
+        halo2curves::bn256::G1Affine as Affine,
+        nifs::protogalaxy::ProtoGalaxy,
+        polynomial::lagrange,
+        poseidon::{PoseidonHash, Spec},
+        sps::SpecialSoundnessVerifier,
+    };
+
+    type Scalar = <Affine as CurveAffine>::ScalarExt;
+    type Base = <Affine as CurveAffine>::Base;
+
+    /// Number of incoming instances, so `L + 1` instances folded over domain of size 4
+    const L: usize = 3;
+
+    fn ro() -> PoseidonHash<Base, 3, 2> {
+        PoseidonHash::new(Spec::new(4, 3))
+    }
+
+    fn shape_with_instances() -> impl Strategy<Value = (Shape, Vec<PlonkInstance<Affine>>)> {
+        shape().prop_flat_map(|shape| {
+            let instances = vec(plonk_instance::<Affine>(&shape), L + 1);
+            (Just(shape), instances)
+        })
+    }
+
+    fn sps_input() -> impl Strategy<Value = (Shape, Vec<Vec<Scalar>>, Vec<Vec<Scalar>>)> {
+        sps_shape().prop_flat_map(|shape| {
+            let instances = instances::<Scalar>(&shape);
+            let advice = advice::<Scalar>(&shape);
+            (Just(shape), instances, advice)
+        })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        /// Folding with lagrange coefficients in a domain point `w^i` results in `i`-th instance
+        #[test]
+        fn fold_in_domain_point((_shape, instances) in shape_with_instances()) {
+            let lagrange_domain = (L + 1).ilog2();
+
+            for (X, expected) in lagrange::iter_cyclic_subgroup::<Scalar>(lagrange_domain)
+                .zip(instances.iter())
+            {
+                let folded = ProtoGalaxy::<Affine, L>::fold_instance(
+                    instances[0].clone(),
+                    instances[1..].iter(),
+                    lagrange::iter_eval_lagrange_poly_for_cyclic_group(X, lagrange_domain),
+                );
+
+                prop_assert_eq!(&folded, expected);
+            }
+        }
+
+        /// `sps_verify` accepts output of `run_sps_protocol` & rejects it with modified challenge
+        #[test]
+        fn sps_verify_accepts_sps_output((shape, instances, advice) in sps_input()) {
+            let S = shape.plonk_structure::<Scalar>();
+            let ck = CommitmentKey::<Affine>::setup(shape.k + 2, b"sps_verify");
+
+            let mut plonk_instance = S
+                .run_sps_protocol(&ck, &instances, &advice, &mut ro())
+                .unwrap()
+                .u;
+
+            prop_assert!(plonk_instance.sps_verify(&mut ro()).is_ok());
+
+            if let Some(challenge) = plonk_instance.challenges.first_mut() {
+                *challenge += Scalar::ONE;
+                prop_assert!(plonk_instance.sps_verify(&mut ro()).is_err());
+            }
+        }
+    }
+}
@@ -0,0 +1,423 @@
+//! Synthetic circuit with controllable shape
+//!
+//! Used for benchmarks & stress tests of folding, when the shape of the circuit (table size, gates
+//! count & degree, lookups, instance width) matters more than what it computes.
+
+use std::{array, iter, marker::PhantomData};
+
+use rand::{rngs::StdRng, CryptoRng, Rng, RngCore, SeedableRng};
+
+use crate::{
+    commitment::CommitmentKey,
+    ff::PrimeField,
+    halo2_proofs::{
+        circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value},
+        plonk::{
+            Advice, Circuit, Column, ConstraintSystem, Error as Halo2PlonkError, Expression,
+            Instance, Selector, TableColumn,
+        },
+        poly::Rotation,
+    },
+    halo2curves::CurveAffine,
+    ivc::step_circuit::{StepCircuit, SynthesisError},
+    plonk::{PlonkStructure, PlonkTrace},
+    poseidon::ROTrait,
+    table::CircuitRunner,
+};
+
+/// Values of lookup tables are `0..LOOKUP_TABLE_SIZE`
+pub const LOOKUP_TABLE_SIZE: u64 = 16;
+
+/// Runtime part of the [`SyntheticCircuit`] shape
+#[derive(Debug, Clone)]
+pub struct SyntheticSpec {
+    /// `2^k` is the number of rows of the table
+    pub k: u32,
+    /// Number of rows with enabled gates & lookups
+    pub rows: usize,
+    /// Number of values in the instance column
+    pub instance_width: usize,
+    /// Seed of the witness generation
+    pub seed: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntheticConfig<const GATES: usize, const LOOKUPS: usize> {
+    inputs: Box<[Column<Advice>]>,
+    out: Column<Advice>,
+    gates: [Selector; GATES],
+    lookup_inputs: [Column<Advice>; LOOKUPS],
+    lookup: Selector,
+    tables: [TableColumn; LOOKUPS],
+    io: Column<Advice>,
+    instance: Option<Column<Instance>>,
+}
+
+/// Circuit with `GATES` gates of degree `DEGREE` & `LOOKUPS` lookup arguments
+///
+/// Halo2 calls [`Circuit::configure`] without the circuit itself, so the part of the shape that
+/// defines constraint system is const generics, the rest is in [`SyntheticSpec`].
+///
+/// - gate `i`: `q_i * (a_0 * ... * a_{DEGREE - 2} + i - out) = 0`, enabled on rows `i mod GATES`
+/// - lookup `j`: `q_lookup * l_j` in `0..LOOKUP_TABLE_SIZE`
+///
+/// Witness is random, generated from [`SyntheticSpec::seed`], & always satisfies the gates.
+pub struct SyntheticCircuit<F, const GATES: usize, const DEGREE: usize, const LOOKUPS: usize> {
+    spec: SyntheticSpec,
+    inputs: Box<[Box<[F]>]>,
+    lookup_inputs: Box<[[F; LOOKUPS]]>,
+    instance: Box<[F]>,
+}
+
+impl<F: PrimeField, const GATES: usize, const DEGREE: usize, const LOOKUPS: usize>
+    SyntheticCircuit<F, GATES, DEGREE, LOOKUPS>
+{
+    /// Convenience wrapper of [`SyntheticCircuit::from_rng`] with [`StdRng`] seeded by
+    /// [`SyntheticSpec::seed`]
+    pub fn new(spec: SyntheticSpec) -> Self {
+        let mut rng = StdRng::seed_from_u64(spec.seed);
+        Self::from_rng(spec, &mut rng)
+    }
+
+    /// Generates witness using `rng`, [`SyntheticSpec::seed`] is ignored
+    pub fn from_rng(spec: SyntheticSpec, rng: &mut (impl RngCore + CryptoRng)) -> Self {
+        assert!(GATES > 0, "at least one gate is required");
+        assert!(DEGREE >= 2, "gate degree can't be less than two");
+        assert!(
+            spec.rows + spec.instance_width < (1 << spec.k) - 10,
+            "not enough rows in table"
+        );
+
+        let inputs = iter::repeat_with(|| {
+            iter::repeat_with(|| F::random(&mut *rng))
+                .take(DEGREE - 1)
+                .collect()
+        })
+        .take(spec.rows)
+        .collect();
+
+        let lookup_inputs =
+            iter::repeat_with(|| array::from_fn(|_| F::from(rng.gen_range(0..LOOKUP_TABLE_SIZE))))
+                .take(spec.rows)
+                .collect();
+
+        let instance = iter::repeat_with(|| F::random(&mut *rng))
+            .take(spec.instance_width)
+            .collect();
+
+        Self {
+            spec,
+            inputs,
+            lookup_inputs,
+            instance,
+        }
+    }
+
+    pub fn spec(&self) -> &SyntheticSpec {
+        &self.spec
+    }
+
+    pub fn instances(&self) -> Vec<Vec<F>> {
+        vec![self.instance.to_vec()]
+    }
+
+    /// Collects [`PlonkStructure`] of this circuit & runs special soundness protocol for its
+    /// witness
+    pub fn plonk_trace<C: CurveAffine<ScalarExt = F>>(
+        self,
+        ck: &CommitmentKey<C>,
+        ro_nark: &mut impl ROTrait<C::Base>,
+    ) -> Result<(PlonkStructure<F>, PlonkTrace<C>), crate::Error> {
+        let instances = self.instances();
+        let runner = CircuitRunner::new(self.spec.k, self, instances.clone());
+
+        let S = runner.try_collect_plonk_structure()?;
+        let witness = runner.try_collect_witness()?;
+        let trace = S.run_sps_protocol(ck, &instances, &witness, ro_nark)?;
+
+        Ok((S, trace))
+    }
+
+    fn configure_columns(meta: &mut ConstraintSystem<F>) -> SyntheticConfig<GATES, LOOKUPS> {
+        assert!(DEGREE >= 2, "gate degree can't be less than two");
+
+        let inputs = iter::repeat_with(|| meta.advice_column())
+            .take(DEGREE - 1)
+            .collect::<Box<[_]>>();
+        let out = meta.advice_column();
+        let gates = array::from_fn(|_| meta.selector());
+
+        gates.iter().enumerate().for_each(|(index, gate)| {
+            meta.create_gate("synthetic", |meta| {
+                let q = meta.query_selector(*gate);
+                let product = inputs
+                    .iter()
+                    .map(|col| meta.query_advice(*col, Rotation::cur()))
+                    .reduce(|acc, input| acc * input)
+                    .expect("at least one input");
+                let out = meta.query_advice(out, Rotation::cur());
+
+                vec![q * (product + Expression::Constant(F::from(index as u64)) - out)]
+            });
+        });
+
+        let lookup_inputs = array::from_fn(|_| meta.advice_column());
+        let lookup = meta.complex_selector();
+        let tables = array::from_fn(|_| meta.lookup_table_column());
+
+        lookup_inputs
+            .iter()
+            .zip(tables.iter())
+            .for_each(|(input, table)| {
+                meta.lookup("synthetic", |meta| {
+                    let q = meta.query_selector(lookup);
+                    let input = meta.query_advice(*input, Rotation::cur());
+                    vec![(q * input, *table)]
+                });
+            });
+
+        let io = meta.advice_column();
+        meta.enable_equality(io);
+
+        SyntheticConfig {
+            inputs,
+            out,
+            gates,
+            lookup_inputs,
+            lookup,
+            tables,
+            io,
+            instance: None,
+        }
+    }
+
+    /// Assigns all rows & returns `io` cells
+    fn assign(
+        &self,
+        config: &SyntheticConfig<GATES, LOOKUPS>,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<Vec<AssignedCell<F, F>>, Halo2PlonkError> {
+        if LOOKUPS > 0 {
+            layouter.assign_table(
+                || "synthetic tables",
+                |mut table| {
+                    config.tables.iter().try_for_each(|column| {
+                        (0..LOOKUP_TABLE_SIZE).try_for_each(|value| {
+                            table.assign_cell(
+                                || "table",
+                                *column,
+                                value as usize,
+                                || Value::known(F::from(value)),
+                            )
+                        })
+                    })
+                },
+            )?;
+        }
+
+        layouter.assign_region(
+            || "synthetic",
+            |mut region| {
+                for (offset, (inputs, lookup_inputs)) in self
+                    .inputs
+                    .iter()
+                    .zip(self.lookup_inputs.iter())
+                    .enumerate()
+                {
+                    let gate_index = offset % GATES;
+                    config.gates[gate_index].enable(&mut region, offset)?;
+
+                    for (col, input) in config.inputs.iter().zip(inputs.iter()) {
+                        region.assign_advice(|| "input", *col, offset, || Value::known(*input))?;
+                    }
+
+                    let out = inputs.iter().fold(F::ONE, |acc, input| acc * input)
+                        + F::from(gate_index as u64);
+                    region.assign_advice(|| "out", config.out, offset, || Value::known(out))?;
+
+                    if LOOKUPS > 0 {
+                        config.lookup.enable(&mut region, offset)?;
+                    }
+                    for (col, input) in config.lookup_inputs.iter().zip(lookup_inputs.iter()) {
+                        region.assign_advice(
+                            || "lookup input",
+                            *col,
+                            offset,
+                            || Value::known(*input),
+                        )?;
+                    }
+                }
+
+                self.instance
+                    .iter()
+                    .enumerate()
+                    .map(|(offset, value)| {
+                        region.assign_advice(
+                            || "io",
+                            config.io,
+                            self.spec.rows + offset,
+                            || Value::known(*value),
+                        )
+                    })
+                    .collect()
+            },
+        )
+    }
+}
+
+impl<F: PrimeField, const GATES: usize, const DEGREE: usize, const LOOKUPS: usize> Circuit<F>
+    for SyntheticCircuit<F, GATES, DEGREE, LOOKUPS>
+{
+    type Config = SyntheticConfig<GATES, LOOKUPS>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.spec.clone())
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let mut config = Self::configure_columns(meta);
+
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+        config.instance = Some(instance);
+
+        config
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Halo2PlonkError> {
+        let io = self.assign(&config, &mut layouter)?;
+
+        let instance = config.instance.expect("created in `configure`");
+        io.iter()
+            .enumerate()
+            .try_for_each(|(row, cell)| layouter.constrain_instance(cell.cell(), instance, row))
+    }
+}
+
+/// [`StepCircuit`] wrapper of [`SyntheticCircuit`]
+///
+/// Step circuit can't have own instance columns, so [`SyntheticSpec::instance_width`] values are
+/// only assigned. Returns `z_in` as is.
+pub struct SyntheticStepCircuit<
+    const ARITY: usize,
+    F,
+    const GATES: usize,
+    const DEGREE: usize,
+    const LOOKUPS: usize,
+> {
+    inner: SyntheticCircuit<F, GATES, DEGREE, LOOKUPS>,
+    _p: PhantomData<[(); ARITY]>,
+}
+
+impl<
+        const ARITY: usize,
+        F: PrimeField,
+        const GATES: usize,
+        const DEGREE: usize,
+        const LOOKUPS: usize,
+    > SyntheticStepCircuit<ARITY, F, GATES, DEGREE, LOOKUPS>
+{
+    pub fn new(spec: SyntheticSpec) -> Self {
+        Self {
+            inner: SyntheticCircuit::new(spec),
+            _p: PhantomData,
+        }
+    }
+
+    pub fn from_rng(spec: SyntheticSpec, rng: &mut (impl RngCore + CryptoRng)) -> Self {
+        Self {
+            inner: SyntheticCircuit::from_rng(spec, rng),
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<
+        const ARITY: usize,
+        F: PrimeField,
+        const GATES: usize,
+        const DEGREE: usize,
+        const LOOKUPS: usize,
+    > StepCircuit<ARITY, F> for SyntheticStepCircuit<ARITY, F, GATES, DEGREE, LOOKUPS>
+{
+    type Config = SyntheticConfig<GATES, LOOKUPS>;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        SyntheticCircuit::<F, GATES, DEGREE, LOOKUPS>::configure_columns(cs)
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; ARITY],
+    ) -> Result<[AssignedCell<F, F>; ARITY], SynthesisError> {
+        self.inner.assign(&config, layouter)?;
+
+        Ok(z_i.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_test::traced_test;
+
+    use super::*;
+    use crate::{
+        commitment,
+        halo2_proofs::dev::MockProver,
+        halo2curves::bn256::G1Affine as Affine,
+        poseidon::{PoseidonHash, Spec},
+    };
+
+    type Scalar = <Affine as CurveAffine>::ScalarExt;
+    type Base = <Affine as CurveAffine>::Base;
+
+    fn ro() -> PoseidonHash<Base, 3, 2> {
+        PoseidonHash::new(Spec::new(4, 3))
+    }
+
+    fn check<const GATES: usize, const DEGREE: usize, const LOOKUPS: usize>(spec: SyntheticSpec) {
+        let circuit = SyntheticCircuit::<Scalar, GATES, DEGREE, LOOKUPS>::new(spec.clone());
+
+        MockProver::run(spec.k, &circuit, circuit.instances())
+            .unwrap()
+            .verify()
+            .unwrap();
+
+        let mut cs = ConstraintSystem::default();
+        SyntheticCircuit::<Scalar, GATES, DEGREE, LOOKUPS>::configure(&mut cs);
+        let ck = commitment::setup_smallest_key::<Affine>(spec.k, &cs, b"synthetic");
+
+        let (S, trace) = circuit.plonk_trace(&ck, &mut ro()).unwrap();
+
+        S.is_sat(&ck, &mut ro(), &trace.u, &trace.w).unwrap();
+    }
+
+    #[traced_test]
+    #[test]
+    fn gates() {
+        check::<3, 3, 0>(SyntheticSpec {
+            k: 6,
+            rows: 20,
+            instance_width: 2,
+            seed: 1,
+        });
+    }
+
+    #[traced_test]
+    #[test]
+    fn gates_with_lookups() {
+        check::<1, 2, 2>(SyntheticSpec {
+            k: 6,
+            rows: 20,
+            instance_width: 1,
+            seed: 2,
+        });
+    }
+}
@@ -88,18 +88,23 @@ impl<const A: usize, F: PrimeField, SC: StepCircuit<A, F>> Circuit<F>
             },
         )?;
 
+        let z_i = z_i.try_into().unwrap();
         let z_out = self
             .step_circuit
-            .synthesize_step(
-                config.step_circuit_config,
-                &mut layouter,
-                &z_i.try_into().unwrap(),
-            )
+            .synthesize_step(config.step_circuit_config, &mut layouter, &z_i)
             .map_err(|err| {
                 error!("error while synthesize_step in MockProver: {err:?}");
                 PlonkError::Synthesis
             })?;
 
+        #[cfg(debug_assertions)]
+        self.step_circuit
+            .validate_z_out(&z_i, &z_out)
+            .map_err(|err| {
+                error!("error while validate z_out in MockProver: {err:?}");
+                PlonkError::Synthesis
+            })?;
+
         self.last_z_out.set(z_out.map(|c| c.value().copied()));
 
         Ok(())
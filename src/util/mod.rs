@@ -9,8 +9,6 @@ use halo2_proofs::{
 };
 use itertools::Itertools;
 use num_bigint::BigUint;
-pub(crate) use rayon::current_num_threads;
-use rayon::prelude::*;
 
 use crate::{
     ff::{BatchInvert, Field, PrimeField},
@@ -19,6 +17,11 @@ use crate::{
 };
 
 pub mod mock_prover;
+pub mod parallel;
+pub mod serde_repr;
+
+pub(crate) use parallel::current_num_threads;
+use parallel::*;
 
 pub use mock_prover::MockProver;
 
@@ -158,7 +161,7 @@ where
     T: Send,
     F: Fn(T) + Send + Sync + Clone,
 {
-    rayon::scope(|scope| {
+    parallel::scope(|scope| {
         for item in iter {
             let f = f.clone();
             scope.spawn(move |_| f(item));
@@ -225,6 +228,43 @@ mod tests {
     use super::*;
     use crate::halo2curves::pasta::Fp;
 
+    #[test]
+    fn try_tree_reduce_same_as_tree_reduce() {
+        for len in 0..20 {
+            let expected = (0..len)
+                .map(|i| i.to_string())
+                .tree_reduce(|a, b| format!("({a} {b})"));
+            let actual = (0..len)
+                .map(|i| Result::<_, ()>::Ok(i.to_string()))
+                .try_tree_reduce(|a, b| Ok(format!("({a} {b})")));
+
+            assert_eq!(actual, expected.map(Ok), "len {len}");
+        }
+    }
+
+    #[test]
+    fn try_tree_reduce_stops_on_error() {
+        let mut pulled = 0;
+        let result = (0..1_000_000)
+            .inspect(|_| pulled += 1)
+            .map(|i| if i == 5 { Err(i) } else { Ok(i) })
+            .try_tree_reduce(|a, b| Ok(a + b));
+
+        assert_eq!(result, Some(Err(5)));
+        assert_eq!(pulled, 6);
+
+        let mut merged = 0;
+        let result = (0..1_000_000)
+            .map(Result::<_, &str>::Ok)
+            .try_tree_reduce(|_, _| {
+                merged += 1;
+                Err("bad merge")
+            });
+
+        assert_eq!(result, Some(Err("bad merge")));
+        assert_eq!(merged, 1);
+    }
+
     // Helper to easily create an Fp element
     fn fp(num: u64) -> Fp {
         Fp::from(num)
@@ -315,6 +355,30 @@ macro_rules! concat_vec {
     }};
 }
 
+/// Instantiates generic tests `fn name<C: CurveAffine>()` of the calling module for each curve
+///
+/// Every curve gets its own module with a `#[test]` per function, e.g.
+/// `curve_tests!([zero_f, zero_g]; bn256 => bn256::G1Affine, pallas => pasta::EpAffine)`
+#[cfg(test)]
+#[macro_export]
+macro_rules! curve_tests {
+    (@curve $name:ident => $curve:ty, [$($test:ident),* $(,)?]) => {
+        mod $name {
+            $(
+                #[test]
+                fn $test() {
+                    super::$test::<$curve>()
+                }
+            )*
+        }
+    };
+    ($tests:tt; $($name:ident => $curve:ty),* $(,)?) => {
+        $(
+            $crate::curve_tests!(@curve $name => $curve, $tests);
+        )*
+    };
+}
+
 /// A macro used for MockProver certain circuit by leveraging halo2_proofs.
 #[macro_export]
 macro_rules! run_mock_prover_test {
@@ -525,3 +589,87 @@ pub mod try_multi_product {
     }
 }
 pub use try_multi_product::{MultiCartesianProduct, MultiProductWithResults, TryMultiProduct};
+
+pub mod try_tree_reduce {
+    /// Fallible version of [`itertools::Itertools::tree_reduce`]
+    ///
+    /// The shape of the tree is the same, but the reduction stops on the first [`Err`] either of
+    /// an item or of `f`, without pulling the rest of the items
+    pub trait TryTreeReduce<T, E>: Iterator<Item = Result<T, E>> + Sized {
+        /// # Example
+        ///
+        /// ```
+        /// use crate::sirius::util::TryTreeReduce;
+        ///
+        /// let sum = (1..=4).map(Result::<_, ()>::Ok).try_tree_reduce(|a, b| Ok(a + b));
+        /// assert_eq!(sum, Some(Ok(10)));
+        ///
+        /// let mut items = vec![Ok(1), Err("bad leaf"), Ok(3)].into_iter();
+        /// let result = items.by_ref().try_tree_reduce(|a, b| Ok(a + b));
+        /// assert_eq!(result, Some(Err("bad leaf")));
+        /// assert_eq!(items.next(), Some(Ok(3)));
+        /// ```
+        fn try_tree_reduce(
+            mut self,
+            mut f: impl FnMut(T, T) -> Result<T, E>,
+        ) -> Option<Result<T, E>> {
+            match inner(usize::MAX, &mut self, &mut f) {
+                Ok(Step::Exhausted(result)) => result.map(Ok),
+                Ok(Step::Full(_)) => unreachable!("the iterator can't be longer than 2^usize::MAX"),
+                Err(err) => Some(Err(err)),
+            }
+        }
+    }
+
+    impl<T, E, I: Iterator<Item = Result<T, E>> + Sized> TryTreeReduce<T, E> for I {}
+
+    enum Step<T> {
+        /// Full subtree of the requested height
+        Full(T),
+        /// The iterator is exhausted, the result is a reduction of the remaining items if any
+        Exhausted(Option<T>),
+    }
+
+    fn inner0<T, E>(
+        it: &mut impl Iterator<Item = Result<T, E>>,
+        f: &mut impl FnMut(T, T) -> Result<T, E>,
+    ) -> Result<Step<T>, E> {
+        let Some(a) = it.next().transpose()? else {
+            return Ok(Step::Exhausted(None));
+        };
+        let Some(b) = it.next().transpose()? else {
+            return Ok(Step::Exhausted(Some(a)));
+        };
+
+        f(a, b).map(Step::Full)
+    }
+
+    fn inner<T, E>(
+        stop: usize,
+        it: &mut impl Iterator<Item = Result<T, E>>,
+        f: &mut impl FnMut(T, T) -> Result<T, E>,
+    ) -> Result<Step<T>, E> {
+        let mut x = match inner0(it, f)? {
+            Step::Full(x) => x,
+            exhausted => return Ok(exhausted),
+        };
+
+        for height in 0..stop {
+            // Try to get another tree the same size to combine it with
+            let next = if height == 0 {
+                inner0(it, f)?
+            } else {
+                inner(height, it, f)?
+            };
+
+            match next {
+                Step::Full(y) => x = f(x, y)?,
+                Step::Exhausted(None) => return Ok(Step::Exhausted(Some(x))),
+                Step::Exhausted(Some(y)) => return Ok(Step::Exhausted(Some(f(x, y)?))),
+            }
+        }
+
+        Ok(Step::Full(x))
+    }
+}
+pub use try_tree_reduce::TryTreeReduce;
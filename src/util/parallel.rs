@@ -0,0 +1,174 @@
+//! Thread-pool abstraction
+//!
+//! With the `parallel` feature (enabled by default) this module re-exports [`rayon`] API, without
+//! it the same API is executed sequentially in the current thread. This allows to use sirius on
+//! targets without threads, e.g. `wasm32-unknown-unknown`.
+
+#[cfg(feature = "parallel")]
+pub use rayon::{current_num_threads, join, prelude::*, scope};
+#[cfg(not(feature = "parallel"))]
+pub use sequential::*;
+
+use std::num::NonZeroUsize;
+
+/// Runs `op` in a dedicated pool of `num_threads` threads, so all parallel iterators inside use
+/// at most them, or in the current pool if `None`
+///
+/// Without the `parallel` feature `op` is just called in the current thread
+pub fn install<R: Send>(num_threads: Option<NonZeroUsize>, op: impl FnOnce() -> R + Send) -> R {
+    #[cfg(feature = "parallel")]
+    if let Some(num_threads) = num_threads {
+        match rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads.get())
+            .build()
+        {
+            Ok(pool) => return pool.install(op),
+            Err(err) => {
+                tracing::warn!("pool of {num_threads} threads is not built, current is used: {err}")
+            }
+        }
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = num_threads;
+
+    op()
+}
+
+#[cfg(not(feature = "parallel"))]
+mod sequential {
+    use std::iter;
+
+    use itertools::ZipEq;
+
+    pub fn current_num_threads() -> usize {
+        1
+    }
+
+    pub fn join<A, B, RA, RB>(oper_a: A, oper_b: B) -> (RA, RB)
+    where
+        A: FnOnce() -> RA,
+        B: FnOnce() -> RB,
+    {
+        (oper_a(), oper_b())
+    }
+
+    pub struct Scope;
+
+    impl Scope {
+        pub fn spawn<BODY: FnOnce(&Scope)>(&self, body: BODY) {
+            body(self)
+        }
+    }
+
+    pub fn scope<OP: FnOnce(&Scope) -> R, R>(op: OP) -> R {
+        op(&Scope)
+    }
+
+    /// Sequential replacement of rayon parallel iterator
+    ///
+    /// Intentionally does not implement [`Iterator`], so rayon-like methods (e.g. `zip_eq`) don't
+    /// conflict with [`Iterator`] & [`itertools::Itertools`] ones
+    pub struct SeqIter<I>(I);
+
+    impl<I: Iterator> SeqIter<I> {
+        pub fn map<R, F: FnMut(I::Item) -> R>(self, f: F) -> SeqIter<iter::Map<I, F>> {
+            SeqIter(self.0.map(f))
+        }
+
+        pub fn enumerate(self) -> SeqIter<iter::Enumerate<I>> {
+            SeqIter(self.0.enumerate())
+        }
+
+        pub fn zip_eq<J: Iterator>(self, other: SeqIter<J>) -> SeqIter<ZipEq<I, J>> {
+            SeqIter(itertools::zip_eq(self.0, other.0))
+        }
+
+        pub fn flat_map_iter<U, F>(self, f: F) -> SeqIter<iter::FlatMap<I, U, F>>
+        where
+            U: IntoIterator,
+            F: FnMut(I::Item) -> U,
+        {
+            SeqIter(self.0.flat_map(f))
+        }
+
+        pub fn for_each<F: FnMut(I::Item)>(self, f: F) {
+            self.0.for_each(f)
+        }
+
+        pub fn all<F: FnMut(I::Item) -> bool>(mut self, f: F) -> bool {
+            self.0.all(f)
+        }
+
+        pub fn collect<C: FromIterator<I::Item>>(self) -> C {
+            self.0.collect()
+        }
+
+        pub fn collect_into_vec(self, target: &mut Vec<I::Item>) {
+            target.clear();
+            target.extend(self.0);
+        }
+
+        pub fn unzip<A, B, FromA, FromB>(self) -> (FromA, FromB)
+        where
+            I: Iterator<Item = (A, B)>,
+            FromA: Default + Extend<A>,
+            FromB: Default + Extend<B>,
+        {
+            self.0.unzip()
+        }
+
+        pub fn try_reduce<T, E, ID, OP>(mut self, identity: ID, op: OP) -> Result<T, E>
+        where
+            I: Iterator<Item = Result<T, E>>,
+            ID: Fn() -> T,
+            OP: Fn(T, T) -> Result<T, E>,
+        {
+            self.0.try_fold(identity(), |acc, item| op(acc, item?))
+        }
+    }
+
+    pub trait IntoParallelIterator {
+        type Iter: Iterator<Item = Self::Item>;
+        type Item;
+
+        fn into_par_iter(self) -> SeqIter<Self::Iter>;
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I {
+        type Iter = I::IntoIter;
+        type Item = I::Item;
+
+        fn into_par_iter(self) -> SeqIter<Self::Iter> {
+            SeqIter(self.into_iter())
+        }
+    }
+
+    pub trait IntoParallelRefIterator<'data> {
+        type Iter: Iterator<Item = Self::Item>;
+        type Item: 'data;
+
+        fn par_iter(&'data self) -> SeqIter<Self::Iter>;
+    }
+
+    impl<'data, I: 'data + ?Sized> IntoParallelRefIterator<'data> for I
+    where
+        &'data I: IntoIterator,
+    {
+        type Iter = <&'data I as IntoIterator>::IntoIter;
+        type Item = <&'data I as IntoIterator>::Item;
+
+        fn par_iter(&'data self) -> SeqIter<Self::Iter> {
+            SeqIter(self.into_iter())
+        }
+    }
+
+    pub trait ParallelBridge: Sized {
+        fn par_bridge(self) -> SeqIter<Self>;
+    }
+
+    impl<I: Iterator> ParallelBridge for I {
+        fn par_bridge(self) -> SeqIter<Self> {
+            SeqIter(self)
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! `serde` helpers for field elements & points, to be used with `#[serde(with = "...")]`
+//!
+//! Field elements are encoded as big-endian bytes of [`PrimeField::Repr`] (which is little-endian
+//! for all curves of `halo2curves`), points as bytes of the compressed [`GroupEncoding`].
+//!
+//! Human-readable formats (e.g. JSON) get a hex string, binary formats (e.g. bincode) get a
+//! fixed-size tuple of bytes without a length prefix, so the size of the element is exactly the
+//! size of its repr.
+
+use std::fmt;
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{ff::PrimeField, group::GroupEncoding, halo2curves::CurveAffine};
+
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Returns `None` if `value` is not a hex string of exactly `output.len()` bytes
+pub(crate) fn decode_hex(value: &str, output: &mut [u8]) -> Option<()> {
+    if value.len() != output.len() * 2 {
+        return None;
+    }
+
+    output.iter_mut().enumerate().try_for_each(|(i, byte)| {
+        *byte = value
+            .get(2 * i..2 * i + 2)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())?;
+        Some(())
+    })
+}
+
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        return serializer.serialize_str(&encode_hex(bytes));
+    }
+
+    let mut tuple = serializer.serialize_tuple(bytes.len())?;
+    for byte in bytes {
+        tuple.serialize_element(byte)?;
+    }
+    tuple.end()
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(
+    output: &mut [u8],
+    deserializer: D,
+) -> Result<(), D::Error> {
+    struct BytesVisitor<'o> {
+        output: &'o mut [u8],
+    }
+
+    impl<'de> Visitor<'de> for BytesVisitor<'_> {
+        type Value = ();
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "{} bytes", self.output.len())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+            let len = self.output.len();
+            for (i, byte) in self.output.iter_mut().enumerate() {
+                *byte = seq.next_element()?.ok_or_else(|| {
+                    de::Error::invalid_length(i, &format!("{len} bytes").as_str())
+                })?;
+            }
+            Ok(())
+        }
+    }
+
+    if deserializer.is_human_readable() {
+        let value = String::deserialize(deserializer)?;
+        return decode_hex(&value, output)
+            .ok_or_else(|| de::Error::custom(format!("invalid hex string: {value}")));
+    }
+
+    let len = output.len();
+    deserializer.deserialize_tuple(len, BytesVisitor { output })
+}
+
+/// Single field element
+pub mod field {
+    use super::*;
+
+    pub fn serialize<F: PrimeField, S: Serializer>(
+        value: &F,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut repr = value.to_repr();
+        repr.as_mut().reverse();
+        serialize_bytes(repr.as_ref(), serializer)
+    }
+
+    pub fn deserialize<'de, F: PrimeField, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<F, D::Error> {
+        let mut repr = F::Repr::default();
+        deserialize_bytes(repr.as_mut(), deserializer)?;
+        repr.as_mut().reverse();
+
+        Option::from(F::from_repr(repr))
+            .ok_or_else(|| de::Error::custom("invalid encoding of field element"))
+    }
+}
+
+/// Sequence of field elements, e.g. `Vec<F>` or `Box<[F]>`
+pub mod fields {
+    use super::*;
+
+    pub fn serialize<F: PrimeField, S: Serializer>(
+        values: &[F],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(FieldRef))
+    }
+
+    pub fn deserialize<'de, F: PrimeField, T: FromIterator<F>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        Ok(Vec::<FieldOwned<F>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|FieldOwned(value)| value)
+            .collect())
+    }
+}
+
+/// Sequence of sequences of field elements, e.g. columns of witness
+pub mod nested_fields {
+    use super::*;
+
+    pub fn serialize<F: PrimeField, S: Serializer>(
+        values: &[Vec<F>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(|column| FieldsRef(column)))
+    }
+
+    pub fn deserialize<'de, F: PrimeField, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<F>>, D::Error> {
+        Ok(Vec::<FieldsOwned<F>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|FieldsOwned(column)| column)
+            .collect())
+    }
+}
+
+/// Single point
+pub mod point {
+    use super::*;
+
+    pub fn serialize<C: CurveAffine, S: Serializer>(
+        value: &C,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_bytes(value.to_bytes().as_ref(), serializer)
+    }
+
+    pub fn deserialize<'de, C: CurveAffine, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<C, D::Error> {
+        let mut repr = C::Repr::default();
+        deserialize_bytes(repr.as_mut(), deserializer)?;
+
+        Option::from(C::from_bytes(&repr))
+            .ok_or_else(|| de::Error::custom("invalid encoding of point"))
+    }
+}
+
+/// Sequence of points, e.g. `Vec<C>` or `Box<[C]>`
+pub mod points {
+    use super::*;
+
+    pub fn serialize<C: CurveAffine, S: Serializer>(
+        values: &[C],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(PointRef))
+    }
+
+    pub fn deserialize<'de, C: CurveAffine, T: FromIterator<C>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        Ok(Vec::<PointOwned<C>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|PointOwned(value)| value)
+            .collect())
+    }
+}
+
+struct FieldRef<'l, F>(&'l F);
+
+impl<F: PrimeField> Serialize for FieldRef<'_, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        field::serialize(self.0, serializer)
+    }
+}
+
+/// Field element deserialized by [`field::deserialize`], e.g. as an element of a sequence
+pub(crate) struct FieldOwned<F>(pub(crate) F);
+
+impl<'de, F: PrimeField> Deserialize<'de> for FieldOwned<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        field::deserialize(deserializer).map(Self)
+    }
+}
+
+struct FieldsRef<'l, F>(&'l [F]);
+
+impl<F: PrimeField> Serialize for FieldsRef<'_, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fields::serialize(self.0, serializer)
+    }
+}
+
+struct FieldsOwned<F>(Vec<F>);
+
+impl<'de, F: PrimeField> Deserialize<'de> for FieldsOwned<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        fields::deserialize(deserializer).map(Self)
+    }
+}
+
+struct PointRef<'l, C>(&'l C);
+
+impl<C: CurveAffine> Serialize for PointRef<'_, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        point::serialize(self.0, serializer)
+    }
+}
+
+struct PointOwned<C>(C);
+
+impl<'de, C: CurveAffine> Deserialize<'de> for PointOwned<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        point::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{
+        ff::Field,
+        group::{prime::PrimeCurveAffine, Curve},
+        halo2curves::bn256::{Fr, G1Affine},
+    };
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Sample {
+        #[serde(with = "field")]
+        field: Fr,
+        #[serde(with = "fields")]
+        fields: Box<[Fr]>,
+        #[serde(with = "nested_fields")]
+        nested: Vec<Vec<Fr>>,
+        #[serde(with = "points")]
+        points: Vec<G1Affine>,
+    }
+
+    fn sample() -> Sample {
+        let field = || Fr::random(rand::thread_rng());
+        Sample {
+            field: field(),
+            fields: Box::new([field(), field()]),
+            nested: vec![vec![field()], vec![], vec![field(), field()]],
+            points: vec![
+                (G1Affine::generator() * field()).to_affine(),
+                G1Affine::identity(),
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let sample = sample();
+
+        let bytes = bincode::serialize(&sample).unwrap();
+        assert_eq!(bincode::deserialize::<Sample>(&bytes).unwrap(), sample);
+
+        let json = serde_json::to_string(&sample).unwrap();
+        assert_eq!(serde_json::from_str::<Sample>(&json).unwrap(), sample);
+    }
+
+    #[test]
+    fn big_endian() {
+        #[derive(Serialize)]
+        struct Wrapper(#[serde(with = "field")] Fr);
+
+        let mut expected = [0; 32];
+        expected[31] = 1;
+
+        assert_eq!(bincode::serialize(&Wrapper(Fr::ONE)).unwrap(), expected);
+        assert_eq!(
+            serde_json::to_string(&Wrapper(Fr::ONE)).unwrap(),
+            format!("\"{}\"", encode_hex(&expected))
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Memory accounting of [`ProtoGalaxy::prove`] checked against the real allocations
+//!
+//! The counting `#[global_allocator]` replaces the allocator of the whole binary, so this check
+//! lives in its own test binary instead of the unit tests of the crate
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    array,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use sirius::{
+    commitment::CommitmentKey,
+    group::prime::PrimeCurveAffine,
+    halo2curves::{bn256::G1Affine as Affine, CurveAffine},
+    nifs::protogalaxy::{
+        memory::{Buffer, MemoryAccounting, MemoryObserver, MemorySample, MemorySampler, Phase},
+        AccumulatorArgs, ProtoGalaxy,
+    },
+    poseidon::{PoseidonHash, Spec},
+    testing::synthetic::{SyntheticCircuit, SyntheticSpec},
+};
+
+type Scalar = <Affine as CurveAffine>::ScalarExt;
+type Base = <Affine as CurveAffine>::Base;
+
+const L: usize = 3;
+const K: u32 = 6;
+
+/// Share of the phase allocations, that is not reported as [`Buffer`]s: lagrange coefficients,
+/// iterators state, spare capacity of vectors & ifft
+const UNREPORTED_PERCENT: usize = 50;
+
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts live bytes allocated by all threads, including the ones of the rayon pool
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let live = LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+struct CountingSampler;
+
+impl MemorySampler for CountingSampler {
+    fn sample(&self) -> MemorySample {
+        MemorySample {
+            current: LIVE_BYTES.load(Ordering::Relaxed),
+            peak: PEAK_BYTES.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Recorder {
+    phases: Mutex<Vec<(Phase, bool, MemorySample)>>,
+    buffers: Mutex<Vec<(Phase, Buffer, usize)>>,
+}
+
+impl MemoryObserver for Recorder {
+    fn on_phase_start(&self, phase: Phase, sample: MemorySample) {
+        // Peak of the phase, not of everything allocated before it
+        PEAK_BYTES.store(LIVE_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.phases.lock().unwrap().push((phase, true, sample));
+    }
+    fn on_phase_end(&self, phase: Phase, sample: MemorySample) {
+        self.phases.lock().unwrap().push((phase, false, sample));
+    }
+    fn on_buffer(&self, phase: Phase, buffer: Buffer, bytes: usize) {
+        self.buffers.lock().unwrap().push((phase, buffer, bytes));
+    }
+}
+
+fn ro() -> PoseidonHash<Base, 3, 2> {
+    PoseidonHash::new(Spec::new(4, 3))
+}
+
+#[test]
+fn reported_buffers_fit_into_allocations() {
+    let ck = CommitmentKey::<Affine>::setup(12, b"memory_observer");
+
+    let traces = array::from_fn::<_, L, _>(|seed| {
+        SyntheticCircuit::<Scalar, 2, 3, 0>::new(SyntheticSpec {
+            k: K,
+            rows: 1 << (K - 1),
+            instance_width: 1,
+            seed: seed as u64,
+        })
+        .plonk_trace(&ck, &mut ro())
+        .unwrap()
+    });
+    let structure = traces[0].0.clone();
+    let traces = traces.map(|(_, trace)| trace);
+
+    let recorder = Arc::new(Recorder::default());
+    let (pp, _vp) =
+        ProtoGalaxy::<Affine, L>::setup_params(Affine::identity(), structure.clone()).unwrap();
+    let pp = pp.with_memory_accounting(MemoryAccounting::new(
+        recorder.clone(),
+        Arc::new(CountingSampler),
+    ));
+
+    let accumulator = ProtoGalaxy::<Affine, L>::new_accumulator(
+        AccumulatorArgs::from(&structure),
+        &pp,
+        &mut ro(),
+    );
+    ProtoGalaxy::<Affine, L>::prove(&ck, &pp, &mut ro(), accumulator, &traces).unwrap();
+
+    let phases = recorder.phases.lock().unwrap();
+    for phase in [Phase::ComputeF, Phase::ComputeG, Phase::ComputeK] {
+        assert!(phases
+            .iter()
+            .any(|(p, is_start, _)| *p == phase && *is_start));
+        assert!(phases
+            .iter()
+            .any(|(p, is_start, _)| *p == phase && !*is_start));
+    }
+
+    let (_, _, start) = phases
+        .iter()
+        .find(|(phase, is_start, _)| *phase == Phase::ComputeG && *is_start)
+        .unwrap();
+    let (_, _, end) = phases
+        .iter()
+        .rfind(|(phase, is_start, _)| *phase == Phase::ComputeG && !*is_start)
+        .unwrap();
+    let allocated = end.peak.saturating_sub(start.current);
+
+    let buffers = recorder.buffers.lock().unwrap();
+    let reported_of = |expected: Buffer| {
+        buffers
+            .iter()
+            .filter(|(phase, buffer, _)| *phase == Phase::ComputeG && *buffer == expected)
+            .map(|(_, _, bytes)| *bytes)
+            .max()
+            .unwrap_or_else(|| panic!("{expected:?} not reported"))
+    };
+    // Both buffers are alive at once, while the tree is reduced
+    let reported = reported_of(Buffer::FoldedWitness) + reported_of(Buffer::NodePoints);
+
+    assert!(reported > 0);
+    assert!(
+        reported <= allocated,
+        "reported {reported} bytes, but only {allocated} were allocated in the phase"
+    );
+    let tolerance = allocated * UNREPORTED_PERCENT / 100;
+    assert!(
+        allocated - reported <= tolerance,
+        "reported {reported} bytes of {allocated} allocated in the phase, more than {tolerance} \
+         bytes are missed"
+    );
+}
@@ -0,0 +1,71 @@
+//! Smoke test of [`ProtoGalaxy::verify`] on `wasm32-unknown-unknown`
+//!
+//! Run with `wasm-pack test --node -- --no-default-features --test wasm_smoke`, on other targets
+//! the binary is empty. The fold is proven in the test itself, sequentially, so the prover is
+//! covered on wasm too.
+
+#![cfg(target_arch = "wasm32")]
+
+use std::array;
+
+use sirius::{
+    commitment::CommitmentKey,
+    group::prime::PrimeCurveAffine,
+    halo2curves::{bn256::G1Affine as Affine, CurveAffine},
+    nifs::protogalaxy::{AccumulatorArgs, AccumulatorInstance, ProtoGalaxy},
+    poseidon::{PoseidonHash, Spec},
+    testing::synthetic::{SyntheticCircuit, SyntheticSpec},
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+type Scalar = <Affine as CurveAffine>::ScalarExt;
+type Base = <Affine as CurveAffine>::Base;
+
+const L: usize = 2;
+const K: u32 = 5;
+
+fn ro() -> PoseidonHash<Base, 3, 2> {
+    PoseidonHash::new(Spec::new(4, 3))
+}
+
+#[wasm_bindgen_test]
+fn verify_fold() {
+    let ck = CommitmentKey::<Affine>::setup(K as usize, b"wasm_smoke");
+
+    let mut ro_nark = ro();
+    let traces = array::from_fn::<_, L, _>(|seed| {
+        SyntheticCircuit::<Scalar, 1, 2, 0>::new(SyntheticSpec {
+            k: K,
+            rows: 1 << (K - 1),
+            instance_width: 1,
+            seed: seed as u64,
+        })
+        .plonk_trace(&ck, &mut ro_nark)
+        .unwrap()
+    });
+    let structure = traces[0].0.clone();
+    let traces = traces.map(|(_, trace)| trace);
+
+    let (pp, vp) =
+        ProtoGalaxy::<Affine, L>::setup_params(Affine::identity(), structure.clone()).unwrap();
+    let accumulator = ProtoGalaxy::<Affine, L>::new_accumulator(
+        AccumulatorArgs::from(&structure),
+        &pp,
+        &mut ro(),
+    );
+
+    let (folded, proof) =
+        ProtoGalaxy::<Affine, L>::prove(&ck, &pp, &mut ro(), accumulator.clone(), &traces).unwrap();
+
+    let verified = ProtoGalaxy::<Affine, L>::verify(
+        &vp,
+        &mut ro(),
+        &mut ro(),
+        &AccumulatorInstance::from(accumulator),
+        &traces.map(|trace| trace.u),
+        &proof,
+    )
+    .unwrap();
+
+    assert_eq!(verified, AccumulatorInstance::from(folded));
+}